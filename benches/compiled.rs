@@ -0,0 +1,59 @@
+//! Compares ticking a wide, deep tree through `BehaviorRunner` and through
+//! `CompiledRunner`
+//!
+//! Run with `cargo bench --bench compiled`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use simple_bt::compiled::{compile, CompiledRunner};
+use simple_bt::composite::{Inverter, Selector, Sequence};
+use simple_bt::testing::{AlwaysFail, SucceedAfter};
+use simple_bt::{BehaviorArc, BehaviorNode, BehaviorRunner};
+
+const DEPTH: u32 = 6;
+const WIDTH: u32 = 4;
+const PROCEEDS: u32 = 2_000;
+
+/// Alternates sequences and selectors, with running leaves at the bottom
+fn tree(depth: u32, index: u32) -> BehaviorArc<()> {
+    if depth == 0 {
+        return SucceedAfter(index % 3).arc();
+    }
+    let mut children = (0..WIDTH)
+        .map(|child| tree(depth - 1, index * WIDTH + child))
+        .collect::<Vec<_>>();
+    if depth.is_multiple_of(2) {
+        children.insert(0, Inverter::new(AlwaysFail.arc()).arc());
+        Sequence::new(children).arc()
+    } else {
+        children.insert(0, AlwaysFail.arc());
+        Selector::new(children).arc()
+    }
+}
+
+fn time(mut proceed: impl FnMut() -> Option<bool>) -> (Duration, u32) {
+    let start = Instant::now();
+    let mut finished = 0;
+    for _ in 0..PROCEEDS {
+        if black_box(proceed()).is_some() {
+            finished += 1;
+        }
+    }
+    (start.elapsed(), finished)
+}
+
+fn main() {
+    let root = tree(DEPTH, 0);
+    let compiled = compile(&root);
+
+    let mut interpreted = BehaviorRunner::new(root);
+    let (interpreted_time, interpreted_finished) = time(|| interpreted.proceed(&mut ()));
+    let mut runner = CompiledRunner::new(compiled.clone());
+    let (compiled_time, compiled_finished) = time(|| runner.proceed(&mut ()));
+
+    assert_eq!(interpreted_finished, compiled_finished);
+    println!("{PROCEEDS} proceeds over {} instructions", compiled.len());
+    println!("interpreted: {interpreted_time:?}");
+    println!("compiled:    {compiled_time:?}");
+}
@@ -0,0 +1,90 @@
+//! Compares ticking `ParallelN`, which keeps its children inline, with the
+//! equivalent `ParallelSequence` and `ParallelSelector`, which keep them in
+//! shared slices, counting the allocations each makes
+//!
+//! Run with `cargo bench --bench parallel_n`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use simple_bt::composite::{FanOutPolicy, ParallelN, ParallelSelector, ParallelSequence};
+use simple_bt::testing::SucceedAfter;
+use simple_bt::{BehaviorArc, BehaviorNode, BehaviorRunner};
+
+const WIDTH: usize = 4;
+const PROCEEDS: u32 = 200_000;
+
+/// Counts every allocation made through the global allocator
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Children that finish on different ticks, so the running copies shrink
+fn children() -> [BehaviorArc<()>; WIDTH] {
+    std::array::from_fn(|index| SucceedAfter(index as u32 + 1).arc())
+}
+
+/// Times `PROCEEDS` proceeds, returning how many finished and how many
+/// allocations they made
+fn time(mut proceed: impl FnMut() -> Option<bool>) -> (Duration, u32, usize) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let mut finished = 0;
+    for _ in 0..PROCEEDS {
+        if black_box(proceed()).is_some() {
+            finished += 1;
+        }
+    }
+    let elapsed = start.elapsed();
+    (
+        elapsed,
+        finished,
+        ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+    )
+}
+
+fn main() {
+    println!("{PROCEEDS} proceeds over {WIDTH} children");
+    for policy in [FanOutPolicy::AllSucceed, FanOutPolicy::AnySucceed] {
+        let mut inline = BehaviorRunner::from_node(ParallelN::new(policy, children()));
+        let (inline_time, inline_finished, inline_allocations) = time(|| inline.proceed(&mut ()));
+        let (name, mut sliced) = match policy {
+            FanOutPolicy::AllSucceed => (
+                "ParallelSequence",
+                BehaviorRunner::from_node(ParallelSequence::new(children())),
+            ),
+            FanOutPolicy::AnySucceed => (
+                "ParallelSelector",
+                BehaviorRunner::from_node(ParallelSelector::new(children())),
+            ),
+        };
+        let (sliced_time, sliced_finished, sliced_allocations) = time(|| sliced.proceed(&mut ()));
+
+        assert_eq!(inline_finished, sliced_finished);
+        assert!(inline_allocations < sliced_allocations);
+        println!("{policy:?}");
+        let rows = [
+            ("ParallelN", inline_time, inline_allocations),
+            (name, sliced_time, sliced_allocations),
+        ];
+        for (name, elapsed, allocations) in rows {
+            println!("  {name:<17} {elapsed:?}, {allocations} allocations");
+        }
+    }
+}
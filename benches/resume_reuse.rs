@@ -0,0 +1,108 @@
+//! Compares resuming a deep tree whose running leaf hands itself back
+//! unchanged, so every sequence and selector above it can reuse its resume,
+//! with one whose leaf allocates a new node each tick, counting the
+//! allocations each makes
+//!
+//! Resumes are only reused, not flattened: a fresh leaf still rebuilds one
+//! resume per composite above it, and this checks exactly that.
+//!
+//! Run with `cargo bench --bench resume_reuse`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use simple_bt::composite::{Inverter, Selector, Sequence};
+use simple_bt::testing::AlwaysFail;
+use simple_bt::{BehaviorArc, BehaviorNode, BehaviorRunner, NodeResult};
+
+const DEPTH: u32 = 5;
+const PROCEEDS: u32 = 200_000;
+
+/// Counts every allocation made through the global allocator
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Runs forever without recording any progress
+#[derive(Debug)]
+struct Idle;
+
+impl BehaviorNode<()> for Idle {
+    fn tick(self: Arc<Self>, _context: &mut ()) -> NodeResult<()> {
+        self.running()
+    }
+}
+
+/// Runs forever, allocating a fresh copy of itself each tick
+#[derive(Debug)]
+struct Fresh;
+
+impl BehaviorNode<()> for Fresh {
+    fn tick(self: Arc<Self>, _context: &mut ()) -> NodeResult<()> {
+        NodeResult::Running(Fresh.arc())
+    }
+}
+
+/// Alternates sequences and selectors down to `leaf`, each waiting on its
+/// second child
+fn tree(depth: u32, leaf: &BehaviorArc<()>) -> BehaviorArc<()> {
+    if depth == 0 {
+        return leaf.clone();
+    }
+    let child = tree(depth - 1, leaf);
+    if depth.is_multiple_of(2) {
+        Sequence::new([Inverter::new(AlwaysFail.arc()).arc(), child]).arc()
+    } else {
+        Selector::new([AlwaysFail.arc(), child]).arc()
+    }
+}
+
+/// Times `PROCEEDS` proceeds after the first, returning how many
+/// allocations they made
+fn time(mut proceed: impl FnMut() -> Option<bool>) -> (Duration, usize) {
+    // The first proceed builds the running spine
+    assert!(proceed().is_none());
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..PROCEEDS {
+        assert!(black_box(proceed()).is_none());
+    }
+    let elapsed = start.elapsed();
+    (elapsed, ALLOCATIONS.load(Ordering::Relaxed) - allocations)
+}
+
+fn main() {
+    let mut reused = BehaviorRunner::new(tree(DEPTH, &Idle.arc()));
+    let (reused_time, reused_allocations) = time(|| reused.proceed(&mut ()));
+    let mut rebuilt = BehaviorRunner::new(tree(DEPTH, &Fresh.arc()));
+    let (rebuilt_time, rebuilt_allocations) = time(|| rebuilt.proceed(&mut ()));
+
+    // An unchanged leaf leaves the whole spine as it was
+    assert_eq!(reused_allocations, 0);
+    // A fresh leaf costs itself plus one resume per composite
+    assert_eq!(
+        rebuilt_allocations,
+        (DEPTH as usize + 1) * PROCEEDS as usize
+    );
+
+    println!("{PROCEEDS} proceeds {DEPTH} composites deep");
+    println!("unchanged leaf: {reused_time:?}, {reused_allocations} allocations");
+    println!("fresh leaf:     {rebuilt_time:?}, {rebuilt_allocations} allocations");
+}
@@ -0,0 +1,107 @@
+//! Declared data access, for schedulers that run trees side by side
+//!
+//! Nothing in this crate checks these declarations while ticking; they are
+//! metadata for an outside scheduler deciding which trees may run at once.
+
+use std::any::TypeId;
+use std::collections::HashSet;
+
+/// The component types a node reads and writes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Access {
+    reads: HashSet<TypeId>,
+    writes: HashSet<TypeId>,
+}
+
+impl Access {
+    /// Declares a read of `T`
+    pub fn read<T: 'static>(mut self) -> Self {
+        self.reads.insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Declares a write of `T`
+    pub fn write<T: 'static>(mut self) -> Self {
+        self.writes.insert(TypeId::of::<T>());
+        self
+    }
+
+    pub fn reads(&self) -> &HashSet<TypeId> {
+        &self.reads
+    }
+
+    pub fn writes(&self) -> &HashSet<TypeId> {
+        &self.writes
+    }
+
+    /// Adds everything `other` declares to this access
+    pub fn union(&mut self, other: &Access) {
+        self.reads.extend(other.reads.iter().copied());
+        self.writes.extend(other.writes.iter().copied());
+    }
+
+    /// Whether running alongside `other` could race: either side writes a
+    /// type the other touches
+    pub fn conflicts_with(&self, other: &Access) -> bool {
+        let touches =
+            |access: &Access, ty: &TypeId| access.reads.contains(ty) || access.writes.contains(ty);
+        self.writes.iter().any(|ty| touches(other, ty))
+            || other.writes.iter().any(|ty| touches(self, ty))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use assert2::check;
+
+    use super::*;
+    use crate::composite::{Inverter, Selector, Sequence};
+    use crate::{BehaviorNode, NodeResult};
+
+    struct Position;
+    struct Velocity;
+    struct Health;
+
+    #[derive(Debug)]
+    struct Move;
+    impl BehaviorNode<()> for Move {
+        fn tick(self: Arc<Self>, _: &mut ()) -> NodeResult<()> {
+            NodeResult::Success
+        }
+
+        fn access(&self) -> Access {
+            Access::default().read::<Velocity>().write::<Position>()
+        }
+    }
+
+    #[derive(Debug)]
+    struct IsHurt;
+    impl BehaviorNode<()> for IsHurt {
+        fn tick(self: Arc<Self>, _: &mut ()) -> NodeResult<()> {
+            NodeResult::Failure
+        }
+
+        fn access(&self) -> Access {
+            Access::default().read::<Health>()
+        }
+    }
+
+    #[test]
+    fn composites_union_child_access() {
+        let tree = Selector::new([
+            Sequence::new([IsHurt.arc(), Move.arc()]).arc(),
+            Inverter::new(IsHurt.arc()).arc(),
+        ]);
+        let mut expected = Move.access();
+        expected.union(&IsHurt.access());
+        check!(tree.access() == expected);
+        check!(tree.access().reads().len() == 2);
+        check!(tree.access().writes().contains(&TypeId::of::<Position>()));
+
+        check!(Move.access().conflicts_with(&Move.access()));
+        check!(!IsHurt.access().conflicts_with(&IsHurt.access()));
+        check!(!IsHurt.access().conflicts_with(&Move.access()));
+    }
+}
@@ -0,0 +1,52 @@
+//! Measuring the shape of a tree
+//!
+//! Both walks follow [`crate::BehaviorNode::children`], so a subtree shared
+//! through the same `Arc` is counted once for every place it appears.
+
+use crate::BehaviorArc;
+
+/// How many nodes the tree has, counting the root
+pub fn node_count<B>(root: &BehaviorArc<B>) -> usize {
+    1 + root.children().iter().map(node_count).sum::<usize>()
+}
+
+/// How many nodes the longest path from the root to a leaf goes through
+///
+/// A lone leaf has a depth of 1.
+pub fn max_depth<B>(root: &BehaviorArc<B>) -> usize {
+    1 + root.children().iter().map(max_depth).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::{Inverter, Selector, Sequence},
+        testing::{AlwaysFail, AlwaysSucceed},
+        BehaviorNode,
+    };
+
+    #[test]
+    fn counts_nested_trees() {
+        let leaf: BehaviorArc<()> = AlwaysSucceed.arc();
+        check!((node_count(&leaf), max_depth(&leaf)) == (1, 1));
+
+        let tree: BehaviorArc<()> = Selector::new([
+            Sequence::new([AlwaysFail.arc(), Inverter::new(AlwaysSucceed.arc()).arc()]).arc(),
+            AlwaysSucceed.arc(),
+        ])
+        .arc();
+        check!(node_count(&tree) == 6);
+        check!(max_depth(&tree) == 4);
+    }
+
+    #[test]
+    fn shared_subtrees_count_each_time() {
+        let shared: BehaviorArc<()> = Sequence::new([AlwaysFail.arc(), AlwaysSucceed.arc()]).arc();
+        let tree = Selector::new([shared.clone(), shared]).arc();
+        check!(node_count(&tree) == 7);
+        check!(max_depth(&tree) == 3);
+    }
+}
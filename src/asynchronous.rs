@@ -0,0 +1,323 @@
+//! Trees whose nodes can `.await` while they tick
+//!
+//! Mirrors the sync API: an [`AsyncBehaviorNode`] returns an
+//! [`AsyncNodeResult`], which holds the node to tick next while running, and
+//! an [`AsyncBehaviorRunner`] drives the tree one proceed at a time. Awaiting
+//! inside a tick doesn't count as running; the proceed simply finishes once
+//! the awaited work does. Sync nodes can be mixed in with [`SyncNode`].
+//!
+//! Ticks return boxed futures so that trees can hold nodes of different
+//! types. They are `Send`, which means the blackboard has to be too.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::{BehaviorArc, NodeResult};
+
+pub type AsyncBehaviorArc<B> = Arc<dyn AsyncBehaviorNode<B>>;
+
+/// What [`AsyncBehaviorNode::tick`] returns
+pub type TickFuture<'a, B> = Pin<Box<dyn Future<Output = AsyncNodeResult<B>> + Send + 'a>>;
+
+#[derive(Debug)]
+pub enum AsyncNodeResult<B> {
+    /// The node is still running
+    ///
+    /// This contains the node to be ticked
+    Running(AsyncBehaviorArc<B>),
+    /// The node succeeded
+    Success,
+    /// The node failed
+    Failure,
+}
+
+pub trait AsyncBehaviorNode<B>: std::fmt::Debug + Send + Sync + 'static {
+    fn tick<'a>(self: Arc<Self>, context: &'a mut B) -> TickFuture<'a, B>;
+
+    fn arc(self) -> AsyncBehaviorArc<B>
+    where
+        Self: Sized,
+    {
+        Arc::new(self)
+    }
+}
+
+/// Ticks a sync node from an async tree
+pub struct SyncNode<B> {
+    node: BehaviorArc<B>,
+}
+
+impl<B> std::fmt::Debug for SyncNode<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SyncNode")
+            .field("node", &self.node)
+            .finish()
+    }
+}
+
+impl<B> SyncNode<B> {
+    pub fn new(node: BehaviorArc<B>) -> Self {
+        Self { node }
+    }
+}
+
+impl<B: Send + 'static> AsyncBehaviorNode<B> for SyncNode<B> {
+    fn tick<'a>(self: Arc<Self>, context: &'a mut B) -> TickFuture<'a, B> {
+        let res = match self.node.clone().tick(context) {
+            NodeResult::Running(node) => AsyncNodeResult::Running(SyncNode::new(node).arc()),
+            NodeResult::Success => AsyncNodeResult::Success,
+            NodeResult::Failure => AsyncNodeResult::Failure,
+        };
+        Box::pin(std::future::ready(res))
+    }
+}
+
+/// Awaits each child in turn until one fails, like [`crate::composite::Sequence`]
+pub struct AsyncSequence<B> {
+    sub: Arc<[AsyncBehaviorArc<B>]>,
+    index: usize,
+    resume: Option<AsyncBehaviorArc<B>>,
+}
+
+impl<B> std::fmt::Debug for AsyncSequence<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncSequence")
+            .field("sub", &self.sub)
+            .field("index", &self.index)
+            .field("resume", &self.resume)
+            .finish()
+    }
+}
+
+impl<B> AsyncSequence<B> {
+    pub fn new(children: impl IntoIterator<Item = AsyncBehaviorArc<B>>) -> Self {
+        Self {
+            sub: children.into_iter().collect(),
+            index: 0,
+            resume: None,
+        }
+    }
+}
+
+impl<B: Send + 'static> AsyncBehaviorNode<B> for AsyncSequence<B> {
+    fn tick<'a>(self: Arc<Self>, context: &'a mut B) -> TickFuture<'a, B> {
+        Box::pin(async move {
+            tick_children(&self.sub, self.index, self.resume.clone(), false, context).await
+        })
+    }
+}
+
+/// Awaits each child in turn until one succeeds, like
+/// [`crate::composite::Selector`]
+pub struct AsyncSelector<B> {
+    sub: Arc<[AsyncBehaviorArc<B>]>,
+    index: usize,
+    resume: Option<AsyncBehaviorArc<B>>,
+}
+
+impl<B> std::fmt::Debug for AsyncSelector<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncSelector")
+            .field("sub", &self.sub)
+            .field("index", &self.index)
+            .field("resume", &self.resume)
+            .finish()
+    }
+}
+
+impl<B> AsyncSelector<B> {
+    pub fn new(children: impl IntoIterator<Item = AsyncBehaviorArc<B>>) -> Self {
+        Self {
+            sub: children.into_iter().collect(),
+            index: 0,
+            resume: None,
+        }
+    }
+}
+
+impl<B: Send + 'static> AsyncBehaviorNode<B> for AsyncSelector<B> {
+    fn tick<'a>(self: Arc<Self>, context: &'a mut B) -> TickFuture<'a, B> {
+        Box::pin(async move {
+            tick_children(&self.sub, self.index, self.resume.clone(), true, context).await
+        })
+    }
+}
+
+/// Ticks `sub` from `index` on, resuming there if asked, until a child
+/// finishes with `stop_on_success` (selectors) or its opposite (sequences)
+async fn tick_children<B: Send + 'static>(
+    sub: &Arc<[AsyncBehaviorArc<B>]>,
+    index: usize,
+    resume: Option<AsyncBehaviorArc<B>>,
+    stop_on_success: bool,
+    context: &mut B,
+) -> AsyncNodeResult<B> {
+    let mut next = resume;
+    for idx in index..sub.len() {
+        let child = next.take().unwrap_or_else(|| sub[idx].clone());
+        let success = match child.tick(context).await {
+            AsyncNodeResult::Running(resume) => {
+                let (sub, index, resume) = (sub.clone(), idx, Some(resume));
+                return AsyncNodeResult::Running(if stop_on_success {
+                    AsyncSelector { sub, index, resume }.arc()
+                } else {
+                    AsyncSequence { sub, index, resume }.arc()
+                });
+            }
+            AsyncNodeResult::Success => true,
+            AsyncNodeResult::Failure => false,
+        };
+        if success == stop_on_success {
+            return finished(success);
+        }
+    }
+    finished(!stop_on_success)
+}
+
+fn finished<B>(success: bool) -> AsyncNodeResult<B> {
+    if success {
+        AsyncNodeResult::Success
+    } else {
+        AsyncNodeResult::Failure
+    }
+}
+
+/// Drives an async tree, like a [`crate::BehaviorRunner`] without the extras
+pub struct AsyncBehaviorRunner<B> {
+    tree: AsyncBehaviorArc<B>,
+    current_tick: Option<AsyncBehaviorArc<B>>,
+}
+
+impl<B> std::fmt::Debug for AsyncBehaviorRunner<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncBehaviorRunner")
+            .field("current_tick", &self.current_tick)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B: Send + 'static> AsyncBehaviorRunner<B> {
+    pub fn new(tree: AsyncBehaviorArc<B>) -> Self {
+        Self {
+            tree,
+            current_tick: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.current_tick.is_some()
+    }
+
+    pub fn reset(&mut self) {
+        self.current_tick = None;
+    }
+
+    /// Same contract as [`crate::BehaviorRunner::proceed`]
+    ///
+    /// Cancellation safe: the running node is only replaced once its tick
+    /// finishes, so dropping the future early leaves the runner where it was
+    /// and the next proceed ticks the same node again.
+    pub async fn proceed(&mut self, context: &mut B) -> Option<bool> {
+        let node = self
+            .current_tick
+            .clone()
+            .unwrap_or_else(|| self.tree.clone());
+        let (current_tick, res) = match node.tick(context).await {
+            AsyncNodeResult::Running(resume) => (Some(resume), None),
+            AsyncNodeResult::Success => (None, Some(true)),
+            AsyncNodeResult::Failure => (None, Some(false)),
+        };
+        self.current_tick = current_tick;
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::{Context, Poll, Waker};
+
+    use assert2::check;
+
+    use super::*;
+    use crate::{testing::SucceedAfter, BehaviorNode};
+
+    /// Pushes its number once a yield comes back
+    #[derive(Debug)]
+    struct Fetch(i32, bool);
+
+    impl AsyncBehaviorNode<Vec<i32>> for Fetch {
+        fn tick<'a>(self: Arc<Self>, fetched: &'a mut Vec<i32>) -> TickFuture<'a, Vec<i32>> {
+            Box::pin(async move {
+                tokio::task::yield_now().await;
+                fetched.push(self.0);
+                finished(self.1)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn sequences_await_each_child() {
+        let mut runner = AsyncBehaviorRunner::new(
+            AsyncSequence::new([
+                Fetch(1, true).arc(),
+                SyncNode::new(SucceedAfter(1).arc()).arc(),
+                Fetch(2, true).arc(),
+            ])
+            .arc(),
+        );
+        let mut fetched = vec![];
+        check!(runner.proceed(&mut fetched).await == None);
+        check!(fetched == [1]);
+        check!(runner.proceed(&mut fetched).await == Some(true));
+        check!(fetched == [1, 2]);
+        check!(!runner.is_running());
+    }
+
+    #[tokio::test]
+    async fn selectors_stop_at_the_first_success() {
+        let mut runner = AsyncBehaviorRunner::new(
+            AsyncSelector::new([
+                Fetch(1, false).arc(),
+                Fetch(2, true).arc(),
+                Fetch(3, true).arc(),
+            ])
+            .arc(),
+        );
+        let mut fetched = vec![];
+        check!(runner.proceed(&mut fetched).await == Some(true));
+        check!(fetched == [1, 2]);
+    }
+
+    #[test]
+    fn dropping_a_proceed_keeps_the_running_node() {
+        let mut runner = AsyncBehaviorRunner::new(
+            AsyncSequence::new([
+                SyncNode::new(SucceedAfter(1).arc()).arc(),
+                Fetch(2, true).arc(),
+            ])
+            .arc(),
+        );
+        let mut fetched = vec![];
+        let mut cx = Context::from_waker(Waker::noop());
+        check!(
+            Box::pin(runner.proceed(&mut fetched))
+                .as_mut()
+                .poll(&mut cx)
+                == Poll::Ready(None)
+        );
+
+        // Give up on the proceed while the fetch is still waiting
+        let mut proceed = Box::pin(runner.proceed(&mut fetched));
+        check!(proceed.as_mut().poll(&mut cx).is_pending());
+        drop(proceed);
+        check!(runner.is_running());
+
+        // The sequence picks up where it was rather than starting over
+        let mut proceed = Box::pin(runner.proceed(&mut fetched));
+        check!(proceed.as_mut().poll(&mut cx).is_pending());
+        check!(proceed.as_mut().poll(&mut cx) == Poll::Ready(Some(true)));
+        drop(proceed);
+        check!(fetched == [2]);
+    }
+}
@@ -0,0 +1,108 @@
+//! Driving behavior trees from a [bevy](https://bevyengine.org) app
+//!
+//! The blackboard is a component on the same entity as the
+//! [`BehaviorRunnerComponent`], and [`tick_behavior_trees`] proceeds every
+//! runner once per frame against it.
+
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin, Update};
+use bevy_ecs::{component::Mutable, prelude::*};
+
+use crate::{BehaviorArc, BehaviorRunner};
+
+/// A [`BehaviorRunner`] attached to an entity
+#[derive(Component)]
+pub struct BehaviorRunnerComponent<B: Component>(pub BehaviorRunner<B>);
+
+impl<B: Component> BehaviorRunnerComponent<B> {
+    pub fn new(tree: BehaviorArc<B>) -> Self {
+        Self(BehaviorRunner::new(tree))
+    }
+}
+
+/// Proceeds every runner once against its entity's blackboard component
+pub fn tick_behavior_trees<B: Component<Mutability = Mutable>>(
+    mut runners: Query<(&mut BehaviorRunnerComponent<B>, &mut B)>,
+) {
+    for (mut runner, mut blackboard) in &mut runners {
+        runner.0.proceed(&mut blackboard);
+    }
+}
+
+/// Adds [`tick_behavior_trees`] for the blackboard `B` to the `Update` schedule
+pub struct BehaviorTreePlugin<B>(PhantomData<fn() -> B>);
+
+impl<B> Default for BehaviorTreePlugin<B> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<B: Component<Mutability = Mutable>> Plugin for BehaviorTreePlugin<B> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, tick_behavior_trees::<B>);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use assert2::check;
+
+    use super::*;
+    use crate::{composite::Sequence, BehaviorNode, NodeResult};
+
+    #[derive(Component, Debug, Default)]
+    struct Hunger {
+        meals: u32,
+    }
+
+    #[derive(Debug)]
+    struct Eat;
+    impl BehaviorNode<Hunger> for Eat {
+        fn tick(self: Arc<Self>, context: &mut Hunger) -> NodeResult<Hunger> {
+            context.meals += 1;
+            NodeResult::Success
+        }
+    }
+
+    #[derive(Debug)]
+    struct Digest(u32);
+    impl BehaviorNode<Hunger> for Digest {
+        fn tick(self: Arc<Self>, _context: &mut Hunger) -> NodeResult<Hunger> {
+            if self.0 > 0 {
+                NodeResult::Running(Digest(self.0 - 1).arc())
+            } else {
+                NodeResult::Success
+            }
+        }
+    }
+
+    #[test]
+    fn app_ticks_runner_components() {
+        let mut app = App::new();
+        app.add_plugins(BehaviorTreePlugin::<Hunger>::default());
+        let agent = app
+            .world_mut()
+            .spawn((
+                Hunger::default(),
+                BehaviorRunnerComponent::new(Sequence::new([Eat.arc(), Digest(1).arc()]).arc()),
+            ))
+            .id();
+
+        app.update();
+        check!(app.world().get::<Hunger>(agent).unwrap().meals == 1);
+        check!(app
+            .world()
+            .get::<BehaviorRunnerComponent<Hunger>>(agent)
+            .unwrap()
+            .0
+            .is_running());
+        app.update();
+        check!(app.world().get::<Hunger>(agent).unwrap().meals == 1);
+        app.update();
+        check!(app.world().get::<Hunger>(agent).unwrap().meals == 2);
+    }
+}
@@ -0,0 +1,233 @@
+//! Short functions for building trees by nesting calls
+//!
+//! Each one wraps the matching node's constructor and returns the node
+//! already in a [`BehaviorArc`], so a tree reads as one expression:
+//!
+//! ```
+//! use simple_bt::builder::{act, cond, selector, sequence};
+//! use simple_bt::NodeResult;
+//!
+//! let tree = selector([
+//!     sequence([
+//!         cond(|hungry: &bool| *hungry),
+//!         act(|hungry: &mut bool| {
+//!             *hungry = false;
+//!             NodeResult::Success
+//!         }),
+//!     ]),
+//!     act(|_: &mut bool| NodeResult::Failure),
+//! ]);
+//! # let _ = tree;
+//! ```
+//!
+//! [`bt!`](crate::bt) goes one step further and drops the brackets and the
+//! `arc`s. Anything not covered here is built with its constructor as usual.
+
+use crate::composite::{
+    Action, Condition, Failer, Inverter, LimitedRepeated, Parallel, Repeated, Selector, Sequence,
+    Succeeder,
+};
+use crate::{BehaviorArc, BehaviorNode, NodeResult};
+
+/// A [`Sequence`] over `children`
+pub fn sequence<B: 'static>(children: impl IntoIterator<Item = BehaviorArc<B>>) -> BehaviorArc<B> {
+    Sequence::new(children).arc()
+}
+
+/// A [`Selector`] over `children`
+pub fn selector<B: 'static>(children: impl IntoIterator<Item = BehaviorArc<B>>) -> BehaviorArc<B> {
+    Selector::new(children).arc()
+}
+
+/// A [`Parallel`] over `children`, without checking the thresholds
+///
+/// See [`Parallel::new_unchecked`] for what out of range thresholds do.
+pub fn parallel<B: 'static>(
+    success_threshold: usize,
+    failure_threshold: usize,
+    children: impl IntoIterator<Item = BehaviorArc<B>>,
+) -> BehaviorArc<B> {
+    Parallel::new_unchecked(success_threshold, failure_threshold, children).arc()
+}
+
+/// An [`Inverter`] around `child`
+pub fn invert<B: 'static>(child: BehaviorArc<B>) -> BehaviorArc<B> {
+    Inverter::new(child).arc()
+}
+
+/// A [`Succeeder`] around `child`
+pub fn succeed<B: 'static>(child: BehaviorArc<B>) -> BehaviorArc<B> {
+    Succeeder::new(child).arc()
+}
+
+/// A [`Failer`] around `child`
+pub fn fail<B: 'static>(child: BehaviorArc<B>) -> BehaviorArc<B> {
+    Failer::new(child).arc()
+}
+
+/// Repeats `child` forever, see [`Repeated`]
+pub fn repeat<B: 'static>(child: BehaviorArc<B>) -> BehaviorArc<B> {
+    Repeated::new(child).arc()
+}
+
+/// Repeats `child` `n` times, see [`LimitedRepeated`]
+pub fn repeat_n<B: 'static>(n: usize, child: BehaviorArc<B>) -> BehaviorArc<B> {
+    LimitedRepeated::new(n, child).arc()
+}
+
+/// A [`Condition`] checking `check`
+pub fn cond<B: 'static>(check: impl Fn(&B) -> bool + Send + Sync + 'static) -> BehaviorArc<B> {
+    Condition::new(check).arc()
+}
+
+/// An [`Action`] running `act`
+pub fn act<B: 'static>(
+    act: impl Fn(&mut B) -> NodeResult<B> + Send + Sync + 'static,
+) -> BehaviorArc<B> {
+    Action::new(act).arc()
+}
+
+/// Builds a tree from a nested outline
+///
+/// ```
+/// use simple_bt::{bt, NodeResult};
+///
+/// let tree = bt! {
+///     selector {
+///         sequence {
+///             condition(|hungry: &bool| *hungry),
+///             action(|hungry: &mut bool| {
+///                 *hungry = false;
+///                 NodeResult::Success
+///             }),
+///         },
+///         repeat(3) { action(|_: &mut bool| NodeResult::Success) },
+///     }
+/// };
+/// # let _ = tree;
+/// ```
+///
+/// The outline is made of
+/// - `sequence { .. }`, `selector { .. }` and `parallel(success, failure) { .. }`
+///   over comma separated children,
+/// - `invert { .. }`, `repeat { .. }` and `repeat(n) { .. }` around a
+///   single child,
+/// - `condition(check)` and `action(act)` over closures,
+/// - and any other expression, which must already be a
+///   [`BehaviorArc`](crate::BehaviorArc).
+///
+/// Each maps onto the function of the same name in [`crate::builder`].
+#[macro_export]
+macro_rules! bt {
+    // Splits children on top level commas, expanding each one
+    (@children [$($done:tt)*] []) => {
+        [$($done)*]
+    };
+    (@children [$($done:tt)*] [$($item:tt)+]) => {
+        [$($done)* $crate::bt!($($item)+)]
+    };
+    (@children [$($done:tt)*] [$($item:tt)*] , $($rest:tt)*) => {
+        $crate::bt!(@children [$($done)* $crate::bt!($($item)*),] [] $($rest)*)
+    };
+    (@children [$($done:tt)*] [$($item:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::bt!(@children [$($done)*] [$($item)* $next] $($rest)*)
+    };
+
+    (sequence { $($children:tt)* }) => {
+        $crate::builder::sequence($crate::bt!(@children [] [] $($children)*))
+    };
+    (selector { $($children:tt)* }) => {
+        $crate::builder::selector($crate::bt!(@children [] [] $($children)*))
+    };
+    (parallel($success:expr, $failure:expr) { $($children:tt)* }) => {
+        $crate::builder::parallel(
+            $success,
+            $failure,
+            $crate::bt!(@children [] [] $($children)*),
+        )
+    };
+    (invert { $($child:tt)+ }) => {
+        $crate::builder::invert($crate::bt!($($child)+))
+    };
+    (repeat { $($child:tt)+ }) => {
+        $crate::builder::repeat($crate::bt!($($child)+))
+    };
+    (repeat($n:expr) { $($child:tt)+ }) => {
+        $crate::builder::repeat_n($n, $crate::bt!($($child)+))
+    };
+    (condition($check:expr)) => {
+        $crate::builder::cond($check)
+    };
+    (action($act:expr)) => {
+        $crate::builder::act($act)
+    };
+    ($leaf:expr) => {
+        $leaf
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::BehaviorRunner;
+
+    fn push(n: i32) -> BehaviorArc<Vec<i32>> {
+        act(move |stack: &mut Vec<i32>| {
+            stack.push(n);
+            NodeResult::Success
+        })
+    }
+
+    #[test]
+    fn nested_calls_build_a_runnable_tree() {
+        let tree = selector([
+            sequence([cond(|stack: &Vec<i32>| stack.is_empty()), invert(push(1))]),
+            sequence([repeat_n(2, push(2)), succeed(invert(push(3)))]),
+        ]);
+        let mut runner = BehaviorRunner::new(tree);
+        let mut stack = vec![];
+        let res = runner.ticks(&mut stack).take(10).flatten().next();
+        check!(res == Some(true));
+        check!(stack == [1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn bt_runs_like_the_hand_written_tree() {
+        let is_empty = |stack: &Vec<i32>| stack.is_empty();
+        let by_macro = crate::bt! {
+            selector {
+                sequence { condition(is_empty), invert { push(1) } },
+                sequence {
+                    repeat(2) { push(2) },
+                    parallel(1, 1) { push(3), action(|_: &mut Vec<i32>| NodeResult::Failure) },
+                    invert { invert { push(4) } }
+                },
+            }
+        };
+        let by_hand = Selector::new([
+            Sequence::new([Condition::new(is_empty).arc(), Inverter::new(push(1)).arc()]).arc(),
+            Sequence::new([
+                LimitedRepeated::new(2, push(2)).arc(),
+                Parallel::new_unchecked(
+                    1,
+                    1,
+                    [push(3), act(|_: &mut Vec<i32>| NodeResult::Failure)],
+                )
+                .arc(),
+                Inverter::new(Inverter::new(push(4)).arc()).arc(),
+            ])
+            .arc(),
+        ])
+        .arc();
+
+        let run = |tree| {
+            let mut runner = BehaviorRunner::new(tree);
+            let mut stack = vec![];
+            let res: Vec<_> = runner.ticks(&mut stack).take(10).collect();
+            (res, stack)
+        };
+        check!(run(by_macro) == run(by_hand));
+    }
+}
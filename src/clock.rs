@@ -0,0 +1,94 @@
+//! Where time-based nodes read the time from
+//!
+//! See [`crate::composite::Wait`], [`crate::composite::Delay`],
+//! [`crate::composite::Cooldown`] and [`crate::composite::Timeout`].
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// A source of the current time, in whatever unit the tree counts in
+///
+/// Any `Fn(&B) -> u64` is a clock, which covers reading a time field off the
+/// blackboard. [`ProceedClock`] counts proceeds instead.
+pub trait TickClock<B>: Send + Sync {
+    fn now(&self, blackboard: &B) -> u64;
+}
+
+impl<B, F: Fn(&B) -> u64 + Send + Sync> TickClock<B> for F {
+    fn now(&self, blackboard: &B) -> u64 {
+        self(blackboard)
+    }
+}
+
+/// A blackboard holding a counter the runner bumps on every proceed
+///
+/// See [`crate::BehaviorRunner::proceed_counted`].
+pub trait TickCounter {
+    fn tick_count(&self) -> u64;
+    fn set_tick_count(&mut self, ticks: u64);
+}
+
+/// Reads the time as the number of counted proceeds
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProceedClock;
+
+impl<B: TickCounter> TickClock<B> for ProceedClock {
+    fn now(&self, blackboard: &B) -> u64 {
+        blackboard.tick_count()
+    }
+}
+
+/// Reads real time, in nanoseconds since the clock was made
+///
+/// Used by [`crate::composite::Delay::new`]. Tests can swap it for any other [`TickClock`]
+/// counting nanoseconds.
+#[derive(Debug, Clone, Copy)]
+pub struct WallClock {
+    origin: Instant,
+}
+
+impl Default for WallClock {
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl<B> TickClock<B> for WallClock {
+    fn now(&self, _blackboard: &B) -> u64 {
+        u64::try_from(self.origin.elapsed().as_nanos()).unwrap_or(u64::MAX)
+    }
+}
+
+/// A clock that only moves when told to
+///
+/// Clones share the same time, so a test can keep one handle and give
+/// another to the tree.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    now: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    pub fn new(now: u64) -> Self {
+        Self {
+            now: Arc::new(AtomicU64::new(now)),
+        }
+    }
+
+    pub fn set(&self, now: u64) {
+        self.now.store(now, Ordering::Relaxed);
+    }
+
+    pub fn advance(&self, by: u64) {
+        self.now.fetch_add(by, Ordering::Relaxed);
+    }
+}
+
+impl<B> TickClock<B> for ManualClock {
+    fn now(&self, _blackboard: &B) -> u64 {
+        self.now.load(Ordering::Relaxed)
+    }
+}
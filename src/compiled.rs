@@ -0,0 +1,368 @@
+//! Trees flattened into a buffer of instructions
+//!
+//! [`compile`] lays a tree out in preorder, so every subtree is a contiguous
+//! range of the buffer, and [`CompiledRunner`] walks it with an explicit
+//! stack instead of recursing through `Arc`s. Plain [`Sequence`]s,
+//! [`Selector`]s, [`Inverter`]s, [`Succeeder`]s and [`Failer`]s are lowered
+//! into instructions; every other node is kept whole and ticked as usual.
+//!
+//! A compiled tree gives the same results as the tree it came from, but
+//! the runner is deliberately small: there are no callbacks, traces or
+//! explanations, and [`crate::BehaviorNode::on_abort`] is never called.
+
+use std::sync::Arc;
+
+use crate::composite::{Failer, Inverter, Selector, Sequence, Succeeder};
+use crate::{fallible, BehaviorArc, NodeResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Sequence,
+    Selector,
+    Inverter,
+    Succeeder,
+    Failer,
+    /// A node ticked through [`crate::BehaviorNode::tick`]
+    Node,
+}
+
+#[derive(Debug)]
+struct Instr<B> {
+    op: Op,
+    /// The node this was lowered from
+    node: BehaviorArc<B>,
+    /// One past the last instruction of this subtree
+    end: usize,
+}
+
+/// A tree laid out flat by [`compile`]
+///
+/// Cheap to clone, so one compiled tree can back many runners.
+pub struct CompiledTree<B> {
+    instrs: Arc<[Instr<B>]>,
+}
+
+impl<B> Clone for CompiledTree<B> {
+    fn clone(&self) -> Self {
+        Self {
+            instrs: self.instrs.clone(),
+        }
+    }
+}
+
+impl<B> std::fmt::Debug for CompiledTree<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompiledTree")
+            .field("instrs", &self.instrs.len())
+            .finish()
+    }
+}
+
+impl<B> CompiledTree<B> {
+    /// How many instructions the tree was lowered into
+    pub fn len(&self) -> usize {
+        self.instrs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instrs.is_empty()
+    }
+}
+
+/// Flattens `root` into a [`CompiledTree`]
+pub fn compile<B: 'static>(root: &BehaviorArc<B>) -> CompiledTree<B> {
+    let mut instrs = vec![];
+    lower(root, &mut instrs);
+    CompiledTree {
+        instrs: Arc::from(instrs),
+    }
+}
+
+fn lower<B: 'static>(node: &BehaviorArc<B>, instrs: &mut Vec<Instr<B>>) {
+    let op = if node.downcast_ref::<Sequence<B>>().is_some() {
+        Op::Sequence
+    } else if node.downcast_ref::<Selector<B>>().is_some() {
+        Op::Selector
+    } else if node.downcast_ref::<Inverter<B>>().is_some() {
+        Op::Inverter
+    } else if node.downcast_ref::<Succeeder<B>>().is_some() {
+        Op::Succeeder
+    } else if node.downcast_ref::<Failer<B>>().is_some() {
+        Op::Failer
+    } else {
+        Op::Node
+    };
+    let at = instrs.len();
+    instrs.push(Instr {
+        op,
+        node: node.clone(),
+        end: at + 1,
+    });
+    if op != Op::Node {
+        for child in node.children() {
+            lower(&child, instrs);
+        }
+    }
+    instrs[at].end = instrs.len();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Running,
+    Success,
+    Failure,
+}
+
+impl Status {
+    fn of(success: bool) -> Self {
+        if success {
+            Status::Success
+        } else {
+            Status::Failure
+        }
+    }
+}
+
+/// Ticks a [`CompiledTree`]
+///
+/// Behaves like a [`crate::BehaviorRunner`] over the original tree, as far
+/// as results and ticked leaves go.
+pub struct CompiledRunner<B> {
+    tree: CompiledTree<B>,
+    /// For each composite, the child instruction it is paused at
+    paused_at: Vec<Option<usize>>,
+    /// For each kept node, the node it returned while running
+    resume: Vec<Option<BehaviorArc<B>>>,
+    running: bool,
+    /// Reused between proceeds: the composites being evaluated and the
+    /// child each is on
+    stack: Vec<(usize, usize)>,
+}
+
+impl<B> std::fmt::Debug for CompiledRunner<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompiledRunner")
+            .field("tree", &self.tree)
+            .field("running", &self.running)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B: 'static> CompiledRunner<B> {
+    pub fn new(tree: CompiledTree<B>) -> Self {
+        let len = tree.len();
+        Self {
+            tree,
+            paused_at: vec![None; len],
+            resume: vec![None; len],
+            running: false,
+            stack: vec![],
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Drops any progress, so the next proceed starts from the root
+    pub fn reset(&mut self) {
+        self.paused_at.fill(None);
+        self.resume.fill(None);
+        self.running = false;
+    }
+
+    // Same contract as BehaviorRunner::proceed
+    pub fn proceed(&mut self, context: &mut B) -> Option<bool> {
+        let instrs = self.tree.instrs.clone();
+        let mut stack = std::mem::take(&mut self.stack);
+        stack.clear();
+
+        // Either a node to enter, or the status the last entered node
+        // finished with
+        let mut entering = Some(0);
+        let mut status = Status::Running;
+        loop {
+            if let Some(at) = entering.take() {
+                let instr = &instrs[at];
+                match instr.op {
+                    Op::Node => {
+                        let node = self.resume[at].take().unwrap_or_else(|| instr.node.clone());
+                        status = match node.tick(context) {
+                            NodeResult::Running(resume) => {
+                                self.resume[at] = Some(resume);
+                                Status::Running
+                            }
+                            NodeResult::Success => Status::Success,
+                            NodeResult::Failure => Status::Failure,
+                        };
+                    }
+                    _ => match self.paused_at[at].take() {
+                        Some(child) => {
+                            stack.push((at, child));
+                            entering = Some(child);
+                        }
+                        None => match self.first_enabled(at, at + 1, context) {
+                            Some(child) => {
+                                stack.push((at, child));
+                                entering = Some(child);
+                            }
+                            None => {
+                                status = match instr.op {
+                                    Op::Sequence | Op::Succeeder => Status::Success,
+                                    _ => Status::Failure,
+                                }
+                            }
+                        },
+                    },
+                }
+                continue;
+            }
+
+            let Some((parent, child)) = stack.pop() else {
+                break;
+            };
+            let op = instrs[parent].op;
+            if status == Status::Running {
+                self.paused_at[parent] = Some(child);
+                continue;
+            }
+            // Whether a sequence or selector moves on to the next child
+            let next = match (op, status) {
+                (Op::Sequence, Status::Success) | (Op::Selector, Status::Failure)
+                    if !fallible::raised() =>
+                {
+                    self.first_enabled(parent, instrs[child].end, context)
+                }
+                _ => None,
+            };
+            match next {
+                Some(next) => {
+                    stack.push((parent, next));
+                    entering = Some(next);
+                }
+                None => {
+                    status = match op {
+                        Op::Sequence => {
+                            Status::of(status == Status::Success && !fallible::raised())
+                        }
+                        Op::Selector => Status::of(status == Status::Success),
+                        Op::Inverter => Status::of(status == Status::Failure),
+                        Op::Succeeder => Status::Success,
+                        Op::Failer => Status::Failure,
+                        Op::Node => unreachable!("kept nodes have no lowered children"),
+                    }
+                }
+            }
+        }
+
+        self.stack = stack;
+        self.running = status == Status::Running;
+        match status {
+            Status::Running => None,
+            Status::Success => Some(true),
+            Status::Failure => Some(false),
+        }
+    }
+
+    /// The first child of `parent`, starting at instruction `from`, that
+    /// should be ticked
+    ///
+    /// Only sequences and selectors skip disabled children.
+    fn first_enabled(&self, parent: usize, mut from: usize, context: &B) -> Option<usize> {
+        let instrs = &self.tree.instrs;
+        let skips = matches!(instrs[parent].op, Op::Sequence | Op::Selector);
+        while from < instrs[parent].end {
+            if !skips || instrs[from].node.is_enabled(context) {
+                return Some(from);
+            }
+            from = instrs[from].end;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::{Gated, LimitedRepeated, Parallel},
+        testing::{AlwaysFail, AlwaysSucceed, FailAfter, SucceedAfter},
+        BehaviorNode, BehaviorRunner,
+    };
+
+    /// Counts how often each leaf is ticked, so both runners can be
+    /// checked to tick the same leaves
+    #[derive(Debug, Default, PartialEq)]
+    struct Log {
+        toggle: bool,
+        ticks: Vec<u32>,
+    }
+
+    #[derive(Debug)]
+    struct Mark(u32);
+    impl BehaviorNode<Log> for Mark {
+        fn tick(self: Arc<Self>, context: &mut Log) -> NodeResult<Log> {
+            context.ticks.push(self.0);
+            if self.0.is_multiple_of(3) {
+                NodeResult::Failure
+            } else {
+                NodeResult::Success
+            }
+        }
+    }
+
+    fn tree() -> BehaviorArc<Log> {
+        Selector::new([
+            Sequence::new([
+                Mark(1).arc(),
+                SucceedAfter(2).arc(),
+                Inverter::new(Mark(3).arc()).arc(),
+                Failer::new(SucceedAfter(1).arc()).arc(),
+            ])
+            .arc(),
+            Sequence::new([
+                Gated::new(|context: &Log| context.toggle, Mark(6).arc()).arc(),
+                Succeeder::new(FailAfter(1).arc()).arc(),
+                LimitedRepeated::new(2, Mark(4).arc()).arc(),
+                Parallel::new_unchecked(1, 1, [SucceedAfter(1).arc(), AlwaysFail.arc()]).arc(),
+            ])
+            .arc(),
+            AlwaysSucceed.arc(),
+        ])
+        .arc()
+    }
+
+    #[test]
+    fn compiled_matches_interpreted() {
+        let tree = tree();
+        let compiled = compile(&tree);
+        check!(compiled.len() == 15);
+
+        let mut interpreted = BehaviorRunner::new(tree);
+        let mut runner = CompiledRunner::new(compiled);
+        let (mut a, mut b) = (Log::default(), Log::default());
+        for proceed in 0..40 {
+            let toggle = proceed % 7 < 3;
+            a.toggle = toggle;
+            b.toggle = toggle;
+            check!(interpreted.proceed(&mut a) == runner.proceed(&mut b));
+            check!(interpreted.is_running() == runner.is_running());
+            check!(a == b);
+        }
+    }
+
+    #[test]
+    fn childless_composites_finish_immediately() {
+        for (tree, expected) in [
+            (Sequence::<Log>::new([]).arc(), true),
+            (Selector::new([]).arc(), false),
+            (Succeeder::default().arc(), true),
+            (Failer::default().arc(), false),
+        ] {
+            let mut runner = CompiledRunner::new(compile(&tree));
+            check!(runner.proceed(&mut Log::default()) == Some(expected));
+        }
+    }
+}
@@ -2,35 +2,191 @@
 
 // We do a little thin runner so nodes are thick
 
+mod action;
+mod assert_pure;
+mod bubble;
+mod builder;
+mod condition;
+mod cyclic;
+mod failer;
+mod for_each;
+mod force;
+mod gated;
+mod hysteresis;
+mod if_else;
+mod inspect;
 mod inverter;
+mod iter_action;
+mod jitter;
+mod labeled;
+mod lazy;
+mod map_result;
 mod parallel;
+mod random;
 mod repeater;
 mod selector;
 mod sequence;
+mod shared;
 mod succeeder;
+mod switch;
+mod tagged;
+mod timing;
 
+#[allow(unused_imports)]
+pub use action::{Action, TimedAction, TryAction};
+#[allow(unused_imports)]
+pub use assert_pure::AssertPure;
+#[allow(unused_imports)]
+pub use bubble::{Bubble, BubbleContext, BubbleId, RestartPoint};
+#[allow(unused_imports)]
+pub use builder::{CompositeBuilder, EmptyCompositeError};
+#[allow(unused_imports)]
+pub use condition::Condition;
+#[allow(unused_imports)]
+pub use cyclic::CyclicSequence;
+#[allow(unused_imports)]
+pub use failer::Failer;
+#[allow(unused_imports)]
+pub use for_each::ForEach;
+#[allow(unused_imports)]
+pub use force::{ForceFailure, ForceSuccess};
+#[allow(unused_imports)]
+pub use gated::Gated;
+#[allow(unused_imports)]
+pub use hysteresis::HysteresisSelector;
+#[allow(unused_imports)]
+pub use if_else::IfElse;
+#[allow(unused_imports)]
+pub use inspect::Inspect;
 #[allow(unused_imports)]
 pub use inverter::Inverter;
 #[allow(unused_imports)]
-pub use parallel::{ParallelSelector, ParallelSequence};
+pub use iter_action::IterAction;
 #[allow(unused_imports)]
-pub use repeater::{LimitedRepeated, Repeated, RepeatedUntilFailure};
+pub use jitter::Jitter;
 #[allow(unused_imports)]
-pub use selector::Selector;
+pub use labeled::{LabeledSelector, LabeledSequence};
 #[allow(unused_imports)]
-pub use sequence::Sequence;
+pub use lazy::Lazy;
+#[allow(unused_imports)]
+pub use map_result::MapResult;
+#[allow(unused_imports)]
+pub use parallel::{
+    FanOutPolicy, Parallel, ParallelConfigError, ParallelN, ParallelOver, ParallelRace,
+    ParallelSelector, ParallelSequence, WeightedQuorumParallel,
+};
+#[allow(unused_imports)]
+pub use random::{Probability, RandomSelector, RandomSequence, TieBreakSelector};
+#[allow(unused_imports)]
+pub use repeater::{
+    LimitedRepeated, Repeated, RepeatedUntil, RepeatedUntilFailure, RepeatedUntilSuccess,
+    RepeatedWith, Retry,
+};
+#[allow(unused_imports)]
+pub use selector::{FallbackSelector, ReactiveSelector, Selector};
+#[allow(unused_imports)]
+pub use sequence::{BudgetedSequence, ReactiveSequence, Sequence, TimedSequence};
+#[allow(unused_imports)]
+pub use shared::SharedChildError;
 #[allow(unused_imports)]
 pub use succeeder::Succeeder;
+#[allow(unused_imports)]
+pub use switch::Switch;
+#[allow(unused_imports)]
+pub use tagged::{find_tagged, Tagged};
+#[allow(unused_imports)]
+pub use timing::{Cooldown, Delay, Timeout, Wait};
 
 // Utilities for testing
 #[cfg(test)]
 mod tests {
-    use crate::BehaviorRunner;
+    use std::sync::Arc;
+
+    use crate::{BehaviorNode, BehaviorRunner, NodeResult};
 
     pub(super) struct Context {
         pub stack: Vec<i32>,
     }
 
+    /// A blackboard the shared fixtures can push onto
+    pub(super) trait Stack {
+        fn stack(&mut self) -> &mut Vec<i32>;
+    }
+
+    impl Stack for Context {
+        fn stack(&mut self) -> &mut Vec<i32> {
+            &mut self.stack
+        }
+    }
+
+    /// Pushes its payload and succeeds
+    #[derive(Debug)]
+    pub(super) struct Push(pub i32);
+
+    impl<B: Stack + 'static> BehaviorNode<B> for Push {
+        fn tick(self: Arc<Self>, context: &mut B) -> NodeResult<B> {
+            context.stack().push(self.0);
+            NodeResult::Success
+        }
+    }
+
+    /// A leaf that declares its chance of success, and never gets ticked
+    #[derive(Debug)]
+    pub(super) struct Chance(pub f32);
+
+    impl BehaviorNode<Context> for Chance {
+        fn tick(self: Arc<Self>, _: &mut Context) -> NodeResult<Context> {
+            unreachable!("only asked for its probability")
+        }
+
+        fn success_probability(&self, _: &Context) -> f32 {
+            self.0
+        }
+    }
+
+    /// Runs for `steps` ticks, then pushes `payload` and succeeds, or fails
+    /// if `fail` is set
+    #[derive(Debug, Clone)]
+    pub(super) struct PushAfter {
+        pub steps: u32,
+        pub payload: i32,
+        pub fail: bool,
+    }
+
+    impl BehaviorNode<Context> for PushAfter {
+        fn tick(self: Arc<Self>, context: &mut Context) -> NodeResult<Context> {
+            if self.steps > 0 {
+                return NodeResult::Running(
+                    Self {
+                        steps: self.steps - 1,
+                        ..*self
+                    }
+                    .arc(),
+                );
+            }
+            context.stack.push(self.payload);
+            if self.fail {
+                NodeResult::Failure
+            } else {
+                NodeResult::Success
+            }
+        }
+    }
+
+    /// Runs forever, pushing its id if aborted
+    #[derive(Debug)]
+    pub(super) struct Idle(pub i32);
+
+    impl BehaviorNode<Context> for Idle {
+        fn tick(self: Arc<Self>, _: &mut Context) -> NodeResult<Context> {
+            self.running()
+        }
+
+        fn on_abort(&self, context: &mut Context) {
+            context.stack.push(self.0);
+        }
+    }
+
     pub(super) fn test_with_context<F>(
         init_context: F,
         mut runner: BehaviorRunner<Context>,
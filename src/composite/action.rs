@@ -0,0 +1,327 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::{fallible, BehaviorNode, NodeResult};
+
+/// Runs a closure against the blackboard and returns whatever it returns
+///
+/// Handy for prototyping a tree without declaring a type per leaf. An
+/// action that takes several ticks returns `Running` with the node to tick
+/// next, which can simply be another `Action` over the same function.
+pub struct Action<B, F> {
+    act: F,
+    _blackboard: PhantomData<fn(&mut B)>,
+}
+
+impl<B, F> std::fmt::Debug for Action<B, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Action")
+            .field(&format_args!("<closure>"))
+            .finish()
+    }
+}
+
+impl<B, F> Action<B, F>
+where
+    F: Fn(&mut B) -> NodeResult<B> + Send + Sync,
+{
+    pub fn new(act: F) -> Self {
+        Self {
+            act,
+            _blackboard: PhantomData,
+        }
+    }
+}
+
+impl<B: 'static, F> BehaviorNode<B> for Action<B, F>
+where
+    F: Fn(&mut B) -> NodeResult<B> + Send + Sync + 'static,
+{
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        (self.act)(blackboard)
+    }
+}
+
+/// An [`Action`] whose closure can fail with an error of its own
+///
+/// An `Err` is raised to [`crate::BehaviorRunner::try_proceed`], which
+/// returns it once the tree has stopped. Everywhere else (including a plain
+/// [`crate::BehaviorRunner::proceed`]) the action just fails.
+///
+/// Once an error is raised, every composite in [`crate::composite`] ticks
+/// no further children for the rest of that proceed, and fails even if a
+/// decorator in between turned the error into a success.
+pub struct TryAction<B, E, F> {
+    act: F,
+    _blackboard: PhantomData<fn(&mut B) -> E>,
+}
+
+impl<B, E, F> std::fmt::Debug for TryAction<B, E, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TryAction")
+            .field(&format_args!("<closure>"))
+            .finish()
+    }
+}
+
+impl<B, E, F> TryAction<B, E, F>
+where
+    F: Fn(&mut B) -> Result<NodeResult<B>, E> + Send + Sync,
+{
+    pub fn new(act: F) -> Self {
+        Self {
+            act,
+            _blackboard: PhantomData,
+        }
+    }
+}
+
+impl<B: 'static, E: Send + 'static, F> BehaviorNode<B> for TryAction<B, E, F>
+where
+    F: Fn(&mut B) -> Result<NodeResult<B>, E> + Send + Sync + 'static,
+{
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        (self.act)(blackboard).unwrap_or_else(|error| {
+            fallible::raise(error);
+            NodeResult::Failure
+        })
+    }
+}
+
+type ReadDt<B> = Arc<dyn Fn(&B) -> f32 + Send + Sync>;
+type ActWithDt<B> = Arc<dyn Fn(&mut B, f32) -> NodeResult<B> + Send + Sync>;
+
+/// An [`Action`] that is also handed the frame's delta time
+///
+/// The delta time is read from the blackboard with `dt` on every tick, so
+/// a fixed-timestep game only has to keep one field up to date.
+pub struct TimedAction<B> {
+    dt: ReadDt<B>,
+    act: ActWithDt<B>,
+}
+
+impl<B> std::fmt::Debug for TimedAction<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimedAction").finish_non_exhaustive()
+    }
+}
+
+impl<B> TimedAction<B> {
+    pub fn new<D, F>(dt: D, act: F) -> Self
+    where
+        D: Fn(&B) -> f32 + Send + Sync + 'static,
+        F: Fn(&mut B, f32) -> NodeResult<B> + Send + Sync + 'static,
+    {
+        Self {
+            dt: Arc::new(dt),
+            act: Arc::new(act),
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for TimedAction<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let dt = (self.dt)(blackboard);
+        (self.act)(blackboard, dt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+
+    use super::*;
+    use crate::{
+        composite::tests::{test_with_context, Context},
+        BehaviorRunner, TryProceedError,
+    };
+
+    #[test]
+    fn action_returns_closure_result() {
+        fn push_twice(context: &mut Context) -> NodeResult<Context> {
+            context.stack.push(context.stack.len() as i32);
+            if context.stack.len() < 2 {
+                NodeResult::Running(Action::new(push_twice).arc())
+            } else {
+                NodeResult::Success
+            }
+        }
+
+        let mut runner = BehaviorRunner::from_node(Action::new(push_twice));
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(context.stack == vec![0, 1]);
+
+        let runner = BehaviorRunner::from_node(Action::new(|context: &mut Context| {
+            context.stack.push(1);
+            NodeResult::Success
+        }));
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 0);
+        check!(res == Some(true));
+        check!(context.stack == vec![1]);
+        check!(format!("{:?}", Action::new(push_twice)) == "Action(<closure>)");
+    }
+
+    #[derive(Debug, Default)]
+    struct Frame {
+        dt: f32,
+        charged: f32,
+    }
+
+    #[test]
+    fn timed_action_reads_dt_each_tick() {
+        fn charge(frame: &mut Frame, dt: f32) -> NodeResult<Frame> {
+            frame.charged += dt;
+            if frame.charged >= 1.0 {
+                NodeResult::Success
+            } else {
+                NodeResult::Running(TimedAction::new(|frame: &Frame| frame.dt, charge).arc())
+            }
+        }
+
+        let mut runner =
+            BehaviorRunner::from_node(TimedAction::new(|frame: &Frame| frame.dt, charge));
+        let mut frame = Frame::default();
+        let mut results = vec![];
+        for dt in [0.25, 0.5, 0.125, 0.25] {
+            frame.dt = dt;
+            results.push(runner.proceed(&mut frame));
+        }
+        check!(results == [None, None, None, Some(true)]);
+        check!(frame.charged == 1.125);
+    }
+
+    #[test]
+    fn errors_stop_the_tree_and_surface_through_the_runner() {
+        use crate::composite::{
+            CyclicSequence, FanOutPolicy, ForEach, HysteresisSelector, IfElse, Inverter,
+            LabeledSelector, LabeledSequence, LimitedRepeated, ParallelN, RandomSelector,
+            RandomSequence, Repeated, RepeatedUntil, RepeatedUntilFailure, RepeatedWith, Retry,
+            Selector, Sequence, Switch, TieBreakSelector,
+        };
+
+        let push = |n| {
+            Action::new(move |context: &mut Context| {
+                context.stack.push(n);
+                NodeResult::Success
+            })
+            .arc()
+        };
+        let broken =
+            || TryAction::new(|_: &mut Context| Err::<NodeResult<Context>, _>("no path")).arc();
+
+        let tree = Sequence::new([push(1), broken(), push(3)]).arc();
+        let mut runner = BehaviorRunner::new(tree.clone());
+        let mut context = Context { stack: vec![] };
+        let_assert!(Err(error) = runner.try_proceed::<&str>(&mut context));
+        check!(error.raised() == Some("no path"));
+        check!(context.stack == vec![1]);
+        check!(!runner.is_running());
+
+        // Without try_proceed the error is just a failure
+        let mut context = Context { stack: vec![] };
+        check!(BehaviorRunner::new(tree).proceed(&mut context) == Some(false));
+
+        // Selectors don't move on to the next branch, and an inverted error
+        // still stops the sequence around it
+        for tree in [
+            Selector::new([broken(), push(2)]).arc(),
+            Sequence::new([Inverter::new(broken()).arc(), push(2)]).arc(),
+        ] {
+            let mut context = Context { stack: vec![] };
+            let mut runner = BehaviorRunner::new(tree);
+            let_assert!(Err(error) = runner.try_proceed::<&str>(&mut context));
+            check!(error.raised() == Some("no path"));
+            check!(context.stack.is_empty());
+        }
+
+        // Neither does any other composite, whichever child goes first
+        let pushes_then_breaks = |n| {
+            TryAction::new(move |context: &mut Context| {
+                context.stack.push(n);
+                Err::<NodeResult<Context>, _>("no path")
+            })
+            .arc()
+        };
+        let inverted = |node| Inverter::new(node).arc();
+        let first = |context: &Context| context.stack.is_empty();
+        let score = |n: f32| move |_: &Context| n;
+        for tree in [
+            LabeledSelector::new([("broken", pushes_then_breaks(1)), ("push", push(2))]).arc(),
+            LabeledSequence::new([
+                ("broken", inverted(pushes_then_breaks(1))),
+                ("push", push(2)),
+            ])
+            .arc(),
+            RandomSelector::free_running(0, [pushes_then_breaks(1), pushes_then_breaks(1)]).arc(),
+            RandomSequence::free_running(
+                0,
+                [
+                    inverted(pushes_then_breaks(1)),
+                    inverted(pushes_then_breaks(1)),
+                ],
+            )
+            .arc(),
+            TieBreakSelector::seeded_by(
+                |_: &Context| 0,
+                [
+                    (1, first as fn(&Context) -> bool, pushes_then_breaks(1)),
+                    (1, |_: &Context| false, push(2)),
+                ],
+            )
+            .arc(),
+            HysteresisSelector::new(
+                0.0,
+                [(score(1.0), pushes_then_breaks(1)), (score(0.0), push(2))],
+            )
+            .arc(),
+            CyclicSequence::new([inverted(pushes_then_breaks(1)), push(2)]).arc(),
+            ForEach::new(
+                [1, 2],
+                |_: &mut Context, _: &i32| {},
+                inverted(pushes_then_breaks(1)),
+            )
+            .arc(),
+            IfElse::new(first, inverted(pushes_then_breaks(1)), push(2)).arc(),
+            Switch::new(|_: &Context| 0, [(0, inverted(pushes_then_breaks(1)))]).arc(),
+            Retry::new(3, pushes_then_breaks(1)).arc(),
+            RepeatedUntil::new(|_: &Context| false, pushes_then_breaks(1)).arc(),
+            Repeated::new(pushes_then_breaks(1)).arc(),
+            LimitedRepeated::new(3, pushes_then_breaks(1)).arc(),
+            RepeatedUntilFailure::new(inverted(pushes_then_breaks(1))).arc(),
+            RepeatedWith::new(pushes_then_breaks(1), push(2)).arc(),
+            ParallelN::new(
+                FanOutPolicy::AllSucceed,
+                [inverted(pushes_then_breaks(1)), push(2)],
+            )
+            .arc(),
+        ] {
+            let mut context = Context { stack: vec![] };
+            let mut runner = BehaviorRunner::new(tree.clone());
+            let_assert!(Err(error) = runner.try_proceed::<&str>(&mut context));
+            check!(error.raised() == Some("no path"), "{tree:?}");
+            check!(context.stack == [1], "{tree:?}");
+            check!(!runner.is_running());
+        }
+
+        // Trees that never error come back Ok
+        let mut runner = BehaviorRunner::new(push(1));
+        check!(runner.try_proceed::<&str>(&mut context).ok() == Some(Some(true)));
+    }
+
+    #[test]
+    fn errors_of_another_type_are_reported_not_lost() {
+        let mut runner = BehaviorRunner::from_node(TryAction::new(|_: &mut Context| {
+            Err::<NodeResult<Context>, _>(404u16)
+        }));
+        let mut context = Context { stack: vec![] };
+        let_assert!(
+            Err(TryProceedError::OtherType { type_name, error }) =
+                runner.try_proceed::<&str>(&mut context)
+        );
+        check!(type_name == "u16");
+        check!(error.downcast_ref::<u16>() == Some(&404));
+        check!(!runner.is_running());
+    }
+}
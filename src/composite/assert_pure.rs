@@ -0,0 +1,156 @@
+use crate::state::StateError;
+use crate::{BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+use std::sync::Arc;
+
+type Fingerprint<B> = Arc<dyn Fn(&B) -> u64 + Send + Sync>;
+
+/// Checks that its child leaves the blackboard untouched
+///
+/// `fingerprint` should hash whatever parts of the blackboard a condition
+/// must not change. In debug builds every tick compares the fingerprint
+/// before and after ticking the child and panics if they differ; release
+/// builds just tick the child.
+pub struct AssertPure<B> {
+    fingerprint: Fingerprint<B>,
+    child: BehaviorArc<B>,
+}
+
+impl<B> std::fmt::Debug for AssertPure<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AssertPure")
+            .field("child", &self.child)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> AssertPure<B> {
+    pub fn new<F>(fingerprint: F, child: BehaviorArc<B>) -> Self
+    where
+        F: Fn(&B) -> u64 + Send + Sync + 'static,
+    {
+        Self {
+            fingerprint: Arc::new(fingerprint),
+            child,
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for AssertPure<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        #[cfg(debug_assertions)]
+        let before = (self.fingerprint)(blackboard);
+
+        let res = self.child.clone().tick(blackboard);
+
+        #[cfg(debug_assertions)]
+        assert!(
+            before == (self.fingerprint)(blackboard),
+            "{} mutated the blackboard",
+            crate::NodeName(self.child.as_ref())
+        );
+
+        match res {
+            NodeResult::Running(child) => NodeResult::Running(
+                Self {
+                    fingerprint: self.fingerprint.clone(),
+                    child,
+                }
+                .arc(),
+            ),
+            res => res,
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Decorator
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.child.estimated_cost()
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let [child] = <[_; 1]>::try_from(children).ok()?;
+        Some(
+            Self {
+                fingerprint: self.fingerprint.clone(),
+                child,
+            }
+            .arc(),
+        )
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        self.child.serialize_state(out)
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        Ok(Self {
+            fingerprint: self.fingerprint.clone(),
+            child: self.child.deserialize_state(bytes)?,
+        }
+        .arc())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::tests::{test_with_context, Context},
+        BehaviorRunner,
+    };
+
+    fn stack_len(context: &Context) -> u64 {
+        context.stack.len() as u64
+    }
+
+    #[derive(Debug)]
+    struct StackIsEmpty;
+
+    impl BehaviorNode<Context> for StackIsEmpty {
+        fn tick(self: Arc<Self>, context: &mut Context) -> NodeResult<Context> {
+            if context.stack.is_empty() {
+                NodeResult::Success
+            } else {
+                NodeResult::Failure
+            }
+        }
+    }
+
+    /// A condition that "accidentally" records that it was checked
+    #[derive(Debug)]
+    struct NoisyCheck;
+
+    impl BehaviorNode<Context> for NoisyCheck {
+        fn tick(self: Arc<Self>, context: &mut Context) -> NodeResult<Context> {
+            context.stack.push(0);
+            NodeResult::Success
+        }
+    }
+
+    #[test]
+    fn pure_condition_passes() {
+        let runner = BehaviorRunner::from_node(AssertPure::new(stack_len, StackIsEmpty.arc()));
+        let (res, _) = test_with_context(|| Context { stack: vec![1] }, runner, 0);
+        check!(res == Some(false));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "NoisyCheck mutated the blackboard")]
+    fn impure_condition_panics() {
+        let runner = BehaviorRunner::from_node(AssertPure::new(stack_len, NoisyCheck.arc()));
+        test_with_context(|| Context { stack: vec![] }, runner, 0);
+    }
+}
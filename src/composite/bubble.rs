@@ -0,0 +1,339 @@
+use crate::state::{self, StateError};
+use crate::{BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+use std::sync::Arc;
+
+/// Names the [`RestartPoint`] a [`Bubble`] is aimed at
+pub type BubbleId = &'static str;
+
+/// A blackboard that can carry a pending bubble up the tree
+///
+/// Bubbles travel through the blackboard instead of through [`NodeResult`],
+/// so composites that know nothing about them keep working unchanged.
+pub trait BubbleContext {
+    fn bubble_slot(&mut self) -> &mut Option<BubbleId>;
+}
+
+/// Asks the nearest enclosing [`RestartPoint`] with a matching id to restart
+///
+/// # Propagation
+///
+/// The leaf records its target in [`BubbleContext::bubble_slot`] and fails.
+/// Every node between it and the target sees that as an ordinary failure:
+/// sequences stop, decorators map it as usual, and a selector may go on to
+/// tick its next child during the same tick. Whatever result reaches the
+/// target, it is discarded in favor of the restart. [`RestartPoint`]s with a
+/// different id let the bubble pass. A bubble that no target claims stays in
+/// the slot for the caller to inspect.
+pub struct Bubble<B> {
+    target: BubbleId,
+    _marker: std::marker::PhantomData<fn(&mut B)>,
+}
+
+impl<B> std::fmt::Debug for Bubble<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Bubble")
+            .field("target", &self.target)
+            .finish()
+    }
+}
+
+impl<B> Bubble<B> {
+    pub fn new(target: BubbleId) -> Self {
+        Self {
+            target,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<B: BubbleContext + 'static> BehaviorNode<B> for Bubble<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        *blackboard.bubble_slot() = Some(self.target);
+        NodeResult::Failure
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| self.target == other.target)
+    }
+
+    fn hash_params(&self, state: &mut dyn std::hash::Hasher) {
+        state.write(self.target.as_bytes());
+    }
+}
+
+/// Restarts its child from scratch when a matching [`Bubble`] reaches it
+///
+/// The restart happens on the following tick: the node reports itself as
+/// running and then ticks a fresh copy of its child. Whatever part of the
+/// old child was still running is aborted (see [`BehaviorNode::on_abort`]).
+pub struct RestartPoint<B> {
+    id: BubbleId,
+    child: BehaviorArc<B>,
+    resume: Option<BehaviorArc<B>>,
+}
+
+impl<B> std::fmt::Debug for RestartPoint<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RestartPoint")
+            .field("id", &self.id)
+            .field("child", &self.child)
+            .field("resume", &self.resume)
+            .finish()
+    }
+}
+
+impl<B> RestartPoint<B> {
+    pub fn new(id: BubbleId, child: BehaviorArc<B>) -> Self {
+        Self {
+            id,
+            child,
+            resume: None,
+        }
+    }
+
+    pub fn id(&self) -> BubbleId {
+        self.id
+    }
+}
+
+impl<B: BubbleContext + 'static> BehaviorNode<B> for RestartPoint<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let node = self.resume.clone().unwrap_or_else(|| self.child.clone());
+        let res = node.tick(blackboard);
+
+        let slot = blackboard.bubble_slot();
+        if *slot == Some(self.id) {
+            *slot = None;
+            // Part of the child may still be running, like a parallel sibling
+            if let NodeResult::Running(resume) = &res {
+                crate::abort_spine(resume, blackboard);
+            }
+            return NodeResult::Running(RestartPoint::new(self.id, self.child.clone()).arc());
+        }
+
+        match res {
+            NodeResult::Running(resume) => NodeResult::Running(
+                RestartPoint {
+                    id: self.id,
+                    child: self.child.clone(),
+                    resume: Some(resume),
+                }
+                .arc(),
+            ),
+            res => res,
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Decorator
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.as_ref().unwrap_or(&self.child).estimated_cost()
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| self.id == other.id && self.child.value_eq(other.child.as_ref()))
+    }
+
+    fn hash_params(&self, state: &mut dyn std::hash::Hasher) {
+        state.write(self.id.as_bytes());
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let [child] = <[_; 1]>::try_from(children).ok()?;
+        Some(RestartPoint::new(self.id, child).arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.resume.iter().cloned().collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_option(out, self.resume.as_ref(), |out, resume| {
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        Ok(RestartPoint {
+            id: self.id,
+            child: self.child.clone(),
+            resume: state::read_option(bytes, |bytes| self.child.deserialize_state(bytes))?,
+        }
+        .arc())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::{
+            tests::{Push, Stack},
+            Sequence,
+        },
+        testing::SucceedAfter,
+        BehaviorRunner,
+    };
+
+    #[derive(Debug, Default)]
+    struct Recovering {
+        stack: Vec<i32>,
+        bubbles_left: u32,
+        bubble: Option<BubbleId>,
+    }
+
+    impl BubbleContext for Recovering {
+        fn bubble_slot(&mut self) -> &mut Option<BubbleId> {
+            &mut self.bubble
+        }
+    }
+
+    impl Stack for Recovering {
+        fn stack(&mut self) -> &mut Vec<i32> {
+            &mut self.stack
+        }
+    }
+
+    /// Bubbles to `target` while the context still has bubbles left
+    #[derive(Debug)]
+    struct Flaky(BubbleId);
+
+    impl BehaviorNode<Recovering> for Flaky {
+        fn tick(self: Arc<Self>, context: &mut Recovering) -> NodeResult<Recovering> {
+            if context.bubbles_left > 0 {
+                context.bubbles_left -= 1;
+                Bubble::new(self.0).arc().tick(context)
+            } else {
+                NodeResult::Success
+            }
+        }
+    }
+
+    fn run(tree: BehaviorArc<Recovering>, bubbles: u32) -> (usize, Option<bool>, Recovering) {
+        let mut runner = BehaviorRunner::new(tree);
+        let mut context = Recovering {
+            bubbles_left: bubbles,
+            ..Default::default()
+        };
+        let mut ticks = 1;
+        let res = loop {
+            if let Some(res) = runner.proceed(&mut context) {
+                break Some(res);
+            }
+            if ticks == 20 {
+                break None;
+            }
+            ticks += 1;
+        };
+        (ticks, res, context)
+    }
+
+    #[test]
+    fn deep_leaf_restarts_mid_tree_sequence() {
+        let tree = Sequence::new([
+            Push(0).arc(),
+            RestartPoint::new(
+                "task",
+                Sequence::new([
+                    Push(1).arc(),
+                    SucceedAfter(1).arc(),
+                    Sequence::new([Sequence::new([Flaky("task").arc()]).arc(), Push(2).arc()])
+                        .arc(),
+                ])
+                .arc(),
+            )
+            .arc(),
+        ])
+        .arc();
+
+        let (ticks, res, context) = run(tree, 1);
+        check!(res == Some(true));
+        check!(ticks == 4);
+        // Only the subtree under the restart point ran again
+        check!(context.stack == vec![0, 1, 1, 2]);
+        check!(context.bubble == None);
+    }
+
+    #[test]
+    fn bubbles_pass_other_restart_points() {
+        let tree = RestartPoint::new(
+            "outer",
+            Sequence::new([
+                Push(0).arc(),
+                RestartPoint::new(
+                    "inner",
+                    Sequence::new([Push(1).arc(), Flaky("outer").arc()]).arc(),
+                )
+                .arc(),
+            ])
+            .arc(),
+        )
+        .arc();
+
+        let (ticks, res, context) = run(tree, 2);
+        check!(res == Some(true));
+        check!(ticks == 3);
+        check!(context.stack == vec![0, 1, 0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn unclaimed_bubbles_stay_pending() {
+        let tree = RestartPoint::new("task", Flaky("elsewhere").arc()).arc();
+        let (_, res, context) = run(tree, 1);
+        check!(res == Some(false));
+        check!(context.bubble == Some("elsewhere"));
+    }
+
+    #[test]
+    fn restart_aborts_running_siblings() {
+        use crate::composite::ParallelSelector;
+
+        /// Runs forever, pushing -1 if aborted
+        #[derive(Debug)]
+        struct Patrol;
+        impl BehaviorNode<Recovering> for Patrol {
+            fn tick(self: Arc<Self>, _: &mut Recovering) -> NodeResult<Recovering> {
+                self.running()
+            }
+
+            fn on_abort(&self, context: &mut Recovering) {
+                context.stack.push(-1);
+            }
+        }
+
+        let tree = RestartPoint::new(
+            "task",
+            ParallelSelector::new([Flaky("task").arc(), Patrol.arc()]).arc(),
+        )
+        .arc();
+        let mut runner = BehaviorRunner::new(tree);
+        let mut context = Recovering {
+            bubbles_left: 1,
+            ..Default::default()
+        };
+        check!(runner.proceed(&mut context) == None);
+        check!(context.stack == vec![-1]);
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(context.stack == vec![-1]);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{BehaviorArc, BehaviorNode};
+
+use super::{Selector, Sequence};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flavor {
+    Sequence,
+    Selector,
+}
+
+/// [`CompositeBuilder::seal`] was called before any child was added
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyCompositeError;
+
+impl std::fmt::Display for EmptyCompositeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("a composite was sealed without any children")
+    }
+}
+
+impl std::error::Error for EmptyCompositeError {}
+
+/// Collects the children of a [`Sequence`] or [`Selector`] over several calls
+///
+/// Useful when the children come from separate registration steps, such as
+/// core behaviors followed by ones added by plugins. Nothing is built until
+/// [`CompositeBuilder::seal`], which is also the only point an empty builder
+/// is rejected.
+pub struct CompositeBuilder<B> {
+    flavor: Flavor,
+    children: Vec<BehaviorArc<B>>,
+}
+
+impl<B> std::fmt::Debug for CompositeBuilder<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompositeBuilder")
+            .field("flavor", &self.flavor)
+            .field("children", &self.children)
+            .finish()
+    }
+}
+
+impl<B> CompositeBuilder<B> {
+    /// Starts an empty builder that seals into a [`Sequence`]
+    pub fn sequence() -> Self {
+        Self {
+            flavor: Flavor::Sequence,
+            children: vec![],
+        }
+    }
+
+    /// Starts an empty builder that seals into a [`Selector`]
+    pub fn selector() -> Self {
+        Self {
+            flavor: Flavor::Selector,
+            children: vec![],
+        }
+    }
+
+    /// Appends a child after the ones already added
+    pub fn push(&mut self, child: impl Into<BehaviorArc<B>>) -> &mut Self {
+        self.children.push(child.into());
+        self
+    }
+
+    /// How many children have been added so far
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+impl<B, I: Into<BehaviorArc<B>>> Extend<I> for CompositeBuilder<B> {
+    fn extend<T: IntoIterator<Item = I>>(&mut self, iter: T) {
+        self.children.extend(iter.into_iter().map(Into::into));
+    }
+}
+
+impl<B: 'static> CompositeBuilder<B> {
+    /// Freezes the children added so far into the composite
+    pub fn seal(self) -> Result<BehaviorArc<B>, EmptyCompositeError> {
+        if self.children.is_empty() {
+            return Err(EmptyCompositeError);
+        }
+        Ok(match self.flavor {
+            Flavor::Sequence => Sequence::new(self.children).arc(),
+            Flavor::Selector => Selector::new(self.children).arc(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+
+    use super::*;
+    use crate::{
+        testing::{AlwaysFail, SucceedAfter},
+        BehaviorRunner, NodeKind,
+    };
+
+    #[derive(Default)]
+    struct Context;
+
+    fn register_core(builder: &mut CompositeBuilder<Context>) {
+        builder.push(AlwaysFail.arc());
+    }
+
+    fn register_plugins(builder: &mut CompositeBuilder<Context>) {
+        builder.extend([SucceedAfter(1).arc(), AlwaysFail.arc()]);
+    }
+
+    #[test]
+    fn selector_built_over_two_phases() {
+        let mut builder = CompositeBuilder::selector();
+        register_core(&mut builder);
+        register_plugins(&mut builder);
+        check!(builder.len() == 3);
+
+        let_assert!(Ok(node) = builder.seal());
+        check!(node.kind() == NodeKind::Selector);
+        check!(node.children().len() == 3);
+
+        let mut runner = BehaviorRunner::new(node);
+        let mut context = Context;
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == Some(true));
+    }
+
+    #[test]
+    fn empty_builders_fail_to_seal() {
+        check!(CompositeBuilder::<Context>::sequence().seal().err() == Some(EmptyCompositeError));
+    }
+}
@@ -0,0 +1,87 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::{BehaviorNode, NodeResult};
+
+/// Succeeds when its check holds and fails otherwise
+///
+/// Never returns `Running`, which makes it a cheap guard at the front of a
+/// [`super::Sequence`].
+pub struct Condition<B, F> {
+    check: F,
+    label: Option<&'static str>,
+    _blackboard: PhantomData<fn(&B)>,
+}
+
+impl<B, F> std::fmt::Debug for Condition<B, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Condition")
+            .field(&format_args!("<closure>"))
+            .finish()
+    }
+}
+
+impl<B, F> Condition<B, F>
+where
+    F: Fn(&B) -> bool + Send + Sync,
+{
+    pub fn new(check: F) -> Self {
+        Self {
+            check,
+            label: None,
+            _blackboard: PhantomData,
+        }
+    }
+
+    /// Describes the check in explanations, as `"{label} → true"` and the
+    /// like (see [`crate::BehaviorRunner::with_explanations`])
+    pub fn explained(mut self, label: &'static str) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+impl<B: 'static, F> BehaviorNode<B> for Condition<B, F>
+where
+    F: Fn(&B) -> bool + Send + Sync + 'static,
+{
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        if (self.check)(blackboard) {
+            NodeResult::Success
+        } else {
+            NodeResult::Failure
+        }
+    }
+
+    fn explain(&self, result: &NodeResult<B>, out: &mut String) {
+        if let Some(label) = self.label {
+            let held = matches!(result, NodeResult::Success);
+            out.push_str(&format!("{label} → {held}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::tests::{test_with_context, Context},
+        BehaviorRunner,
+    };
+
+    #[test]
+    fn condition_checks_blackboard() {
+        let is_empty = || Condition::new(|context: &Context| context.stack.is_empty());
+        check!(format!("{:?}", is_empty()) == "Condition(<closure>)");
+
+        let runner = BehaviorRunner::from_node(is_empty());
+        let (res, _) = test_with_context(|| Context { stack: vec![] }, runner, 0);
+        check!(res == Some(true));
+
+        let runner = BehaviorRunner::from_node(is_empty());
+        let (res, _) = test_with_context(|| Context { stack: vec![1] }, runner, 0);
+        check!(res == Some(false));
+    }
+}
@@ -0,0 +1,202 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::state::{self, StateError};
+use crate::{fallible, BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+
+/// Runs one child per activation, moving on to the next child each time
+///
+/// Each activation runs the child under the cursor to completion and
+/// returns its result, then advances the cursor, wrapping back to the first
+/// child after the last. Handy for patrols that visit one waypoint per
+/// activation.
+///
+/// Like [`super::Cooldown`], the cursor belongs to the node, so it carries
+/// over between activations of the tree.
+pub struct CyclicSequence<B> {
+    pub(crate) sub: Arc<[BehaviorArc<B>]>,
+    cursor: Arc<AtomicUsize>,
+    running: Option<(usize, BehaviorArc<B>)>,
+}
+
+impl<B> std::fmt::Debug for CyclicSequence<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("CyclicSequence<{:p}>", self.sub.as_ref()))
+            .field("sub", &self.sub)
+            .field("cursor", &self.cursor())
+            .field("running", &self.running)
+            .finish()
+    }
+}
+
+impl<B> CyclicSequence<B> {
+    pub fn new(children: impl IntoIterator<Item = BehaviorArc<B>>) -> Self {
+        children.into_iter().collect()
+    }
+
+    /// Starts the cursor at `index`, counting from the end when negative
+    ///
+    /// Out-of-range indices wrap around, so `-1` is the last child.
+    pub fn starting_at(self, index: isize) -> Self {
+        if !self.sub.is_empty() {
+            let index = index.rem_euclid(self.sub.len() as isize) as usize;
+            self.cursor.store(index, Ordering::Relaxed);
+        }
+        self
+    }
+
+    /// The child the next activation will run
+    pub fn cursor(&self) -> usize {
+        self.cursor.load(Ordering::Relaxed)
+    }
+}
+
+impl<B, I: Into<BehaviorArc<B>>> FromIterator<I> for CyclicSequence<B> {
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        Self {
+            sub: Arc::from(iter.into_iter().map(Into::into).collect::<Vec<_>>()),
+            cursor: Arc::new(AtomicUsize::new(0)),
+            running: None,
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for CyclicSequence<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let (index, node) = match self.running.as_ref() {
+            Some((index, resume)) => (*index, resume.clone()),
+            None => {
+                let Some(child) = self.sub.get(self.cursor()) else {
+                    return NodeResult::Success;
+                };
+                (self.cursor(), child.clone())
+            }
+        };
+        match fallible::tick_child(node, blackboard) {
+            Some(NodeResult::Running(resume)) => NodeResult::Running(
+                Self {
+                    sub: self.sub.clone(),
+                    cursor: self.cursor.clone(),
+                    running: Some((index, resume)),
+                }
+                .arc(),
+            ),
+            res => {
+                self.cursor
+                    .store((index + 1) % self.sub.len(), Ordering::Relaxed);
+                res.unwrap_or(NodeResult::Failure)
+            }
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Sequence
+    }
+
+    fn is_stateful(&self) -> bool {
+        true
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        match self.running.as_ref() {
+            Some((_, resume)) => resume.estimated_cost(),
+            None => self
+                .sub
+                .get(self.cursor())
+                .map_or(1, |child| child.estimated_cost()),
+        }
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.running
+            .iter()
+            .map(|(_, resume)| resume.clone())
+            .collect()
+    }
+
+    fn active_origins(&self) -> Vec<BehaviorArc<B>> {
+        self.running
+            .iter()
+            .map(|(idx, _)| self.sub[*idx].clone())
+            .collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        let (index, resume) = self
+            .running
+            .as_ref()
+            .ok_or(StateError::Unsupported(self.name()))?;
+        state::write_u64(out, *index as u64);
+        resume.serialize_state(out)
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let index = state::read_index(bytes, self.sub.len(), self.name())?;
+        let resume = self.sub[index].deserialize_state(bytes)?;
+        // The cursor stays on the running child until it finishes
+        self.cursor.store(index, Ordering::Relaxed);
+        Ok(Self {
+            sub: self.sub.clone(),
+            cursor: self.cursor.clone(),
+            running: Some((index, resume)),
+        }
+        .arc())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{composite::tests::Context, testing::SucceedAfter, BehaviorRunner};
+
+    #[derive(Debug)]
+    struct Visit(i32);
+    impl BehaviorNode<Context> for Visit {
+        fn tick(self: Arc<Self>, context: &mut Context) -> NodeResult<Context> {
+            context.stack.push(self.0);
+            if self.0 < 0 {
+                NodeResult::Failure
+            } else {
+                NodeResult::Success
+            }
+        }
+    }
+
+    #[test]
+    fn cursor_advances_and_wraps() {
+        let mut runner = BehaviorRunner::from_node(CyclicSequence::new([
+            Visit(0).arc(),
+            Visit(-1).arc(),
+            SucceedAfter(1).arc(),
+        ]));
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(runner.proceed(&mut context) == Some(false));
+        // The running child finishes before the cursor moves on
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(context.stack == vec![0, -1, 0]);
+    }
+
+    #[test]
+    fn negative_start_counts_from_the_end() {
+        let cyclic =
+            CyclicSequence::<Context>::new([Visit(0).arc(), Visit(1).arc(), Visit(2).arc()]);
+        check!(cyclic.starting_at(-1).cursor() == 2);
+
+        let cyclic = CyclicSequence::new([Visit(0).arc(), Visit(1).arc(), Visit(2).arc()]);
+        let mut runner = BehaviorRunner::from_node(cyclic.starting_at(-2));
+        let mut context = Context { stack: vec![] };
+        for _ in 0..4 {
+            check!(runner.proceed(&mut context) == Some(true));
+        }
+        check!(context.stack == vec![1, 2, 0, 1]);
+    }
+}
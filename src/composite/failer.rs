@@ -0,0 +1,136 @@
+use crate::state::StateError;
+use crate::{BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+use std::sync::Arc;
+
+/// Always fails.
+///
+/// With a child, the child still runs to completion first; its result is
+/// then ignored.
+pub struct Failer<B> {
+    child: Option<BehaviorArc<B>>,
+}
+
+impl<B> std::fmt::Debug for Failer<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Failer")
+            .field("child", &self.child)
+            .finish()
+    }
+}
+
+impl<B> Default for Failer<B> {
+    fn default() -> Self {
+        Self { child: None }
+    }
+}
+
+impl<B> Failer<B> {
+    pub fn new(child: BehaviorArc<B>) -> Self {
+        Self { child: Some(child) }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for Failer<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> crate::NodeResult<B> {
+        if let Some(child) = self.child.as_ref() {
+            match child.clone().tick(blackboard) {
+                NodeResult::Failure | NodeResult::Success => NodeResult::Failure,
+                NodeResult::Running(resume) => NodeResult::Running(Failer::new(resume).arc()),
+            }
+        } else {
+            NodeResult::Failure
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Failer
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.child
+            .as_ref()
+            .map_or(1, |child| child.estimated_cost())
+    }
+
+    fn success_probability(&self, _blackboard: &B) -> f32 {
+        0.0
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            match (self.child.as_ref(), other.child.as_ref()) {
+                (Some(a), Some(b)) => a.value_eq(b.as_ref()),
+                (None, None) => true,
+                _ => false,
+            }
+        })
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.child.iter().cloned().collect()
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let [child] = <[_; 1]>::try_from(children).ok()?;
+        Some(Failer::new(child).arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.child.iter().cloned().collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        match self.child.as_ref() {
+            Some(child) => child.serialize_state(out),
+            None => Err(StateError::Unsupported(self.name())),
+        }
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        match self.child.as_ref() {
+            Some(child) => Ok(Failer::new(child.deserialize_state(bytes)?).arc()),
+            None => Err(StateError::Unsupported(self.name())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::{
+            tests::{test_with_context, Context, Push},
+            Selector, Sequence,
+        },
+        testing::{AlwaysSucceed, SucceedAfter},
+        BehaviorRunner,
+    };
+
+    #[test]
+    fn failer_always_fails() {
+        let runner = BehaviorRunner::from_node(Failer::<Context>::default());
+        let (res, _) = test_with_context(|| Context { stack: vec![] }, runner, 0);
+        check!(res == Some(false));
+
+        let runner = BehaviorRunner::from_node(Failer::new(SucceedAfter(2).arc()));
+        let (res, _) = test_with_context(|| Context { stack: vec![] }, runner, 2);
+        check!(res == Some(false));
+    }
+
+    #[test]
+    fn failer_forces_selector_onwards() {
+        let runner = BehaviorRunner::from_node(Selector::new([
+            Failer::new(Sequence::new([Push(1).arc(), AlwaysSucceed.arc()]).arc()).arc(),
+            Push(2).arc(),
+        ]));
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 0);
+        check!(res == Some(true));
+        check!(context.stack == vec![1, 2]);
+    }
+}
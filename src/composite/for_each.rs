@@ -0,0 +1,272 @@
+use crate::state::{self, StateError};
+use crate::{fallible, BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+use std::sync::Arc;
+
+type SetItem<B, T> = Arc<dyn Fn(&mut B, &T) + Send + Sync>;
+type GenerateItems<B, T> = Arc<dyn Fn(&B) -> Vec<T> + Send + Sync>;
+
+enum Items<B, T> {
+    Fixed(Arc<[T]>),
+    Generated(GenerateItems<B, T>),
+}
+
+/// Runs its body once per item, like a [`super::Sequence`] whose length
+/// comes from data
+///
+/// Before each iteration `set` exposes the current item on the blackboard.
+/// The body must succeed for every item; the first failure fails the loop.
+pub struct ForEach<B, T> {
+    items: Items<B, T>,
+    set: SetItem<B, T>,
+    body: BehaviorArc<B>,
+}
+
+impl<B, T> std::fmt::Debug for ForEach<B, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("ForEach");
+        if let Items::Fixed(items) = &self.items {
+            s.field("items", &items.len());
+        }
+        s.field("body", &self.body).finish_non_exhaustive()
+    }
+}
+
+impl<B, T> ForEach<B, T> {
+    /// Loops over a fixed list of items
+    pub fn new<F>(items: impl IntoIterator<Item = T>, set: F, body: BehaviorArc<B>) -> Self
+    where
+        F: Fn(&mut B, &T) + Send + Sync + 'static,
+    {
+        Self {
+            items: Items::Fixed(items.into_iter().collect()),
+            set: Arc::new(set),
+            body,
+        }
+    }
+
+    /// Loops over the items `generate` returns, called once per activation
+    pub fn generated<G, F>(generate: G, set: F, body: BehaviorArc<B>) -> Self
+    where
+        G: Fn(&B) -> Vec<T> + Send + Sync + 'static,
+        F: Fn(&mut B, &T) + Send + Sync + 'static,
+    {
+        Self {
+            items: Items::Generated(Arc::new(generate)),
+            set: Arc::new(set),
+            body,
+        }
+    }
+}
+
+impl<B: 'static, T: Send + Sync + 'static> ForEach<B, T> {
+    fn tick_from(
+        items: Arc<[T]>,
+        set: &SetItem<B, T>,
+        body: &BehaviorArc<B>,
+        index: usize,
+        mut resume: Option<BehaviorArc<B>>,
+        blackboard: &mut B,
+    ) -> NodeResult<B> {
+        for idx in index..items.len() {
+            let node = match resume.take() {
+                Some(node) => node,
+                None => {
+                    set(blackboard, &items[idx]);
+                    body.clone()
+                }
+            };
+            let Some(res) = fallible::tick_child(node, blackboard) else {
+                return NodeResult::Failure;
+            };
+            match res {
+                NodeResult::Success => {}
+                NodeResult::Failure => return NodeResult::Failure,
+                NodeResult::Running(resume) => {
+                    return NodeResult::Running(
+                        ForEachResume {
+                            items,
+                            set: set.clone(),
+                            body: body.clone(),
+                            index: idx,
+                            resume,
+                        }
+                        .arc(),
+                    )
+                }
+            }
+        }
+        NodeResult::Success
+    }
+}
+
+impl<B: 'static, T: Send + Sync + 'static> BehaviorNode<B> for ForEach<B, T> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let items = match &self.items {
+            Items::Fixed(items) => items.clone(),
+            Items::Generated(generate) => Arc::from(generate(blackboard)),
+        };
+        Self::tick_from(items, &self.set, &self.body, 0, None, blackboard)
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Repeater
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.body.estimated_cost()
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.body.clone()]
+    }
+
+    /// Generated items are gone once the loop is running, so only loops
+    /// over fixed items can be restored
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let Items::Fixed(items) = &self.items else {
+            return Err(StateError::Unsupported(self.name()));
+        };
+        let index = state::read_index(bytes, items.len(), self.name())?;
+        Ok(ForEachResume {
+            items: items.clone(),
+            set: self.set.clone(),
+            body: self.body.clone(),
+            index,
+            resume: self.body.deserialize_state(bytes)?,
+        }
+        .arc())
+    }
+}
+
+pub(crate) struct ForEachResume<B, T> {
+    items: Arc<[T]>,
+    set: SetItem<B, T>,
+    body: BehaviorArc<B>,
+    pub(crate) index: usize,
+    pub(crate) resume: BehaviorArc<B>,
+}
+
+impl<B, T> std::fmt::Debug for ForEachResume<B, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ForEachResume")
+            .field("resume", &self.resume)
+            .field("index", &self.index)
+            .field("items", &self.items.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B: 'static, T: Send + Sync + 'static> BehaviorNode<B> for ForEachResume<B, T> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        ForEach::tick_from(
+            self.items.clone(),
+            &self.set,
+            &self.body,
+            self.index,
+            Some(self.resume.clone()),
+            blackboard,
+        )
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Repeater
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.estimated_cost()
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.resume.clone()]
+    }
+
+    fn active_origins(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.body.clone()]
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_u64(out, self.index as u64);
+        self.resume.serialize_state(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::BehaviorRunner;
+
+    #[derive(Debug, Default)]
+    struct Walker {
+        waypoints: Vec<(i32, i32)>,
+        target: Option<(i32, i32)>,
+        position: (i32, i32),
+        visited: Vec<(i32, i32)>,
+    }
+
+    /// Takes one step along each axis towards the target per tick
+    #[derive(Debug)]
+    struct MoveToTarget;
+
+    impl BehaviorNode<Walker> for MoveToTarget {
+        fn tick(self: Arc<Self>, walker: &mut Walker) -> NodeResult<Walker> {
+            let Some(target) = walker.target else {
+                return NodeResult::Failure;
+            };
+            if walker.position == target {
+                walker.visited.push(target);
+                return NodeResult::Success;
+            }
+            walker.position.0 += (target.0 - walker.position.0).signum();
+            walker.position.1 += (target.1 - walker.position.1).signum();
+            NodeResult::Running(self)
+        }
+    }
+
+    fn run(runner: &mut BehaviorRunner<Walker>, walker: &mut Walker) -> (usize, bool) {
+        let mut ticks = 1;
+        loop {
+            if let Some(res) = runner.proceed(walker) {
+                break (ticks, res);
+            }
+            ticks += 1;
+        }
+    }
+
+    #[test]
+    fn for_each_visits_every_item() {
+        let mut runner = BehaviorRunner::from_node(ForEach::new(
+            [(2, 0), (2, 2), (0, 0)],
+            |walker: &mut Walker, &target| walker.target = Some(target),
+            MoveToTarget.arc(),
+        ));
+        let mut walker = Walker::default();
+
+        let (ticks, res) = run(&mut runner, &mut walker);
+        check!(res);
+        check!(walker.visited == vec![(2, 0), (2, 2), (0, 0)]);
+        // Two steps to each waypoint, plus the arriving tick
+        check!(ticks == 7);
+    }
+
+    #[test]
+    fn for_each_generates_items_per_activation() {
+        let mut runner = BehaviorRunner::from_node(ForEach::generated(
+            |walker: &Walker| walker.waypoints.clone(),
+            |walker: &mut Walker, &target| walker.target = Some(target),
+            MoveToTarget.arc(),
+        ));
+        let mut walker = Walker {
+            waypoints: vec![(1, 1), (1, 2)],
+            ..Default::default()
+        };
+
+        check!(run(&mut runner, &mut walker).1);
+        check!(walker.visited == vec![(1, 1), (1, 2)]);
+
+        walker.waypoints = vec![];
+        check!(runner.proceed(&mut walker) == Some(true));
+        check!(walker.visited.len() == 2);
+    }
+}
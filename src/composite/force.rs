@@ -0,0 +1,118 @@
+use crate::state::StateError;
+use crate::{BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+use std::sync::Arc;
+
+use super::Succeeder;
+
+/// Fails once its child finishes, whatever the child's result
+///
+/// Like [`super::Failer::new`], except the child isn't optional.
+pub struct ForceFailure<B> {
+    child: BehaviorArc<B>,
+}
+
+/// Succeeds once its child finishes, whatever the child's result
+///
+/// Build one with [`Succeeder::new`]; the name pairs with [`ForceFailure`].
+pub type ForceSuccess<B> = Succeeder<B>;
+
+impl<B> std::fmt::Debug for ForceFailure<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ForceFailure")
+            .field("child", &self.child)
+            .finish()
+    }
+}
+
+impl<B> ForceFailure<B> {
+    pub fn new(child: BehaviorArc<B>) -> Self {
+        Self { child }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for ForceFailure<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        match self.child.clone().tick(blackboard) {
+            NodeResult::Success | NodeResult::Failure => NodeResult::Failure,
+            NodeResult::Running(resume) => NodeResult::Running(ForceFailure::new(resume).arc()),
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Failer
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.child.estimated_cost()
+    }
+
+    fn success_probability(&self, _blackboard: &B) -> f32 {
+        0.0
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| self.child.value_eq(other.child.as_ref()))
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let [child] = <[_; 1]>::try_from(children).ok()?;
+        Some(ForceFailure::new(child).arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        self.child.serialize_state(out)
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        Ok(ForceFailure::new(self.child.deserialize_state(bytes)?).arc())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::tests::{test_with_context, Context},
+        testing::{FailAfter, SucceedAfter},
+        BehaviorRunner,
+    };
+
+    #[test]
+    fn force_failure_fails_after_running_child() {
+        let runner = BehaviorRunner::new(ForceFailure::new(SucceedAfter(9).arc()).arc());
+        // Still running after 8 more proceeds, so the child's steps pass through
+        let (res, _) = test_with_context(|| Context { stack: vec![] }, runner, 8);
+        check!(res == None);
+
+        let runner = BehaviorRunner::new(ForceFailure::new(SucceedAfter(9).arc()).arc());
+        let (res, _) = test_with_context(|| Context { stack: vec![] }, runner, 9);
+        check!(res == Some(false));
+
+        let runner = BehaviorRunner::new(ForceFailure::new(FailAfter(3).arc()).arc());
+        let (res, _) = test_with_context(|| Context { stack: vec![] }, runner, 3);
+        check!(res == Some(false));
+    }
+
+    #[test]
+    fn force_success_succeeds_after_running_child() {
+        let runner = BehaviorRunner::new(ForceSuccess::new(FailAfter(9).arc()).arc());
+        let (res, _) = test_with_context(|| Context { stack: vec![] }, runner, 9);
+        check!(res == Some(true));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+use std::sync::Arc;
+
+type EnableFn<B> = Arc<dyn Fn(&B) -> bool + Send + Sync>;
+
+/// A child that sequences and selectors skip while `enabled` says no
+///
+/// Meant for toggling optional behaviors (ones added by mods, say) from a
+/// flag on the blackboard without rebuilding the tree. See
+/// [`BehaviorNode::is_enabled`]. Ticked anywhere else while disabled, it
+/// fails without ticking its child.
+pub struct Gated<B> {
+    enabled: EnableFn<B>,
+    child: BehaviorArc<B>,
+}
+
+impl<B> std::fmt::Debug for Gated<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Gated")
+            .field("child", &self.child)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> Gated<B> {
+    pub fn new<F>(enabled: F, child: BehaviorArc<B>) -> Self
+    where
+        F: Fn(&B) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            enabled: Arc::new(enabled),
+            child,
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for Gated<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        if !(self.enabled)(blackboard) {
+            return NodeResult::Failure;
+        }
+        self.child.clone().tick(blackboard)
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Decorator
+    }
+
+    fn is_enabled(&self, blackboard: &B) -> bool {
+        (self.enabled)(blackboard)
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.child.estimated_cost()
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::{Action, Selector, Sequence},
+        BehaviorRunner,
+    };
+
+    #[derive(Debug, Default)]
+    struct Modded {
+        enabled: bool,
+        ran: Vec<i32>,
+    }
+
+    fn push(n: i32, res: fn() -> NodeResult<Modded>) -> BehaviorArc<Modded> {
+        Action::new(move |context: &mut Modded| {
+            context.ran.push(n);
+            res()
+        })
+        .arc()
+    }
+
+    fn gated(child: BehaviorArc<Modded>) -> BehaviorArc<Modded> {
+        Gated::new(|context: &Modded| context.enabled, child).arc()
+    }
+
+    #[test]
+    fn disabled_children_are_skipped() {
+        let activate = |runner: &mut BehaviorRunner<Modded>, enabled| {
+            let mut context = Modded {
+                enabled,
+                ..Default::default()
+            };
+            (runner.proceed(&mut context), context.ran)
+        };
+
+        // A disabled child doesn't fail the sequence
+        let mut runner = BehaviorRunner::new(
+            Sequence::new([
+                push(1, || NodeResult::Success),
+                gated(push(2, || NodeResult::Failure)),
+                push(3, || NodeResult::Success),
+            ])
+            .arc(),
+        );
+        check!(activate(&mut runner, true) == (Some(false), vec![1, 2]));
+        check!(activate(&mut runner, false) == (Some(true), vec![1, 3]));
+        check!(activate(&mut runner, true) == (Some(false), vec![1, 2]));
+
+        // Nor does it succeed the selector
+        let mut runner = BehaviorRunner::new(
+            Selector::new([
+                gated(push(1, || NodeResult::Success)),
+                push(2, || NodeResult::Failure),
+            ])
+            .arc(),
+        );
+        check!(activate(&mut runner, true) == (Some(true), vec![1]));
+        check!(activate(&mut runner, false) == (Some(false), vec![2]));
+    }
+}
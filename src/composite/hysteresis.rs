@@ -0,0 +1,304 @@
+use std::hash::Hasher;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use super::sequence::{read_resume, write_resume};
+use crate::state::StateError;
+use crate::{explain, fallible, BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+
+type Score<B> = Arc<dyn Fn(&B) -> f32 + Send + Sync>;
+
+const NONE_CHOSEN: usize = usize::MAX;
+
+/// A scored [`Selector`](super::Selector) that sticks with its previous choice
+///
+/// Every activation scores the children and tries them from the highest
+/// score down. The branch chosen last time is tried first, though, unless
+/// another branch beats its score by more than `margin`. This keeps agents
+/// from flickering between branches whose scores are close.
+///
+/// A branch counts as chosen once the activation finishes with it
+/// succeeding. While a branch is still running, or if the activation is
+/// aborted, the previous choice stands.
+pub struct HysteresisSelector<B> {
+    pub(crate) sub: Arc<[(Score<B>, BehaviorArc<B>)]>,
+    margin: f32,
+    last_chosen: Arc<AtomicUsize>,
+}
+
+impl<B> std::fmt::Debug for HysteresisSelector<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let children = self.sub.iter().map(|(_, child)| child).collect::<Vec<_>>();
+        f.debug_struct(&format!("HysteresisSelector<{:p}>", self.sub.as_ref()))
+            .field("sub", &children)
+            .field("margin", &self.margin)
+            .field("last_chosen", &self.last_chosen())
+            .finish()
+    }
+}
+
+impl<B> HysteresisSelector<B> {
+    pub fn new<F>(margin: f32, children: impl IntoIterator<Item = (F, BehaviorArc<B>)>) -> Self
+    where
+        F: Fn(&B) -> f32 + Send + Sync + 'static,
+    {
+        Self {
+            sub: Arc::from(
+                children
+                    .into_iter()
+                    .map(|(score, child)| (Arc::new(score) as Score<B>, child))
+                    .collect::<Vec<_>>(),
+            ),
+            margin,
+            last_chosen: Arc::new(AtomicUsize::new(NONE_CHOSEN)),
+        }
+    }
+
+    /// The index of the branch chosen on the last activation
+    pub fn last_chosen(&self) -> Option<usize> {
+        match self.last_chosen.load(Ordering::Relaxed) {
+            NONE_CHOSEN => None,
+            idx => Some(idx),
+        }
+    }
+
+    fn order(&self, blackboard: &B) -> Vec<usize> {
+        let scores = self
+            .sub
+            .iter()
+            .map(|(score, _)| score(blackboard))
+            .collect::<Vec<_>>();
+        let mut order = (0..self.sub.len()).collect::<Vec<_>>();
+        order.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]));
+        if let Some(last) = self.last_chosen() {
+            if scores[order[0]] - scores[last] <= self.margin {
+                order.retain(|idx| *idx != last);
+                order.insert(0, last);
+            }
+        }
+        order
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for HysteresisSelector<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let order = Arc::<[usize]>::from(self.order(blackboard));
+        let seq = order
+            .iter()
+            .map(|idx| self.sub[*idx].1.clone())
+            .collect::<Arc<[_]>>();
+        tick_from(&seq, &order, &self.last_chosen, 0, blackboard)
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Selector
+    }
+
+    fn is_stateful(&self) -> bool {
+        true
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.iter().map(|(_, child)| child.clone()).collect()
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_u32(self.margin.to_bits());
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let sub = Arc::from(self.children());
+        read_resume(&sub, bytes, self.name(), |seq, order, index, resume| {
+            // Scored selectors always write their order
+            let order = order.unwrap_or_else(|| (0..seq.len()).collect());
+            HysteresisResume {
+                seq,
+                order,
+                last_chosen: self.last_chosen.clone(),
+                index,
+                resume,
+            }
+            .arc()
+        })
+    }
+}
+
+/// Handles what [`fallible::tick_child`] returned for the branch at `index`
+/// of `seq`, or `None` to try the next branch
+///
+/// The activation only picks a winner once a branch succeeds.
+fn settle<B: 'static>(
+    seq: &Arc<[BehaviorArc<B>]>,
+    order: &Arc<[usize]>,
+    last_chosen: &Arc<AtomicUsize>,
+    index: usize,
+    res: Option<NodeResult<B>>,
+) -> Option<NodeResult<B>> {
+    let Some(res) = res else {
+        last_chosen.store(NONE_CHOSEN, Ordering::Relaxed);
+        return Some(NodeResult::Failure);
+    };
+    match res {
+        NodeResult::Failure => None,
+        NodeResult::Success => {
+            last_chosen.store(order[index], Ordering::Relaxed);
+            explain::note(|out| out.push_str(&format!("selected branch {}", order[index])));
+            Some(NodeResult::Success)
+        }
+        NodeResult::Running(resume) => Some(NodeResult::Running(
+            HysteresisResume {
+                seq: seq.clone(),
+                order: order.clone(),
+                last_chosen: last_chosen.clone(),
+                index,
+                resume,
+            }
+            .arc(),
+        )),
+    }
+}
+
+/// Tries the branches of `seq` from `index` on
+fn tick_from<B: 'static>(
+    seq: &Arc<[BehaviorArc<B>]>,
+    order: &Arc<[usize]>,
+    last_chosen: &Arc<AtomicUsize>,
+    index: usize,
+    blackboard: &mut B,
+) -> NodeResult<B> {
+    for (idx, sub) in seq.iter().enumerate().skip(index) {
+        let res = fallible::tick_child(sub.clone(), blackboard);
+        if let Some(res) = settle(seq, order, last_chosen, idx, res) {
+            return res;
+        }
+    }
+    last_chosen.store(NONE_CHOSEN, Ordering::Relaxed);
+    NodeResult::Failure
+}
+
+/// A running [`HysteresisSelector`], waiting on the branch at `index` of its
+/// scored order
+struct HysteresisResume<B> {
+    seq: Arc<[BehaviorArc<B>]>,
+    order: Arc<[usize]>,
+    last_chosen: Arc<AtomicUsize>,
+    index: usize,
+    resume: BehaviorArc<B>,
+}
+
+impl<B> std::fmt::Debug for HysteresisResume<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("HysteresisResume<{:p}>", self.seq.as_ref()))
+            .field("resume", &self.resume)
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for HysteresisResume<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let res = match fallible::tick_child(self.resume.clone(), blackboard) {
+            // Nothing changed below us, so neither did we
+            Some(NodeResult::Running(resume)) if Arc::ptr_eq(&resume, &self.resume) => {
+                return self.running()
+            }
+            res => res,
+        };
+        let (seq, order, last_chosen) = (&self.seq, &self.order, &self.last_chosen);
+        settle(seq, order, last_chosen, self.index, res)
+            .unwrap_or_else(|| tick_from(seq, order, last_chosen, self.index + 1, blackboard))
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Selector
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.estimated_cost()
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.resume.clone()]
+    }
+
+    fn active_origins(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.seq[self.index].clone()]
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        write_resume(out, Some(&self.order), self.index, &self.resume)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::testing::FailAfter;
+    use crate::BehaviorRunner;
+
+    #[derive(Debug, Default)]
+    struct Agent {
+        fight: f32,
+        flee: f32,
+        actions: Vec<&'static str>,
+    }
+
+    #[derive(Debug)]
+    struct Act(&'static str);
+    impl BehaviorNode<Agent> for Act {
+        fn tick(self: Arc<Self>, context: &mut Agent) -> NodeResult<Agent> {
+            context.actions.push(self.0);
+            NodeResult::Success
+        }
+    }
+
+    #[test]
+    fn hysteresis_keeps_branch_within_margin() {
+        let fight: fn(&Agent) -> f32 = |agent| agent.fight;
+        let flee: fn(&Agent) -> f32 = |agent| agent.flee;
+        let mut runner = BehaviorRunner::from_node(HysteresisSelector::new(
+            0.1,
+            [(fight, Act("fight").arc()), (flee, Act("flee").arc())],
+        ));
+
+        let mut agent = Agent::default();
+        for (fight, flee) in [
+            (0.50, 0.52),
+            (0.53, 0.51),
+            (0.49, 0.55),
+            (0.58, 0.50),
+            (0.7, 0.5),
+            (0.6, 0.65),
+        ] {
+            agent.fight = fight;
+            agent.flee = flee;
+            check!(runner.proceed(&mut agent) == Some(true));
+        }
+        check!(agent.actions == vec!["flee", "flee", "flee", "flee", "fight", "fight"]);
+    }
+
+    #[test]
+    fn hysteresis_records_the_branch_that_wins() {
+        let fight: fn(&Agent) -> f32 = |agent| agent.fight;
+        let flee: fn(&Agent) -> f32 = |agent| agent.flee;
+        let selector = Arc::new(HysteresisSelector::new(
+            0.1,
+            [(fight, Act("fight").arc()), (flee, FailAfter(1).arc())],
+        ));
+        let mut runner = BehaviorRunner::new(selector.clone());
+
+        let mut agent = Agent {
+            flee: 0.9,
+            ..Default::default()
+        };
+        // Fleeing is still running, so nothing has been chosen yet
+        check!(runner.proceed(&mut agent) == None);
+        check!(selector.last_chosen() == None);
+        // Then it fails and fighting wins instead
+        check!(runner.proceed(&mut agent) == Some(true));
+        check!(selector.last_chosen() == Some(0));
+        check!(agent.actions == vec!["fight"]);
+    }
+}
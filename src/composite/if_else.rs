@@ -0,0 +1,127 @@
+use crate::{fallible, BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+use std::sync::Arc;
+
+type Condition<B> = Arc<dyn Fn(&B) -> bool + Send + Sync>;
+
+/// Ticks one of two branches depending on a condition
+///
+/// The condition is evaluated once, when the node is activated. The chosen
+/// branch then runs to completion even if the condition changes while it is
+/// running; the other branch is only considered on the next activation.
+pub struct IfElse<B> {
+    condition: Condition<B>,
+    then_branch: BehaviorArc<B>,
+    else_branch: BehaviorArc<B>,
+}
+
+impl<B> std::fmt::Debug for IfElse<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IfElse")
+            .field("then_branch", &self.then_branch)
+            .field("else_branch", &self.else_branch)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> IfElse<B> {
+    pub fn new<F>(condition: F, then_branch: BehaviorArc<B>, else_branch: BehaviorArc<B>) -> Self
+    where
+        F: Fn(&B) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            condition: Arc::new(condition),
+            then_branch,
+            else_branch,
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for IfElse<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        // The chosen branch's own resume carries on from here, so the
+        // condition is not consulted again until we are reactivated.
+        let branch = if (self.condition)(blackboard) {
+            &self.then_branch
+        } else {
+            &self.else_branch
+        };
+        fallible::tick_child(branch.clone(), blackboard).unwrap_or(NodeResult::Failure)
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Selector
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.then_branch
+            .estimated_cost()
+            .max(self.else_branch.estimated_cost())
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.then_branch.clone(), self.else_branch.clone()]
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let [then_branch, else_branch] = <[_; 2]>::try_from(children).ok()?;
+        Some(
+            Self {
+                condition: self.condition.clone(),
+                then_branch,
+                else_branch,
+            }
+            .arc(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        testing::{AlwaysFail, SucceedAfter},
+        BehaviorRunner,
+    };
+
+    #[derive(Debug, Default)]
+    struct Gate {
+        open: bool,
+    }
+
+    fn gate_tree() -> BehaviorRunner<Gate> {
+        BehaviorRunner::from_node(IfElse::new(
+            |gate: &Gate| gate.open,
+            SucceedAfter(2).arc(),
+            AlwaysFail.arc(),
+        ))
+    }
+
+    #[test]
+    fn if_else_picks_branch() {
+        let mut runner = gate_tree();
+        let mut gate = Gate { open: true };
+        check!(runner.proceed(&mut gate) == None);
+        check!(runner.proceed(&mut gate) == None);
+        check!(runner.proceed(&mut gate) == Some(true));
+
+        gate.open = false;
+        check!(runner.proceed(&mut gate) == Some(false));
+    }
+
+    #[test]
+    fn if_else_samples_condition_on_activation() {
+        let mut runner = gate_tree();
+        let mut gate = Gate { open: true };
+        check!(runner.proceed(&mut gate) == None);
+
+        // Closing the gate mid-branch does not switch to the else branch
+        gate.open = false;
+        check!(runner.proceed(&mut gate) == None);
+        check!(runner.proceed(&mut gate) == Some(true));
+
+        // ...but the next activation sees it
+        check!(runner.proceed(&mut gate) == Some(false));
+    }
+}
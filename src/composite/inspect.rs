@@ -0,0 +1,151 @@
+use crate::state::StateError;
+use crate::{BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+use std::sync::Arc;
+
+type InspectFn<B> = Arc<dyn Fn(&NodeResult<B>, &B) + Send + Sync>;
+
+/// Calls a closure with its child's result, then passes the result through
+///
+/// Like [`Iterator::inspect`], this is meant for logging and breakpoints.
+pub struct Inspect<B> {
+    inspect: InspectFn<B>,
+    child: BehaviorArc<B>,
+}
+
+impl<B> std::fmt::Debug for Inspect<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Inspect")
+            .field("child", &self.child)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> Inspect<B> {
+    pub fn new<F>(inspect: F, child: BehaviorArc<B>) -> Self
+    where
+        F: Fn(&NodeResult<B>, &B) + Send + Sync + 'static,
+    {
+        Self {
+            inspect: Arc::new(inspect),
+            child,
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for Inspect<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let res = self.child.clone().tick(blackboard);
+        (self.inspect)(&res, blackboard);
+        match res {
+            NodeResult::Running(resume) => NodeResult::Running(
+                Self {
+                    inspect: self.inspect.clone(),
+                    child: resume,
+                }
+                .arc(),
+            ),
+            res => res,
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Decorator
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.child.estimated_cost()
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        self.child.serialize_state(out)
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        Ok(Self {
+            inspect: self.inspect.clone(),
+            child: self.child.deserialize_state(bytes)?,
+        }
+        .arc())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::{
+            tests::{Context, PushAfter},
+            Inverter, Succeeder,
+        },
+        BehaviorRunner,
+    };
+
+    #[test]
+    fn inspect_taps_each_tick() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let tap = seen.clone();
+        let mut runner = BehaviorRunner::from_node(Inspect::new(
+            move |res: &NodeResult<Context>, context: &Context| {
+                tap.lock()
+                    .unwrap()
+                    .push((res.to_string(), context.stack.len()))
+            },
+            PushAfter {
+                steps: 2,
+                payload: 7,
+                fail: false,
+            }
+            .arc(),
+        ));
+
+        let mut context = Context { stack: Vec::new() };
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(context.stack == vec![7]);
+        check!(
+            *seen.lock().unwrap()
+                == vec![
+                    ("running".to_string(), 0),
+                    ("running".to_string(), 0),
+                    ("success".to_string(), 1),
+                ]
+        );
+    }
+
+    #[test]
+    fn inspect_passes_result_through() {
+        let mut context = Context { stack: Vec::new() };
+
+        let success = Inspect::new(|_, _| {}, Succeeder::default().arc()).arc();
+        check!(matches!(success.tick(&mut context), NodeResult::Success));
+
+        let failure =
+            Inspect::new(|_, _| {}, Inverter::new(Succeeder::default().arc()).arc()).arc();
+        check!(matches!(failure.tick(&mut context), NodeResult::Failure));
+
+        let running = Inspect::new(
+            |_, _| {},
+            PushAfter {
+                steps: 1,
+                payload: 1,
+                fail: false,
+            }
+            .arc(),
+        )
+        .arc();
+        check!(matches!(running.tick(&mut context), NodeResult::Running(_)));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{BehaviorArc, BehaviorNode, NodeResult};
+use crate::state::StateError;
+use crate::{BehaviorArc, BehaviorNode, NodeKind, NodeResult};
 use std::sync::Arc;
 
 /// Inverts the result of its child
@@ -30,6 +31,49 @@ impl<B: 'static> BehaviorNode<B> for Inverter<B> {
             NodeResult::Running(resume) => NodeResult::Running(Inverter::new(resume).arc()),
         }
     }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Inverter
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.child.estimated_cost()
+    }
+
+    fn success_probability(&self, blackboard: &B) -> f32 {
+        1.0 - self.child.success_probability(blackboard)
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| self.child.value_eq(other.child.as_ref()))
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let [child] = <[_; 1]>::try_from(children).ok()?;
+        Some(Inverter::new(child).arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        self.child.serialize_state(out)
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        Ok(Inverter::new(self.child.deserialize_state(bytes)?).arc())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,88 @@
+use crate::{BehaviorNode, NodeResult};
+use std::sync::{Arc, Mutex};
+
+type Apply<B, T> = Arc<dyn Fn(&mut B, T) + Send + Sync>;
+
+/// Applies one item from an iterator per tick
+///
+/// Returns `Running` after each item, and `Success` on the first tick that
+/// finds the iterator exhausted. The iterator lives inside the node, so it
+/// is only drained once: reactivating a finished `IterAction` succeeds
+/// straight away.
+pub struct IterAction<B, I: Iterator> {
+    items: Arc<Mutex<I>>,
+    apply: Apply<B, I::Item>,
+}
+
+impl<B, I: Iterator> std::fmt::Debug for IterAction<B, I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IterAction").finish_non_exhaustive()
+    }
+}
+
+impl<B, I: Iterator> IterAction<B, I> {
+    pub fn new<F>(items: impl IntoIterator<IntoIter = I>, apply: F) -> Self
+    where
+        F: Fn(&mut B, I::Item) + Send + Sync + 'static,
+    {
+        Self {
+            items: Arc::new(Mutex::new(items.into_iter())),
+            apply: Arc::new(apply),
+        }
+    }
+}
+
+impl<B: 'static, I> BehaviorNode<B> for IterAction<B, I>
+where
+    I: Iterator + Send + 'static,
+{
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let next = self
+            .items
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .next();
+        match next {
+            Some(item) => {
+                (self.apply)(blackboard, item);
+                self.running()
+            }
+            None => NodeResult::Success,
+        }
+    }
+
+    fn is_stateful(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::tests::{test_with_context, Context},
+        BehaviorRunner,
+    };
+
+    #[test]
+    fn iter_action_applies_one_item_per_tick() {
+        let mut runner =
+            BehaviorRunner::from_node(IterAction::new([3, 1, 4], |context: &mut Context, item| {
+                context.stack.push(item)
+            }));
+        let mut context = Context { stack: vec![] };
+        for expected in [vec![3], vec![3, 1], vec![3, 1, 4]] {
+            check!(runner.proceed(&mut context) == None);
+            check!(context.stack == expected);
+        }
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(context.stack == vec![3, 1, 4]);
+
+        // Already drained
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 0);
+        check!(res == Some(true));
+        check!(context.stack.is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::rng::SplitMix64;
+use crate::state::{self, StateError};
+use crate::{BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+use std::sync::Arc;
+
+/// Waits a random number of ticks before running its child
+///
+/// The delay is drawn from `0..max_delay` using a seed read from the
+/// blackboard, so agents with different seeds start their child on different
+/// ticks. Every activation is delayed; to stagger only the start of a loop,
+/// put the `Jitter` around the loop rather than inside it.
+pub struct Jitter<B> {
+    max_delay: u32,
+    seed: Arc<dyn Fn(&B) -> u64 + Send + Sync>,
+    child: BehaviorArc<B>,
+}
+
+impl<B> std::fmt::Debug for Jitter<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Jitter")
+            .field("max_delay", &self.max_delay)
+            .field("child", &self.child)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> Jitter<B> {
+    pub fn new<F>(max_delay: u32, seed: F, child: BehaviorArc<B>) -> Self
+    where
+        F: Fn(&B) -> u64 + Send + Sync + 'static,
+    {
+        Self {
+            max_delay,
+            seed: Arc::new(seed),
+            child,
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for Jitter<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let delay = match self.max_delay {
+            0 => 0,
+            max => SplitMix64::new((self.seed)(blackboard)).below(max as u64) as u32,
+        };
+        JitterDelay {
+            remaining: delay,
+            child: self.child.clone(),
+            resume: None,
+        }
+        .arc()
+        .tick(blackboard)
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Decorator
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.child.estimated_cost()
+    }
+
+    fn hash_params(&self, state: &mut dyn std::hash::Hasher) {
+        state.write_u32(self.max_delay);
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let remaining = u32::try_from(state::read_u64(bytes)?)
+            .ok()
+            .filter(|remaining| *remaining < self.max_delay.max(1))
+            .ok_or(StateError::Invalid(self.name()))?;
+        let resume = state::read_option(bytes, |bytes| self.child.deserialize_state(bytes))?;
+        if remaining > 0 && resume.is_some() {
+            return Err(StateError::Invalid(self.name()));
+        }
+        Ok(JitterDelay {
+            remaining,
+            child: self.child.clone(),
+            resume,
+        }
+        .arc())
+    }
+}
+
+/// A running [`Jitter`]: first the ticks left to wait, then the child
+pub(crate) struct JitterDelay<B> {
+    remaining: u32,
+    child: BehaviorArc<B>,
+    resume: Option<BehaviorArc<B>>,
+}
+
+impl<B> std::fmt::Debug for JitterDelay<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JitterDelay")
+            .field("remaining", &self.remaining)
+            .field("child", &self.child)
+            .field("resume", &self.resume)
+            .finish()
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for JitterDelay<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        if self.remaining > 0 {
+            return NodeResult::Running(
+                JitterDelay {
+                    remaining: self.remaining - 1,
+                    child: self.child.clone(),
+                    resume: None,
+                }
+                .arc(),
+            );
+        }
+        let node = self.resume.as_ref().unwrap_or(&self.child).clone();
+        match node.tick(blackboard) {
+            NodeResult::Running(resume) => NodeResult::Running(
+                JitterDelay {
+                    remaining: 0,
+                    child: self.child.clone(),
+                    resume: Some(resume),
+                }
+                .arc(),
+            ),
+            res => res,
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Decorator
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        match self.remaining {
+            0 => self.resume.as_ref().unwrap_or(&self.child).estimated_cost(),
+            _ => 1,
+        }
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.resume.iter().cloned().collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_u64(out, u64::from(self.remaining));
+        state::write_option(out, self.resume.as_ref(), |out, resume| {
+            resume.serialize_state(out)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{composite::Repeated, BehaviorRunner};
+
+    #[derive(Debug, Default)]
+    struct Agent {
+        seed: u64,
+        tick: u32,
+        started: Option<u32>,
+    }
+
+    #[derive(Debug)]
+    struct Start;
+
+    impl BehaviorNode<Agent> for Start {
+        fn tick(self: Arc<Self>, agent: &mut Agent) -> NodeResult<Agent> {
+            agent.started.get_or_insert(agent.tick);
+            NodeResult::Success
+        }
+    }
+
+    #[test]
+    fn jitter_staggers_agents() {
+        let tree = Jitter::new(
+            8,
+            |agent: &Agent| agent.seed,
+            Repeated::new(Start.arc()).arc(),
+        )
+        .arc();
+
+        let mut starts = vec![];
+        for seed in 0..6 {
+            let mut runner = BehaviorRunner::new(tree.clone());
+            let mut agent = Agent {
+                seed,
+                ..Default::default()
+            };
+            while agent.tick < 10 {
+                check!(runner.proceed(&mut agent) == None);
+                agent.tick += 1;
+            }
+            let started = agent.started.unwrap();
+            check!(started < 8);
+            starts.push(started);
+        }
+        starts.sort();
+        starts.dedup();
+        check!(starts.len() > 1);
+    }
+
+    #[test]
+    fn jitter_is_deterministic_per_seed() {
+        let start_tick = |seed| {
+            let mut runner =
+                BehaviorRunner::from_node(Jitter::new(5, |agent: &Agent| agent.seed, Start.arc()));
+            let mut agent = Agent {
+                seed,
+                ..Default::default()
+            };
+            while runner.proceed(&mut agent).is_none() {
+                agent.tick += 1;
+            }
+            agent.started
+        };
+        check!(start_tick(42) == start_tick(42));
+    }
+}
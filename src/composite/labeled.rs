@@ -0,0 +1,454 @@
+//! Variants of [`super::Sequence`] and [`super::Selector`] where every child
+//! carries a label, so debugging output can name a child instead of
+//! pointing at an index.
+
+use crate::state::{self, StateError};
+use crate::{explain, fallible, BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+use std::hash::Hasher;
+use std::sync::Arc;
+
+type LabeledChildren<B> = Arc<[(&'static str, BehaviorArc<B>)]>;
+type FailureReport<B> = Arc<dyn Fn(&mut B, &'static str) + Send + Sync>;
+
+pub struct LabeledSequence<B> {
+    pub(crate) sub: LabeledChildren<B>,
+    on_child_failure: Option<FailureReport<B>>,
+}
+
+impl<B> std::fmt::Debug for LabeledSequence<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("LabeledSequence<{:p}>", self.sub.as_ref()))
+            .field("sub", &self.sub)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> FromIterator<(&'static str, BehaviorArc<B>)> for LabeledSequence<B> {
+    fn from_iter<T: IntoIterator<Item = (&'static str, BehaviorArc<B>)>>(iter: T) -> Self {
+        Self {
+            sub: Arc::from(iter.into_iter().collect::<Vec<_>>()),
+            on_child_failure: None,
+        }
+    }
+}
+
+impl<B> LabeledSequence<B> {
+    pub fn new(children: impl IntoIterator<Item = (&'static str, BehaviorArc<B>)>) -> Self {
+        children.into_iter().collect()
+    }
+
+    /// Calls `report` with the label of every child that fails
+    pub fn on_child_failure<F>(mut self, report: F) -> Self
+    where
+        F: Fn(&mut B, &'static str) + Send + Sync + 'static,
+    {
+        self.on_child_failure = Some(Arc::new(report));
+        self
+    }
+
+    pub fn labels(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.sub.iter().map(|(label, _)| *label)
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for LabeledSequence<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        tick_labeled(
+            false,
+            self.sub.clone(),
+            self.on_child_failure.clone(),
+            0,
+            None,
+            blackboard,
+        )
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Sequence
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.sub
+            .first()
+            .map_or(1, |(_, child)| child.estimated_cost())
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            self.sub.len() == other.sub.len()
+                && self
+                    .sub
+                    .iter()
+                    .zip(other.sub.iter())
+                    .all(|((la, a), (lb, b))| la == lb && a.value_eq(b.as_ref()))
+        })
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.iter().map(|(_, child)| child.clone()).collect()
+    }
+
+    fn child_label(&self, index: usize) -> Option<&'static str> {
+        self.sub.get(index).map(|(label, _)| *label)
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        self.labels()
+            .for_each(|label| state.write(label.as_bytes()));
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let index = state::read_index(bytes, self.sub.len(), self.name())?;
+        Ok(LabeledResume {
+            selector: false,
+            seq: self.sub.clone(),
+            on_child_failure: self.on_child_failure.clone(),
+            index,
+            resume: self.sub[index].1.deserialize_state(bytes)?,
+        }
+        .arc())
+    }
+}
+
+pub struct LabeledSelector<B> {
+    pub(crate) sub: LabeledChildren<B>,
+    on_child_failure: Option<FailureReport<B>>,
+}
+
+impl<B> std::fmt::Debug for LabeledSelector<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("LabeledSelector<{:p}>", self.sub.as_ref()))
+            .field("sub", &self.sub)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> FromIterator<(&'static str, BehaviorArc<B>)> for LabeledSelector<B> {
+    fn from_iter<T: IntoIterator<Item = (&'static str, BehaviorArc<B>)>>(iter: T) -> Self {
+        Self {
+            sub: Arc::from(iter.into_iter().collect::<Vec<_>>()),
+            on_child_failure: None,
+        }
+    }
+}
+
+impl<B> LabeledSelector<B> {
+    pub fn new(children: impl IntoIterator<Item = (&'static str, BehaviorArc<B>)>) -> Self {
+        children.into_iter().collect()
+    }
+
+    /// Calls `report` with the label of every child that fails
+    pub fn on_child_failure<F>(mut self, report: F) -> Self
+    where
+        F: Fn(&mut B, &'static str) + Send + Sync + 'static,
+    {
+        self.on_child_failure = Some(Arc::new(report));
+        self
+    }
+
+    pub fn labels(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.sub.iter().map(|(label, _)| *label)
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for LabeledSelector<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        tick_labeled(
+            true,
+            self.sub.clone(),
+            self.on_child_failure.clone(),
+            0,
+            None,
+            blackboard,
+        )
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Selector
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.sub
+            .first()
+            .map_or(1, |(_, child)| child.estimated_cost())
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            self.sub.len() == other.sub.len()
+                && self
+                    .sub
+                    .iter()
+                    .zip(other.sub.iter())
+                    .all(|((la, a), (lb, b))| la == lb && a.value_eq(b.as_ref()))
+        })
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.iter().map(|(_, child)| child.clone()).collect()
+    }
+
+    fn child_label(&self, index: usize) -> Option<&'static str> {
+        self.sub.get(index).map(|(label, _)| *label)
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        self.labels()
+            .for_each(|label| state.write(label.as_bytes()));
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let index = state::read_index(bytes, self.sub.len(), self.name())?;
+        Ok(LabeledResume {
+            selector: true,
+            seq: self.sub.clone(),
+            on_child_failure: self.on_child_failure.clone(),
+            index,
+            resume: self.sub[index].1.deserialize_state(bytes)?,
+        }
+        .arc())
+    }
+}
+
+// Both labeled composites share their resume logic; the only difference is
+// whether a child's success or its failure ends the composite.
+fn tick_labeled<B: 'static>(
+    selector: bool,
+    seq: LabeledChildren<B>,
+    on_child_failure: Option<FailureReport<B>>,
+    start: usize,
+    resume: Option<BehaviorArc<B>>,
+    blackboard: &mut B,
+) -> NodeResult<B> {
+    let mut resume = resume;
+    for (idx, (label, sub)) in seq.iter().enumerate().skip(start) {
+        let node = resume.take().unwrap_or_else(|| sub.clone());
+        let Some(res) = fallible::tick_child(node, blackboard) else {
+            return NodeResult::Failure;
+        };
+        match res {
+            NodeResult::Success if selector => {
+                explain::note(|out| out.push_str(&format!("selected branch `{label}`")));
+                return NodeResult::Success;
+            }
+            NodeResult::Success => {}
+            NodeResult::Failure => {
+                if let Some(report) = on_child_failure.as_ref() {
+                    report(blackboard, label);
+                }
+                if !selector {
+                    explain::note(|out| out.push_str(&format!("failed at step `{label}`")));
+                    return NodeResult::Failure;
+                }
+            }
+            NodeResult::Running(resume) => {
+                return NodeResult::Running(
+                    LabeledResume {
+                        selector,
+                        seq: seq.clone(),
+                        on_child_failure,
+                        index: idx,
+                        resume,
+                    }
+                    .arc(),
+                )
+            }
+        }
+    }
+    if selector {
+        NodeResult::Failure
+    } else {
+        NodeResult::Success
+    }
+}
+
+pub(crate) struct LabeledResume<B> {
+    selector: bool,
+    seq: LabeledChildren<B>,
+    on_child_failure: Option<FailureReport<B>>,
+    pub(crate) index: usize,
+    pub(crate) resume: BehaviorArc<B>,
+}
+
+impl<B> std::fmt::Debug for LabeledResume<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = if self.selector {
+            "LabeledSelectorResume"
+        } else {
+            "LabeledSequenceResume"
+        };
+        f.debug_struct(&format!("{name}<{:p}>", self.seq.as_ref()))
+            .field("label", &self.seq[self.index].0)
+            .field("resume", &self.resume)
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for LabeledResume<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        tick_labeled(
+            self.selector,
+            self.seq.clone(),
+            self.on_child_failure.clone(),
+            self.index,
+            Some(self.resume.clone()),
+            blackboard,
+        )
+    }
+
+    fn kind(&self) -> NodeKind {
+        if self.selector {
+            NodeKind::Selector
+        } else {
+            NodeKind::Sequence
+        }
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.estimated_cost()
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.resume.clone()]
+    }
+
+    fn active_origins(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.seq[self.index].1.clone()]
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_u64(out, self.index as u64);
+        self.resume.serialize_state(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::{
+            tests::{test_with_context, Context, PushAfter},
+            Inverter, Succeeder,
+        },
+        BehaviorRunner,
+    };
+
+    fn failing() -> BehaviorArc<Context> {
+        Inverter::new(Succeeder::default().arc()).arc()
+    }
+
+    #[test]
+    fn labeled_selector_reports_failed_labels() {
+        let selector = LabeledSelector::new([
+            ("melee", failing()),
+            (
+                "shoot",
+                PushAfter {
+                    steps: 1,
+                    payload: 1,
+                    fail: true,
+                }
+                .arc(),
+            ),
+            (
+                "flee",
+                PushAfter {
+                    steps: 1,
+                    payload: 2,
+                    fail: false,
+                }
+                .arc(),
+            ),
+        ])
+        .on_child_failure(|context: &mut Context, label| context.stack.push(-(label.len() as i32)));
+        check!(selector.labels().collect::<Vec<_>>() == vec!["melee", "shoot", "flee"]);
+
+        let (res, context) = test_with_context(
+            || Context { stack: Vec::new() },
+            BehaviorRunner::from_node(selector),
+            10,
+        );
+        check!(res == Some(true));
+        // "melee" and "shoot" fail (reported by label length), then "flee" pushes
+        check!(context.stack == vec![-5, 1, -5, 2]);
+    }
+
+    #[test]
+    fn labeled_sequence_stops_at_failed_label() {
+        let labels = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let reported = labels.clone();
+        let sequence = LabeledSequence::new([
+            ("ready", Succeeder::default().arc()),
+            ("aim", failing()),
+            ("fire", Succeeder::default().arc()),
+        ])
+        .on_child_failure(move |_: &mut Context, label| reported.lock().unwrap().push(label));
+
+        let (res, _) = test_with_context(
+            || Context { stack: Vec::new() },
+            BehaviorRunner::from_node(sequence),
+            0,
+        );
+        check!(res == Some(false));
+        check!(*labels.lock().unwrap() == vec!["aim"]);
+    }
+
+    #[test]
+    fn labeled_resume_debug_shows_label() {
+        let mut runner = BehaviorRunner::from_node(LabeledSequence::new([
+            ("ready", Succeeder::default().arc()),
+            (
+                "aim",
+                PushAfter {
+                    steps: 1,
+                    payload: 1,
+                    fail: false,
+                }
+                .arc(),
+            ),
+        ]));
+        let mut context = Context { stack: Vec::new() };
+        check!(runner.proceed(&mut context) == None);
+        let debug = format!("{runner:?}");
+        check!(debug.contains("LabeledSequenceResume"));
+        check!(debug.contains("label: \"aim\""));
+    }
+
+    #[test]
+    fn explanations_name_the_label() {
+        let sequence =
+            LabeledSequence::new([("ready", Succeeder::default().arc()), ("shoot", failing())]);
+        let mut runner = BehaviorRunner::from_node(sequence).with_explanations();
+        check!(runner.proceed(&mut Context { stack: Vec::new() }) == Some(false));
+        check!(runner.explanation() == Some("failed at step `shoot`"));
+
+        let selector =
+            LabeledSelector::new([("melee", failing()), ("flee", Succeeder::default().arc())]);
+        let mut runner = BehaviorRunner::from_node(selector).with_explanations();
+        check!(runner.proceed(&mut Context { stack: Vec::new() }) == Some(true));
+        check!(runner.explanation() == Some("selected branch `flee`"));
+    }
+
+    #[test]
+    fn visualize_shows_labels() {
+        let tree =
+            LabeledSequence::new([("ready", Succeeder::default().arc()), ("shoot", failing())])
+                .arc();
+        check!(
+            crate::visualize::to_text(&tree)
+                == "LabeledSequence\n  ready: Succeeder\n  shoot: Inverter\n    Succeeder\n"
+        );
+        check!(crate::visualize::to_dot(&tree).contains("[label=\"shoot\"];"));
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::{BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+
+type Build<B> = Box<dyn FnOnce() -> BehaviorArc<B> + Send>;
+
+/// Builds its child the first time it is ticked
+///
+/// For subtrees that are expensive to construct and may never run. The
+/// child is built once and kept, so later activations reuse it. Until then
+/// the node reports no children. See [`super::Sequence::from_lazy`].
+pub struct Lazy<B> {
+    build: Mutex<Option<Build<B>>>,
+    child: OnceLock<BehaviorArc<B>>,
+}
+
+impl<B> std::fmt::Debug for Lazy<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Lazy")
+            .field("child", &self.child.get())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> Lazy<B> {
+    pub fn new<F>(build: F) -> Self
+    where
+        F: FnOnce() -> BehaviorArc<B> + Send + 'static,
+    {
+        Self {
+            build: Mutex::new(Some(Box::new(build))),
+            child: OnceLock::new(),
+        }
+    }
+
+    /// The child, if it has been built yet
+    pub fn built(&self) -> Option<&BehaviorArc<B>> {
+        self.child.get()
+    }
+
+    fn child(&self) -> &BehaviorArc<B> {
+        self.child.get_or_init(|| {
+            let build = self
+                .build
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .take()
+                .expect("Lazy builder is only taken once");
+            build()
+        })
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for Lazy<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        // The running child carries on by itself; only a fresh activation
+        // comes back through here
+        self.child().clone().tick(blackboard)
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Decorator
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.built().into_iter().cloned().collect()
+    }
+}
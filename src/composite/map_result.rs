@@ -0,0 +1,187 @@
+use crate::state::{self, StateError};
+use crate::{BehaviorArc, BehaviorNode, NodeKind, NodeResult, NodeResultKind};
+use std::sync::Arc;
+
+type MapFn = Arc<dyn Fn(NodeResultKind) -> NodeResultKind + Send + Sync>;
+
+/// Remaps the status of its child
+///
+/// A generalization of [`super::Inverter`] and [`super::Succeeder`]:
+/// - A terminal status may map to anything. Mapping it to `Running`
+///   restarts the child from scratch on the next tick, so mapping both
+///   terminal statuses to `Running` makes a node that never completes.
+/// - `Running` must map to `Running`. Mapping it to a terminal status would
+///   drop the child mid-run, and is disallowed (this is checked in debug
+///   builds).
+pub struct MapResult<B> {
+    map: MapFn,
+    child: BehaviorArc<B>,
+    resume: Option<BehaviorArc<B>>,
+}
+
+impl<B> std::fmt::Debug for MapResult<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MapResult")
+            .field("child", &self.child)
+            .field("resume", &self.resume)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> MapResult<B> {
+    pub fn new<F>(map: F, child: BehaviorArc<B>) -> Self
+    where
+        F: Fn(NodeResultKind) -> NodeResultKind + Send + Sync + 'static,
+    {
+        Self {
+            map: Arc::new(map),
+            child,
+            resume: None,
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for MapResult<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let res = self
+            .resume
+            .as_ref()
+            .unwrap_or(&self.child)
+            .clone()
+            .tick(blackboard);
+        let mapped = (self.map)(res.kind());
+        match (res, mapped) {
+            (NodeResult::Running(resume), NodeResultKind::Running) => NodeResult::Running(
+                Self {
+                    map: self.map.clone(),
+                    child: self.child.clone(),
+                    resume: Some(resume),
+                }
+                .arc(),
+            ),
+            (_, NodeResultKind::Running) => NodeResult::Running(
+                Self {
+                    map: self.map.clone(),
+                    child: self.child.clone(),
+                    resume: None,
+                }
+                .arc(),
+            ),
+            (res, mapped) => {
+                debug_assert!(
+                    res.kind() != NodeResultKind::Running,
+                    "MapResult cannot map Running to {mapped:?}"
+                );
+                match mapped {
+                    NodeResultKind::Success => NodeResult::Success,
+                    _ => NodeResult::Failure,
+                }
+            }
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Decorator
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.as_ref().unwrap_or(&self.child).estimated_cost()
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.resume.iter().cloned().collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_option(out, self.resume.as_ref(), |out, resume| {
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        Ok(Self {
+            map: self.map.clone(),
+            child: self.child.clone(),
+            resume: state::read_option(bytes, |bytes| self.child.deserialize_state(bytes))?,
+        }
+        .arc())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        testing::{AlwaysFail, AlwaysSucceed, FailAfter, SucceedAfter},
+        BehaviorRunner,
+    };
+
+    fn run(tree: MapResult<()>, limit: usize) -> (usize, Option<bool>) {
+        let mut runner = BehaviorRunner::from_node(tree);
+        for proceeds in 1..=limit {
+            if let Some(res) = runner.proceed(&mut ()) {
+                return (proceeds, Some(res));
+            }
+        }
+        (limit, None)
+    }
+
+    fn invert(kind: NodeResultKind) -> NodeResultKind {
+        match kind {
+            NodeResultKind::Success => NodeResultKind::Failure,
+            NodeResultKind::Failure => NodeResultKind::Success,
+            NodeResultKind::Running => NodeResultKind::Running,
+        }
+    }
+
+    fn succeed(kind: NodeResultKind) -> NodeResultKind {
+        match kind {
+            NodeResultKind::Running => NodeResultKind::Running,
+            _ => NodeResultKind::Success,
+        }
+    }
+
+    fn fail(kind: NodeResultKind) -> NodeResultKind {
+        match kind {
+            NodeResultKind::Running => NodeResultKind::Running,
+            _ => NodeResultKind::Failure,
+        }
+    }
+
+    #[test]
+    fn map_result_as_inverter() {
+        check!(run(MapResult::new(invert, AlwaysSucceed.arc()), 10) == (1, Some(false)));
+        check!(run(MapResult::new(invert, SucceedAfter(3).arc()), 10) == (4, Some(false)));
+        check!(run(MapResult::new(invert, FailAfter(2).arc()), 10) == (3, Some(true)));
+    }
+
+    #[test]
+    fn map_result_as_succeeder() {
+        check!(run(MapResult::new(succeed, AlwaysFail.arc()), 10) == (1, Some(true)));
+        check!(run(MapResult::new(succeed, FailAfter(2).arc()), 10) == (3, Some(true)));
+        check!(run(MapResult::new(succeed, SucceedAfter(1).arc()), 10) == (2, Some(true)));
+    }
+
+    #[test]
+    fn map_result_as_failer() {
+        check!(run(MapResult::new(fail, AlwaysSucceed.arc()), 10) == (1, Some(false)));
+        check!(run(MapResult::new(fail, SucceedAfter(2).arc()), 10) == (3, Some(false)));
+        check!(run(MapResult::new(fail, FailAfter(1).arc()), 10) == (2, Some(false)));
+    }
+
+    #[test]
+    fn map_result_to_running_never_completes() {
+        check!(
+            run(
+                MapResult::new(|_| NodeResultKind::Running, AlwaysSucceed.arc()),
+                10
+            ) == (10, None)
+        );
+    }
+}
@@ -2,12 +2,59 @@
 //! [`super::Selector`], but instead of polling each node individually
 //! "in sequence", all nodes are polled each poll step.
 
+use std::hash::Hasher;
 use std::sync::Arc;
 
-use crate::{BehaviorArc, BehaviorNode, NodeResult};
+use crate::params::NodeParams;
+use crate::state::{self, StateError};
+use crate::{children_value_eq, fallible, BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+
+type ChildFailure<B> = Arc<dyn Fn(&mut B, usize, &[usize]) + Send + Sync>;
+
+/// Writes the original index and the state of each running child
+fn write_running<'a, B: 'static>(
+    out: &mut Vec<u8>,
+    running: impl ExactSizeIterator<Item = (usize, &'a BehaviorArc<B>)>,
+) -> Result<(), StateError> {
+    state::write_u64(out, running.len() as u64);
+    for (index, child) in running {
+        state::write_u64(out, index as u64);
+        child.serialize_state(out)?;
+    }
+    Ok(())
+}
+
+/// Reads back what [`write_running`] wrote, rebuilding each running child
+/// from `template(index)`, one of `len` children
+fn read_running<'a, B: 'static>(
+    bytes: &mut &[u8],
+    len: usize,
+    template: impl Fn(usize) -> &'a BehaviorArc<B>,
+    node: &'static str,
+) -> Result<Vec<(usize, BehaviorArc<B>)>, StateError> {
+    let count = state::read_u64(bytes)?;
+    if count > len as u64 {
+        return Err(StateError::Invalid(node));
+    }
+    let mut running: Vec<(usize, BehaviorArc<B>)> = Vec::new();
+    for _ in 0..count {
+        let index = state::read_index(bytes, len, node)?;
+        // Running children keep their order
+        if running.last().is_some_and(|(last, _)| *last >= index) {
+            return Err(StateError::Invalid(node));
+        }
+        running.push((index, template(index).deserialize_state(bytes)?));
+    }
+    Ok(running)
+}
 
 pub struct ParallelSequence<B> {
     pub(crate) sub: Arc<[BehaviorArc<B>]>,
+    /// The original index of each entry in `sub`
+    indices: Arc<[usize]>,
+    /// Original indices of the children that already succeeded
+    succeeded: Arc<[usize]>,
+    on_child_failure: Option<ChildFailure<B>>,
 }
 
 impl<B> std::fmt::Debug for ParallelSequence<B> {
@@ -18,10 +65,41 @@ impl<B> std::fmt::Debug for ParallelSequence<B> {
     }
 }
 
+impl<B> ParallelSequence<B> {
+    pub fn new(children: impl IntoIterator<Item = BehaviorArc<B>>) -> Self {
+        children.into_iter().collect()
+    }
+
+    /// Orders children by their key, so they're polled in the same order
+    /// no matter what order the source (like a `HashMap`) yields them in
+    pub fn sorted_by_key<K: Ord>(children: impl IntoIterator<Item = (K, BehaviorArc<B>)>) -> Self {
+        let mut children = children.into_iter().collect::<Vec<_>>();
+        children.sort_by(|(a, _), (b, _)| a.cmp(b));
+        children.into_iter().map(|(_, child)| child).collect()
+    }
+
+    /// Calls `report` when a child fails, with the failing child's index and
+    /// the indices of the children that had already succeeded
+    ///
+    /// Indices are positions in the list the sequence was built from.
+    pub fn on_child_failure<F>(mut self, report: F) -> Self
+    where
+        F: Fn(&mut B, usize, &[usize]) + Send + Sync + 'static,
+    {
+        self.on_child_failure = Some(Arc::new(report));
+        self
+    }
+}
+
+/// Children are polled in the order the iterator yields them
 impl<B, I: Into<BehaviorArc<B>>> FromIterator<I> for ParallelSequence<B> {
     fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        let sub = Arc::from(iter.into_iter().map(Into::into).collect::<Vec<_>>());
         Self {
-            sub: Arc::from(iter.into_iter().map(Into::into).collect::<Vec<_>>()),
+            indices: (0..<[_]>::len(&sub)).collect(),
+            sub,
+            succeeded: Arc::from([]),
+            on_child_failure: None,
         }
     }
 }
@@ -29,12 +107,24 @@ impl<B, I: Into<BehaviorArc<B>>> FromIterator<I> for ParallelSequence<B> {
 impl<B: 'static> BehaviorNode<B> for ParallelSequence<B> {
     fn tick(self: Arc<Self>, context: &mut B) -> NodeResult<B> {
         let mut new_children = vec![];
-        for child in self.sub.iter() {
-            match child.clone().tick(context) {
-                NodeResult::Failure => return NodeResult::Failure,
-                NodeResult::Success => {}
+        let mut new_indices = vec![];
+        let mut succeeded = self.succeeded.to_vec();
+        for (child, &index) in self.sub.iter().zip(self.indices.iter()) {
+            let Some(res) = fallible::tick_child(child.clone(), context) else {
+                return NodeResult::Failure;
+            };
+            match res {
+                NodeResult::Failure => {
+                    if let Some(report) = self.on_child_failure.as_ref() {
+                        succeeded.sort_unstable();
+                        report(context, index, &succeeded);
+                    }
+                    return NodeResult::Failure;
+                }
+                NodeResult::Success => succeeded.push(index),
                 NodeResult::Running(node) => {
                     new_children.push(node);
+                    new_indices.push(index);
                 }
             }
         }
@@ -45,62 +135,1325 @@ impl<B: 'static> BehaviorNode<B> for ParallelSequence<B> {
             NodeResult::Running(
                 Self {
                     sub: Arc::from(new_children),
+                    indices: Arc::from(new_indices),
+                    succeeded: Arc::from(succeeded),
+                    on_child_failure: self.on_child_failure.clone(),
                 }
                 .arc(),
             )
         }
     }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Parallel
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.sub
+            .iter()
+            .map(|child| child.estimated_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| children_value_eq(&self.sub, &other.sub))
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let mut edited = ParallelSequence::new(children);
+        edited.on_child_failure = self.on_child_failure.clone();
+        Some(edited.arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        write_running(out, self.indices.iter().copied().zip(self.sub.iter()))?;
+        state::write_indices(out, &self.succeeded);
+        Ok(())
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let len = self.sub.len();
+        let running = read_running(bytes, len, |index| &self.sub[index], self.name())?;
+        let succeeded = state::read_indices(bytes, len, self.name())?;
+        let (indices, sub): (Vec<_>, Vec<_>) = running.into_iter().unzip();
+        Ok(Self {
+            sub: Arc::from(sub),
+            indices: Arc::from(indices),
+            succeeded: Arc::from(succeeded),
+            on_child_failure: self.on_child_failure.clone(),
+        }
+        .arc())
+    }
 }
 
+/// Ticks all of its children until one of them succeeds
+///
+/// When a child wins, every sibling that is still running is aborted
+/// ([`BehaviorNode::on_abort`] is called along its active spine), including
+/// the ones the winning tick didn't get to.
 pub struct ParallelSelector<B> {
     pub(crate) sub: Arc<[BehaviorArc<B>]>,
+    /// The original index of each entry in `sub`
+    indices: Arc<[usize]>,
+    /// Whether `sub` holds running children rather than fresh ones
+    resumed: bool,
+}
+
+impl<B> std::fmt::Debug for ParallelSelector<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("ParallelSelector<{:p}>", self.sub.as_ref()))
+            .field("sub", &self.sub)
+            .finish()
+    }
+}
+
+impl<B> ParallelSelector<B> {
+    pub fn new(children: impl IntoIterator<Item = BehaviorArc<B>>) -> Self {
+        children.into_iter().collect()
+    }
+
+    /// Orders children by their key, so they're polled in the same order
+    /// no matter what order the source (like a `HashMap`) yields them in
+    pub fn sorted_by_key<K: Ord>(children: impl IntoIterator<Item = (K, BehaviorArc<B>)>) -> Self {
+        let mut children = children.into_iter().collect::<Vec<_>>();
+        children.sort_by(|(a, _), (b, _)| a.cmp(b));
+        children.into_iter().map(|(_, child)| child).collect()
+    }
+}
+
+impl<B: 'static> ParallelSelector<B> {
+    /// Aborts every child still running once the child at `index` finished
+    /// the selector, given the ones that returned `Running` before it
+    fn abort_siblings(&self, index: usize, running: &[BehaviorArc<B>], context: &mut B) {
+        let unreached = if self.resumed {
+            &self.sub[index + 1..]
+        } else {
+            &[]
+        };
+        for sibling in running.iter().chain(unreached) {
+            crate::abort_spine(sibling, context);
+        }
+    }
+}
+
+/// Children are polled in the order the iterator yields them
+impl<B, I: Into<BehaviorArc<B>>> FromIterator<I> for ParallelSelector<B> {
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        let sub = Arc::from(iter.into_iter().map(Into::into).collect::<Vec<_>>());
+        Self {
+            indices: (0..<[_]>::len(&sub)).collect(),
+            sub,
+            resumed: false,
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for ParallelSelector<B> {
+    fn tick(self: Arc<Self>, context: &mut B) -> NodeResult<B> {
+        let mut new_children = vec![];
+        let mut new_indices = vec![];
+        for (index, child) in self.sub.iter().enumerate() {
+            let Some(res) = fallible::tick_child(child.clone(), context) else {
+                self.abort_siblings(index, &new_children, context);
+                return NodeResult::Failure;
+            };
+            match res {
+                NodeResult::Success => {
+                    self.abort_siblings(index, &new_children, context);
+                    return NodeResult::Success;
+                }
+                NodeResult::Failure => {}
+                NodeResult::Running(node) => {
+                    new_children.push(node);
+                    new_indices.push(self.indices[index]);
+                }
+            }
+        }
+
+        if new_children.is_empty() {
+            NodeResult::Failure
+        } else {
+            NodeResult::Running(
+                Self {
+                    sub: Arc::from(new_children),
+                    indices: Arc::from(new_indices),
+                    resumed: true,
+                }
+                .arc(),
+            )
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Parallel
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.sub
+            .iter()
+            .map(|child| child.estimated_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| children_value_eq(&self.sub, &other.sub))
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        Some(ParallelSelector::new(children).arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        write_running(out, self.indices.iter().copied().zip(self.sub.iter()))
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let running = read_running(bytes, self.sub.len(), |index| &self.sub[index], self.name())?;
+        let (indices, sub): (Vec<_>, Vec<_>) = running.into_iter().unzip();
+        Ok(Self {
+            sub: Arc::from(sub),
+            indices: Arc::from(indices),
+            resumed: true,
+        }
+        .arc())
+    }
+}
+
+/// Ticks every child each step and finishes with the result of the first
+/// one to finish, aborting the rest
+///
+/// When several children finish on the same step, the one with the lowest
+/// index wins. Every child is still ticked on that step, so the losers see
+/// it too. Without children the race fails.
+pub struct ParallelRace<B> {
+    pub(crate) sub: Arc<[BehaviorArc<B>]>,
+    /// The original index of each entry in `sub`
+    indices: Arc<[usize]>,
+}
+
+impl<B> std::fmt::Debug for ParallelRace<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("ParallelRace<{:p}>", self.sub.as_ref()))
+            .field("sub", &self.sub)
+            .finish()
+    }
+}
+
+impl<B> ParallelRace<B> {
+    pub fn new(children: impl IntoIterator<Item = BehaviorArc<B>>) -> Self {
+        children.into_iter().collect()
+    }
+}
+
+impl<B, I: Into<BehaviorArc<B>>> FromIterator<I> for ParallelRace<B> {
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        let sub = Arc::from(iter.into_iter().map(Into::into).collect::<Vec<_>>());
+        Self {
+            indices: (0..<[_]>::len(&sub)).collect(),
+            sub,
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for ParallelRace<B> {
+    fn tick(self: Arc<Self>, context: &mut B) -> NodeResult<B> {
+        let mut winner = None;
+        let mut new_children = vec![];
+        let mut new_indices = vec![];
+        for (child, &index) in self.sub.iter().zip(self.indices.iter()) {
+            let Some(res) = fallible::tick_child(child.clone(), context) else {
+                winner = Some(false);
+                break;
+            };
+            match res {
+                NodeResult::Running(node) => {
+                    new_children.push(node);
+                    new_indices.push(index);
+                }
+                res if winner.is_none() => winner = Some(matches!(res, NodeResult::Success)),
+                _ => {}
+            }
+        }
+
+        match winner {
+            Some(success) => {
+                for child in &new_children {
+                    crate::abort_spine(child, context);
+                }
+                if success {
+                    NodeResult::Success
+                } else {
+                    NodeResult::Failure
+                }
+            }
+            None if new_children.is_empty() => NodeResult::Failure,
+            None => NodeResult::Running(
+                Self {
+                    sub: Arc::from(new_children),
+                    indices: Arc::from(new_indices),
+                }
+                .arc(),
+            ),
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Parallel
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.sub
+            .iter()
+            .map(|child| child.estimated_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| children_value_eq(&self.sub, &other.sub))
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        Some(ParallelRace::new(children).arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        write_running(out, self.indices.iter().copied().zip(self.sub.iter()))
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let running = read_running(bytes, self.sub.len(), |index| &self.sub[index], self.name())?;
+        let (indices, sub): (Vec<_>, Vec<_>) = running.into_iter().unzip();
+        Ok(Self {
+            sub: Arc::from(sub),
+            indices: Arc::from(indices),
+        }
+        .arc())
+    }
+}
+
+/// Succeeds once the summed weight of its succeeded children reaches a
+/// threshold, and fails once the still-running children can't get it there.
+///
+/// Children still running when it finishes are aborted (see
+/// [`BehaviorNode::on_abort`]).
+pub struct WeightedQuorumParallel<B> {
+    pub(crate) sub: Arc<[(u32, BehaviorArc<B>)]>,
+    /// The original index of each entry in `sub`
+    indices: Arc<[usize]>,
+    threshold: u32,
+    accumulated: u32,
+    /// Whether `sub` holds running children rather than fresh ones
+    resumed: bool,
+}
+
+impl<B> std::fmt::Debug for WeightedQuorumParallel<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("WeightedQuorumParallel<{:p}>", self.sub.as_ref()))
+            .field("sub", &self.sub)
+            .field("threshold", &self.threshold)
+            .field("accumulated", &self.accumulated)
+            .finish()
+    }
+}
+
+impl<B> WeightedQuorumParallel<B> {
+    pub fn new(threshold: u32, children: impl IntoIterator<Item = (u32, BehaviorArc<B>)>) -> Self {
+        let sub = Arc::from(children.into_iter().collect::<Vec<_>>());
+        Self {
+            indices: (0..<[_]>::len(&sub)).collect(),
+            sub,
+            threshold,
+            accumulated: 0,
+            resumed: false,
+        }
+    }
+}
+
+impl<B: 'static> WeightedQuorumParallel<B> {
+    /// Aborts every child still running once the quorum was decided before
+    /// ticking the child at `next`, given the ones that returned `Running`
+    /// before it
+    fn abort_rest(&self, next: usize, running: &[(u32, BehaviorArc<B>)], context: &mut B) {
+        let unreached = if self.resumed {
+            self.sub.get(next..).unwrap_or_default()
+        } else {
+            &[]
+        };
+        for (_, child) in running.iter().chain(unreached) {
+            crate::abort_spine(child, context);
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for WeightedQuorumParallel<B> {
+    fn tick(self: Arc<Self>, context: &mut B) -> NodeResult<B> {
+        let mut accumulated = self.accumulated;
+        let mut remaining = 0u32;
+        let mut new_children = vec![];
+        let mut new_indices = vec![];
+        for (position, ((weight, child), &index)) in
+            self.sub.iter().zip(self.indices.iter()).enumerate()
+        {
+            let Some(res) = fallible::tick_child(child.clone(), context) else {
+                self.abort_rest(position + 1, &new_children, context);
+                return NodeResult::Failure;
+            };
+            match res {
+                NodeResult::Success => {
+                    accumulated = accumulated.saturating_add(*weight);
+                    if accumulated >= self.threshold {
+                        self.abort_rest(position + 1, &new_children, context);
+                        return NodeResult::Success;
+                    }
+                }
+                NodeResult::Failure => {}
+                NodeResult::Running(node) => {
+                    remaining = remaining.saturating_add(*weight);
+                    new_children.push((*weight, node));
+                    new_indices.push(index);
+                }
+            }
+        }
+
+        if accumulated >= self.threshold {
+            NodeResult::Success
+        } else if accumulated.saturating_add(remaining) < self.threshold {
+            self.abort_rest(self.sub.len(), &new_children, context);
+            NodeResult::Failure
+        } else {
+            NodeResult::Running(
+                Self {
+                    sub: Arc::from(new_children),
+                    indices: Arc::from(new_indices),
+                    threshold: self.threshold,
+                    accumulated,
+                    resumed: true,
+                }
+                .arc(),
+            )
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Parallel
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.sub
+            .iter()
+            .map(|(_, child)| child.estimated_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            self.threshold == other.threshold
+                && self.sub.len() == other.sub.len()
+                && self
+                    .sub
+                    .iter()
+                    .zip(other.sub.iter())
+                    .all(|((wa, a), (wb, b))| wa == wb && a.value_eq(b.as_ref()))
+        })
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.iter().map(|(_, child)| child.clone()).collect()
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_u32(self.threshold);
+        self.sub
+            .iter()
+            .for_each(|(weight, _)| state.write_u32(*weight));
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.iter().map(|(_, child)| child.clone()).collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_u64(out, self.accumulated as u64);
+        let running = self.sub.iter().map(|(_, child)| child);
+        write_running(out, self.indices.iter().copied().zip(running))
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let accumulated = state::read_u64(bytes)?;
+        let accumulated =
+            u32::try_from(accumulated).map_err(|_| StateError::Invalid(self.name()))?;
+        let running = read_running(
+            bytes,
+            self.sub.len(),
+            |index| &self.sub[index].1,
+            self.name(),
+        )?;
+        let (indices, sub): (Vec<_>, Vec<_>) = running
+            .into_iter()
+            .map(|(index, child)| (index, (self.sub[index].0, child)))
+            .unzip();
+        Ok(Self {
+            sub: Arc::from(sub),
+            indices: Arc::from(indices),
+            threshold: self.threshold,
+            accumulated,
+            resumed: true,
+        }
+        .arc())
+    }
+}
+
+/// Succeeds once `success_threshold` children have succeeded, and fails once
+/// `failure_threshold` have failed (or enough have failed that the success
+/// threshold is out of reach).
+///
+/// Children still running when it finishes are aborted (see
+/// [`BehaviorNode::on_abort`]).
+pub struct Parallel<B> {
+    pub(crate) sub: Arc<[BehaviorArc<B>]>,
+    /// The original index of each entry in `sub`
+    indices: Arc<[usize]>,
+    success_threshold: usize,
+    failure_threshold: usize,
+    succeeded: usize,
+    failed: usize,
+    /// Whether `sub` holds running children rather than fresh ones
+    resumed: bool,
+}
+
+impl<B> std::fmt::Debug for Parallel<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("Parallel<{:p}>", self.sub.as_ref()))
+            .field("sub", &self.sub)
+            .field("success_threshold", &self.success_threshold)
+            .field("failure_threshold", &self.failure_threshold)
+            .field("succeeded", &self.succeeded)
+            .field("failed", &self.failed)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParallelConfigError {
+    /// A threshold of zero would finish before any child was ticked
+    ZeroThreshold,
+    /// More children would have to finish than there are children
+    ThresholdTooHigh { threshold: usize, children: usize },
+}
+
+impl std::fmt::Display for ParallelConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParallelConfigError::ZeroThreshold => {
+                f.write_str("parallel thresholds must be at least 1")
+            }
+            ParallelConfigError::ThresholdTooHigh {
+                threshold,
+                children,
+            } => write!(
+                f,
+                "parallel threshold {threshold} can never be met by {children} children"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParallelConfigError {}
+
+impl<B> Parallel<B> {
+    /// Checks that both thresholds are between 1 and the number of children
+    pub fn new(
+        success_threshold: usize,
+        failure_threshold: usize,
+        children: impl IntoIterator<Item = BehaviorArc<B>>,
+    ) -> Result<Self, ParallelConfigError> {
+        let parallel = Self::new_unchecked(success_threshold, failure_threshold, children);
+        for threshold in [success_threshold, failure_threshold] {
+            if threshold == 0 {
+                return Err(ParallelConfigError::ZeroThreshold);
+            }
+            if threshold > parallel.sub.len() {
+                return Err(ParallelConfigError::ThresholdTooHigh {
+                    threshold,
+                    children: parallel.sub.len(),
+                });
+            }
+        }
+        Ok(parallel)
+    }
+
+    /// Skips the threshold checks of [`Parallel::new`]
+    ///
+    /// A zero threshold finishes on the first tick, and an unreachable
+    /// success threshold just means the node fails.
+    pub fn new_unchecked(
+        success_threshold: usize,
+        failure_threshold: usize,
+        children: impl IntoIterator<Item = BehaviorArc<B>>,
+    ) -> Self {
+        let sub = Arc::from(children.into_iter().collect::<Vec<_>>());
+        Self {
+            indices: (0..<[_]>::len(&sub)).collect(),
+            sub,
+            success_threshold,
+            failure_threshold,
+            succeeded: 0,
+            failed: 0,
+            resumed: false,
+        }
+    }
+}
+
+impl<B: 'static> Parallel<B> {
+    /// Aborts every child still running once the parallel finished before
+    /// ticking the child at `next`, given the ones that returned `Running`
+    /// before it
+    fn abort_rest(&self, next: usize, running: &[BehaviorArc<B>], context: &mut B) {
+        let unreached = if self.resumed {
+            self.sub.get(next..).unwrap_or_default()
+        } else {
+            &[]
+        };
+        for child in running.iter().chain(unreached) {
+            crate::abort_spine(child, context);
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for Parallel<B> {
+    fn tick(self: Arc<Self>, context: &mut B) -> NodeResult<B> {
+        let mut succeeded = self.succeeded;
+        let mut failed = self.failed;
+        let mut new_children = vec![];
+        let mut new_indices = vec![];
+        for (position, (child, &index)) in self.sub.iter().zip(self.indices.iter()).enumerate() {
+            if succeeded >= self.success_threshold {
+                self.abort_rest(position, &new_children, context);
+                return NodeResult::Success;
+            }
+            if failed >= self.failure_threshold {
+                self.abort_rest(position, &new_children, context);
+                return NodeResult::Failure;
+            }
+            let Some(res) = fallible::tick_child(child.clone(), context) else {
+                self.abort_rest(position + 1, &new_children, context);
+                return NodeResult::Failure;
+            };
+            match res {
+                NodeResult::Success => succeeded += 1,
+                NodeResult::Failure => failed += 1,
+                NodeResult::Running(node) => {
+                    new_children.push(node);
+                    new_indices.push(index);
+                }
+            }
+        }
+
+        let undecided = succeeded < self.success_threshold
+            && failed < self.failure_threshold
+            && succeeded + new_children.len() >= self.success_threshold;
+        if undecided {
+            return NodeResult::Running(
+                Self {
+                    sub: Arc::from(new_children),
+                    indices: Arc::from(new_indices),
+                    success_threshold: self.success_threshold,
+                    failure_threshold: self.failure_threshold,
+                    succeeded,
+                    failed,
+                    resumed: true,
+                }
+                .arc(),
+            );
+        }
+        self.abort_rest(self.sub.len(), &new_children, context);
+        if succeeded >= self.success_threshold {
+            NodeResult::Success
+        } else {
+            NodeResult::Failure
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Parallel
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.sub
+            .iter()
+            .map(|child| child.estimated_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        Some(Self::new_unchecked(self.success_threshold, self.failure_threshold, children).arc())
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_usize(self.success_threshold);
+        state.write_usize(self.failure_threshold);
+    }
+
+    fn params(&self) -> NodeParams {
+        [
+            ("success_threshold", self.success_threshold.to_string()),
+            ("failure_threshold", self.failure_threshold.to_string()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            self.success_threshold == other.success_threshold
+                && self.failure_threshold == other.failure_threshold
+                && children_value_eq(&self.sub, &other.sub)
+        })
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_u64(out, self.succeeded as u64);
+        state::write_u64(out, self.failed as u64);
+        write_running(out, self.indices.iter().copied().zip(self.sub.iter()))
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let succeeded = state::read_u64(bytes)? as usize;
+        let failed = state::read_u64(bytes)? as usize;
+        let running = read_running(bytes, self.sub.len(), |index| &self.sub[index], self.name())?;
+        let (indices, sub): (Vec<_>, Vec<_>) = running.into_iter().unzip();
+        Ok(Self {
+            sub: Arc::from(sub),
+            indices: Arc::from(indices),
+            success_threshold: self.success_threshold,
+            failure_threshold: self.failure_threshold,
+            succeeded,
+            failed,
+            resumed: true,
+        }
+        .arc())
+    }
+}
+
+/// How [`ParallelOver`] and [`ParallelN`] combine the results of their children
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FanOutPolicy {
+    /// Succeeds once every item succeeds, fails as soon as one fails
+    AllSucceed,
+    /// Succeeds as soon as one item succeeds, fails once every item fails
+    AnySucceed,
+}
+
+type ProjectItems<B, Item> = Arc<dyn Fn(&mut B) -> &mut [Item] + Send + Sync>;
+
+/// Runs the same subtree against every item of a collection on the blackboard
+///
+/// Each item gets its own copy of the child's progress, and all of them are
+/// polled each poll step (one after the other, since they share the
+/// blackboard). When it finishes early, the copies still running are
+/// aborted (see [`BehaviorNode::on_abort`]).
+///
+/// The collection must keep its length while the node runs. If items are
+/// added or removed mid-run, the node fails, aborting the copies whose item
+/// is still there; the ones whose item is gone can't be aborted.
+///
+/// Its [`BehaviorNode::children`] is the child subtree seen from the parent
+/// blackboard, and its [`BehaviorNode::active_children`] are the per-item
+/// copies still running, each ticking against its own item.
+pub struct ParallelOver<B, Item> {
+    project: ProjectItems<B, Item>,
+    template: Arc<ForItems<B, Item>>,
+    policy: FanOutPolicy,
+    running: Option<Arc<[Option<BehaviorArc<Item>>]>>,
+}
+
+impl<B, Item> std::fmt::Debug for ParallelOver<B, Item> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParallelOver")
+            .field("child", &self.template.node)
+            .field("policy", &self.policy)
+            .field("running", &self.running)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B, Item> ParallelOver<B, Item> {
+    pub fn new<F>(project: F, policy: FanOutPolicy, child: BehaviorArc<Item>) -> Self
+    where
+        F: Fn(&mut B) -> &mut [Item] + Send + Sync + 'static,
+    {
+        let project: ProjectItems<B, Item> = Arc::new(project);
+        Self {
+            template: Arc::new(ForItems::new(project.clone(), policy, child)),
+            project,
+            policy,
+            running: None,
+        }
+    }
+}
+
+/// Aborts every running per-item node, each against its own item
+fn abort_items<Item>(slots: &[Option<BehaviorArc<Item>>], items: &mut [Item]) {
+    for (slot, item) in slots.iter().zip(items) {
+        if let Some(node) = slot {
+            crate::abort_spine(node, item);
+        }
+    }
+}
+
+impl<B: 'static, Item: 'static> BehaviorNode<B> for ParallelOver<B, Item> {
+    fn tick(self: Arc<Self>, context: &mut B) -> NodeResult<B> {
+        let items = (self.project)(context);
+        let (mut slots, resumed) = match self.running.as_ref() {
+            // The collection was resized under the running copies
+            Some(running) if running.len() != items.len() => {
+                abort_items(running, items);
+                return NodeResult::Failure;
+            }
+            Some(running) => (running.to_vec(), true),
+            None => (vec![Some(self.template.node.clone()); items.len()], false),
+        };
+
+        let mut pending = false;
+        for index in 0..slots.len() {
+            let Some(node) = slots[index].take() else {
+                continue;
+            };
+            let res = fallible::tick_child(node, &mut items[index]);
+            let succeeded = match (res, self.policy) {
+                (None, _) => false,
+                (Some(NodeResult::Running(resume)), _) => {
+                    slots[index] = Some(resume);
+                    pending = true;
+                    continue;
+                }
+                (Some(NodeResult::Failure), FanOutPolicy::AllSucceed) => false,
+                (Some(NodeResult::Success), FanOutPolicy::AnySucceed) => true,
+                _ => continue,
+            };
+            // Finished early: stop the copies that are still running
+            if !resumed {
+                slots[index + 1..].fill(None);
+            }
+            abort_items(&slots, items);
+            return if succeeded {
+                NodeResult::Success
+            } else {
+                NodeResult::Failure
+            };
+        }
+
+        if pending {
+            NodeResult::Running(
+                Self {
+                    project: self.project.clone(),
+                    template: self.template.clone(),
+                    policy: self.policy,
+                    running: Some(Arc::from(slots)),
+                }
+                .arc(),
+            )
+        } else if self.policy == FanOutPolicy::AllSucceed {
+            NodeResult::Success
+        } else {
+            NodeResult::Failure
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Parallel
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_u8(self.policy as u8);
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.template.clone()]
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        let Some(running) = self.running.as_ref() else {
+            return Vec::new();
+        };
+        running
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let node = slot.clone()?;
+                Some(OnItem::wrap(
+                    self.project.clone(),
+                    index,
+                    node,
+                    Some(self.template.clone()),
+                ))
+            })
+            .collect()
+    }
+
+    fn active_origins(&self) -> Vec<BehaviorArc<B>> {
+        let running = self.running.iter().flat_map(|running| running.iter());
+        running
+            .flatten()
+            .map(|_| self.template.clone() as BehaviorArc<B>)
+            .collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        let running = self
+            .running
+            .as_ref()
+            .ok_or(StateError::Unsupported(self.name()))?;
+        state::write_u64(out, running.len() as u64);
+        for slot in running.iter() {
+            state::write_option(out, slot.as_ref(), |out, node| node.serialize_state(out))?;
+        }
+        Ok(())
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let items = state::read_u64(bytes)?;
+        // Each slot takes at least a byte, which bounds the count
+        if items > bytes.len() as u64 {
+            return Err(StateError::Truncated);
+        }
+        let running = (0..items)
+            .map(|_| state::read_option(bytes, |bytes| self.template.node.deserialize_state(bytes)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            project: self.project.clone(),
+            template: self.template.clone(),
+            policy: self.policy,
+            running: Some(Arc::from(running)),
+        }
+        .arc())
+    }
+}
+
+/// The child of a [`ParallelOver`], seen from the parent blackboard
+///
+/// Mirrors the subtree under `node`, so tools walking the parent tree see
+/// what runs against each item. Ticked on its own, it runs `node` over every
+/// item, like the `ParallelOver` it came from.
+struct ForItems<B, Item> {
+    project: ProjectItems<B, Item>,
+    policy: FanOutPolicy,
+    node: BehaviorArc<Item>,
+    children: Vec<Arc<ForItems<B, Item>>>,
+}
+
+impl<B, Item> std::fmt::Debug for ForItems<B, Item> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ForItems")
+            .field("node", &self.node)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B, Item> ForItems<B, Item> {
+    fn new(project: ProjectItems<B, Item>, policy: FanOutPolicy, node: BehaviorArc<Item>) -> Self {
+        let children = node
+            .children()
+            .into_iter()
+            .map(|child| Arc::new(ForItems::new(project.clone(), policy, child)))
+            .collect();
+        Self {
+            project,
+            policy,
+            node,
+            children,
+        }
+    }
+}
+
+impl<B: 'static, Item: 'static> BehaviorNode<B> for ForItems<B, Item> {
+    fn tick(self: Arc<Self>, context: &mut B) -> NodeResult<B> {
+        ParallelOver {
+            project: self.project.clone(),
+            policy: self.policy,
+            template: self,
+            running: None,
+        }
+        .arc()
+        .tick(context)
+    }
+
+    fn name(&self) -> &'static str {
+        self.node.name()
+    }
+
+    fn kind(&self) -> NodeKind {
+        self.node.kind()
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.node.estimated_cost()
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.children
+            .iter()
+            .map(|child| child.clone() as BehaviorArc<B>)
+            .collect()
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.node.is_leaf()
+    }
+
+    fn is_stateful(&self) -> bool {
+        self.node.is_stateful()
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.node.is_deterministic()
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        self.node.hash_params(state);
+    }
+
+    fn params(&self) -> NodeParams {
+        self.node.params()
+    }
+}
+
+/// One item's running node in a [`ParallelOver`], seen from the parent
+/// blackboard
+///
+/// `origin` is the part of the child subtree `node` was started from, when
+/// it could be found.
+struct OnItem<B, Item> {
+    project: ProjectItems<B, Item>,
+    index: usize,
+    node: BehaviorArc<Item>,
+    origin: ItemOrigin<B, Item>,
+}
+
+type ItemOrigin<B, Item> = Option<Arc<ForItems<B, Item>>>;
+
+impl<B, Item> std::fmt::Debug for OnItem<B, Item> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OnItem")
+            .field("index", &self.index)
+            .field("node", &self.node)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B: 'static, Item: 'static> OnItem<B, Item> {
+    fn wrap(
+        project: ProjectItems<B, Item>,
+        index: usize,
+        node: BehaviorArc<Item>,
+        origin: ItemOrigin<B, Item>,
+    ) -> BehaviorArc<B> {
+        Self {
+            project,
+            index,
+            node,
+            origin,
+        }
+        .arc()
+    }
+
+    /// The children `node` is in the middle of, each with the part of the
+    /// child subtree it was started from
+    ///
+    /// Matches them up the same way [`crate::visualize::debug_runner`] does.
+    fn active(&self) -> Vec<(ItemOrigin<B, Item>, BehaviorArc<Item>)> {
+        let active = self.node.active_children();
+        let Some(origin) = self.origin.as_ref() else {
+            return active.into_iter().map(|node| (None, node)).collect();
+        };
+        let origins = self.node.active_origins();
+        if !origins.is_empty() && origins.len() == active.len() {
+            let templates = origin.node.children();
+            origins
+                .iter()
+                .zip(active)
+                .map(|(from, node)| {
+                    let index = templates.iter().position(|child| Arc::ptr_eq(child, from));
+                    (index.map(|index| origin.children[index].clone()), node)
+                })
+                .collect()
+        } else if active.len() == origin.children.len() {
+            origin
+                .children
+                .iter()
+                .cloned()
+                .map(Some)
+                .zip(active)
+                .collect()
+        } else {
+            active.into_iter().map(|node| (None, node)).collect()
+        }
+    }
+}
+
+impl<B: 'static, Item: 'static> BehaviorNode<B> for OnItem<B, Item> {
+    fn tick(self: Arc<Self>, context: &mut B) -> NodeResult<B> {
+        let Some(item) = (self.project)(context).get_mut(self.index) else {
+            return NodeResult::Failure;
+        };
+        match self.node.clone().tick(item) {
+            NodeResult::Running(node) => NodeResult::Running(OnItem::wrap(
+                self.project.clone(),
+                self.index,
+                node,
+                self.origin.clone(),
+            )),
+            NodeResult::Success => NodeResult::Success,
+            NodeResult::Failure => NodeResult::Failure,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        self.node.name()
+    }
+
+    fn kind(&self) -> NodeKind {
+        self.node.kind()
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.node.estimated_cost()
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.origin
+            .iter()
+            .flat_map(|origin| origin.children())
+            .collect()
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.node.is_leaf()
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.active()
+            .into_iter()
+            .map(|(origin, node)| OnItem::wrap(self.project.clone(), self.index, node, origin))
+            .collect()
+    }
+
+    fn active_origins(&self) -> Vec<BehaviorArc<B>> {
+        self.active()
+            .into_iter()
+            .map(|(origin, _)| origin.map(|origin| origin as BehaviorArc<B>))
+            .collect::<Option<_>>()
+            .unwrap_or_default()
+    }
+
+    fn on_abort(&self, context: &mut B) {
+        if let Some(item) = (self.project)(context).get_mut(self.index) {
+            self.node.on_abort(item);
+        }
+    }
+}
+
+/// A parallel over a fixed number of children, kept inline in the node
+///
+/// Behaves like [`ParallelSequence`] with [`FanOutPolicy::AllSucceed`] and
+/// like [`ParallelSelector`] with [`FanOutPolicy::AnySucceed`], but never
+/// allocates a child slice: the running copy holds its children in an array
+/// with finished slots emptied out. With either policy, children still
+/// running when it finishes early are aborted (see
+/// [`BehaviorNode::on_abort`]).
+pub struct ParallelN<B, const N: usize> {
+    sub: [Option<BehaviorArc<B>>; N],
+    policy: FanOutPolicy,
+    /// Whether `sub` holds running children rather than fresh ones
+    resumed: bool,
 }
 
-impl<B> std::fmt::Debug for ParallelSelector<B> {
+impl<B, const N: usize> std::fmt::Debug for ParallelN<B, N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct(&format!("ParallelSelector<{:p}>", self.sub.as_ref()))
+        f.debug_struct("ParallelN")
             .field("sub", &self.sub)
+            .field("policy", &self.policy)
             .finish()
     }
 }
 
-impl<B, I: Into<BehaviorArc<B>>> FromIterator<I> for ParallelSelector<B> {
-    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+impl<B, const N: usize> ParallelN<B, N> {
+    pub fn new(policy: FanOutPolicy, children: [BehaviorArc<B>; N]) -> Self {
         Self {
-            sub: Arc::from(iter.into_iter().map(Into::into).collect::<Vec<_>>()),
+            sub: children.map(Some),
+            policy,
+            resumed: false,
         }
     }
 }
 
-impl<B: 'static> BehaviorNode<B> for ParallelSelector<B> {
+impl<B: 'static, const N: usize> ParallelN<B, N> {
+    /// Aborts every child still running once the child at `index` finished
+    /// the node, given the slots as this tick left them
+    fn abort_slots(&self, index: usize, slots: &[Option<BehaviorArc<B>>], context: &mut B) {
+        let unreached = if self.resumed {
+            &slots[index + 1..]
+        } else {
+            &[]
+        };
+        for child in slots[..index].iter().chain(unreached).flatten() {
+            crate::abort_spine(child, context);
+        }
+    }
+}
+
+impl<B: 'static, const N: usize> BehaviorNode<B> for ParallelN<B, N> {
     fn tick(self: Arc<Self>, context: &mut B) -> NodeResult<B> {
-        let mut new_children = vec![];
-        for child in self.sub.iter() {
-            match child.clone().tick(context) {
-                NodeResult::Success => return NodeResult::Success,
-                NodeResult::Failure => {}
-                NodeResult::Running(node) => {
-                    new_children.push(node);
+        let mut slots = self.sub.clone();
+        let mut pending = false;
+        for index in 0..N {
+            let Some(node) = slots[index].take() else {
+                continue;
+            };
+            let Some(res) = fallible::tick_child(node, context) else {
+                self.abort_slots(index, &slots, context);
+                return NodeResult::Failure;
+            };
+            match (res, self.policy) {
+                (NodeResult::Running(resume), _) => {
+                    slots[index] = Some(resume);
+                    pending = true;
+                }
+                (NodeResult::Failure, FanOutPolicy::AllSucceed) => {
+                    self.abort_slots(index, &slots, context);
+                    return NodeResult::Failure;
                 }
+                (NodeResult::Success, FanOutPolicy::AnySucceed) => {
+                    self.abort_slots(index, &slots, context);
+                    return NodeResult::Success;
+                }
+                _ => {}
             }
         }
 
-        if new_children.is_empty() {
-            NodeResult::Failure
-        } else {
+        if pending {
             NodeResult::Running(
                 Self {
-                    sub: Arc::from(new_children),
+                    sub: slots,
+                    policy: self.policy,
+                    resumed: true,
                 }
                 .arc(),
             )
+        } else if self.policy == FanOutPolicy::AllSucceed {
+            NodeResult::Success
+        } else {
+            NodeResult::Failure
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Parallel
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.sub
+            .iter()
+            .flatten()
+            .map(|child| child.estimated_cost())
+            .fold(0, u32::saturating_add)
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.iter().flatten().cloned().collect()
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.children()
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_u8(self.policy as u8);
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        for slot in &self.sub {
+            state::write_option(out, slot.as_ref(), |out, node| node.serialize_state(out))?;
+        }
+        Ok(())
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let mut sub = self.sub.clone();
+        for slot in sub.iter_mut() {
+            let template = slot.take().ok_or(StateError::Unsupported(self.name()))?;
+            *slot = state::read_option(bytes, |bytes| template.deserialize_state(bytes))?;
         }
+        Ok(Self {
+            sub,
+            policy: self.policy,
+            resumed: true,
+        }
+        .arc())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use assert2::check;
+    use assert2::{check, let_assert};
 
     use crate::BehaviorRunner;
 
@@ -140,6 +1493,38 @@ mod tests {
         }
     }
 
+    #[derive(Debug)]
+    struct Record(usize);
+
+    impl BehaviorNode<Diem> for Record {
+        fn tick(self: Arc<Self>, context: &mut Diem) -> NodeResult<Diem> {
+            context.paydays.push(self.0);
+            NodeResult::Failure
+        }
+    }
+
+    #[test]
+    fn sorted_by_key_orders_children() {
+        let children = (0..8)
+            .map(|i| (format!("child{i}"), Record(i).arc()))
+            .collect::<std::collections::HashMap<_, _>>();
+
+        let mut diem = Diem::default();
+        let mut runner =
+            BehaviorRunner::from_node(ParallelSelector::sorted_by_key(children.clone()));
+        check!(runner.proceed(&mut diem) == Some(false));
+        check!(diem.paydays == (0..8).collect::<Vec<_>>());
+
+        let mut diem = Diem::default();
+        let mut runner = BehaviorRunner::from_node(ParallelSequence::sorted_by_key(
+            children
+                .into_iter()
+                .map(|(key, child)| (std::cmp::Reverse(key), child)),
+        ));
+        check!(runner.proceed(&mut diem) == Some(false));
+        check!(diem.paydays == vec![7]);
+    }
+
     #[test]
     fn parallel_sequence_test() {
         let mut runner = BehaviorRunner::from_node(
@@ -260,4 +1645,594 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn parallel_selector_aborts_losing_siblings() {
+        use crate::composite::tests::{Context, Idle};
+        use crate::testing::{AlwaysSucceed, SucceedAfter};
+
+        // Winning on a later tick aborts the siblings on both sides
+        let mut runner = BehaviorRunner::from_node(ParallelSelector::new([
+            Idle(1).arc(),
+            SucceedAfter(1).arc(),
+            Idle(3).arc(),
+        ]));
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == None);
+        check!(context.stack.is_empty());
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(context.stack == vec![1, 3]);
+
+        // Winning on the first tick leaves unstarted siblings alone
+        let mut runner = BehaviorRunner::from_node(ParallelSelector::new([
+            Idle(1).arc(),
+            AlwaysSucceed.arc(),
+            Idle(3).arc(),
+        ]));
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(context.stack == vec![1]);
+    }
+
+    #[test]
+    fn parallel_race_takes_the_first_result() {
+        use crate::composite::tests::Context;
+        use crate::testing::{FailAfter, SucceedAfter};
+
+        let mut runner = BehaviorRunner::from_node(ParallelRace::new([
+            SucceedAfter(5).arc(),
+            SucceedAfter(1).arc(),
+        ]));
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == Some(true));
+
+        // A failure wins a race too
+        let mut runner = BehaviorRunner::from_node(ParallelRace::new([
+            SucceedAfter(5).arc(),
+            FailAfter(1).arc(),
+        ]));
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == Some(false));
+
+        // Ties go to the lowest index
+        for (children, expected) in [
+            ([FailAfter(1).arc(), SucceedAfter(1).arc()], false),
+            ([SucceedAfter(1).arc(), FailAfter(1).arc()], true),
+        ] {
+            let mut runner = BehaviorRunner::from_node(ParallelRace::new(children));
+            check!(runner.proceed(&mut context) == None);
+            check!(runner.proceed(&mut context) == Some(expected));
+        }
+    }
+
+    #[test]
+    fn parallel_new_accepts_any_iterator() {
+        let wait = |index| {
+            PaydayWait {
+                index,
+                payload: index + 1,
+                time: index,
+                ..Default::default()
+            }
+            .arc()
+        };
+        let run = |mut runner: BehaviorRunner<Diem>| {
+            let mut diem = Diem::default();
+            let mut results = vec![];
+            for day in 0..4 {
+                diem.day = day;
+                results.push(runner.proceed(&mut diem));
+            }
+            (results, diem.paydays)
+        };
+
+        let expected = (vec![None, None, None, Some(true)], vec![1, 2, 3]);
+        check!(
+            run(BehaviorRunner::from_node(ParallelSequence::new([
+                wait(0),
+                wait(1),
+                wait(2)
+            ]))) == expected
+        );
+        check!(
+            run(BehaviorRunner::from_node(ParallelSequence::new(vec![
+                wait(0),
+                wait(1),
+                wait(2)
+            ]))) == expected
+        );
+        check!(
+            run(BehaviorRunner::from_node(ParallelSequence::new(
+                (0..3).map(wait)
+            ))) == expected
+        );
+
+        let expected = (vec![None, Some(true), Some(true), Some(true)], vec![1]);
+        check!(
+            run(BehaviorRunner::from_node(ParallelSelector::new([
+                wait(0),
+                wait(1),
+                wait(2)
+            ]))) == expected
+        );
+        check!(
+            run(BehaviorRunner::from_node(ParallelSelector::new(vec![
+                wait(0),
+                wait(1),
+                wait(2)
+            ]))) == expected
+        );
+        check!(
+            run(BehaviorRunner::from_node(ParallelSelector::new(
+                (0..3).map(wait)
+            ))) == expected
+        );
+    }
+
+    #[test]
+    fn weighted_quorum_succeeds_at_threshold() {
+        let wait = |index, time, terminal| {
+            PaydayWait {
+                index,
+                payload: index + 1,
+                time,
+                terminal,
+            }
+            .arc()
+        };
+        let mut runner = BehaviorRunner::from_node(WeightedQuorumParallel::new(
+            3,
+            [
+                (1, wait(0, 0, false)),
+                (2, wait(1, 1, false)),
+                (3, wait(2, 2, false)),
+            ],
+        ));
+
+        let mut diem = Diem::default();
+        check!(runner.proceed(&mut diem) == None);
+        diem.day = 1;
+        check!(runner.proceed(&mut diem) == None);
+        check!(diem.paydays == vec![1]);
+        diem.day = 2;
+        // Weights 1 + 2 reach the threshold before the third child is ticked
+        check!(runner.proceed(&mut diem) == Some(true));
+        check!(diem.paydays == vec![1, 2]);
+    }
+
+    #[test]
+    fn weighted_quorum_fails_when_unreachable() {
+        let wait = |index, time, terminal| {
+            PaydayWait {
+                index,
+                payload: index + 1,
+                time,
+                terminal,
+            }
+            .arc()
+        };
+        let mut runner = BehaviorRunner::from_node(WeightedQuorumParallel::new(
+            5,
+            [
+                (1, wait(0, 0, false)),
+                (2, wait(1, 1, false)),
+                (3, wait(2, 1, true)),
+            ],
+        ));
+
+        let mut diem = Diem::default();
+        check!(runner.proceed(&mut diem) == None);
+        diem.day = 1;
+        check!(runner.proceed(&mut diem) == None);
+        diem.day = 2;
+        // Only weight 3 was ever reached, and nothing is left running
+        check!(runner.proceed(&mut diem) == Some(false));
+        check!(diem.paydays == vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn estimated_cost_saturates() {
+        #[derive(Debug)]
+        struct Costly;
+
+        impl BehaviorNode<()> for Costly {
+            fn tick(self: Arc<Self>, _context: &mut ()) -> NodeResult<()> {
+                NodeResult::Success
+            }
+
+            fn estimated_cost(&self) -> u32 {
+                u32::MAX
+            }
+        }
+
+        check!(ParallelSequence::new([Costly.arc(), Costly.arc()]).estimated_cost() == u32::MAX);
+        check!(
+            WeightedQuorumParallel::new(1, [(1, Costly.arc()), (1, Costly.arc())]).estimated_cost()
+                == u32::MAX
+        );
+    }
+
+    #[test]
+    fn weighted_quorum_aborts_running_children() {
+        use crate::composite::tests::{Context, Idle};
+        use crate::testing::SucceedAfter;
+
+        let mut runner = BehaviorRunner::from_node(WeightedQuorumParallel::new(
+            3,
+            [
+                (1, Idle(1).arc()),
+                (3, SucceedAfter(1).arc()),
+                (1, Idle(3).arc()),
+            ],
+        ));
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(context.stack == vec![1, 3]);
+    }
+
+    #[test]
+    fn weighted_quorum_saturates_huge_weights() {
+        use crate::testing::{AlwaysSucceed, SucceedAfter};
+
+        let mut runner = BehaviorRunner::from_node(WeightedQuorumParallel::new(
+            u32::MAX,
+            [
+                (u32::MAX - 1, AlwaysSucceed.arc()),
+                (2, SucceedAfter(1).arc()),
+            ],
+        ));
+        check!(runner.proceed(&mut ()) == None);
+        check!(runner.proceed(&mut ()) == Some(true));
+    }
+
+    #[test]
+    fn parallel_rejects_impossible_thresholds() {
+        use crate::testing::{AlwaysFail, AlwaysSucceed, SucceedAfter};
+
+        let children = || -> [BehaviorArc<()>; 3] {
+            [AlwaysSucceed.arc(), AlwaysFail.arc(), SucceedAfter(1).arc()]
+        };
+        check!(
+            Parallel::new(4, 1, children()).err()
+                == Some(ParallelConfigError::ThresholdTooHigh {
+                    threshold: 4,
+                    children: 3
+                })
+        );
+        check!(
+            Parallel::new(1, 5, children()).err()
+                == Some(ParallelConfigError::ThresholdTooHigh {
+                    threshold: 5,
+                    children: 3
+                })
+        );
+        check!(Parallel::new(0, 1, children()).err() == Some(ParallelConfigError::ZeroThreshold));
+        check!(Parallel::new(2, 0, children()).err() == Some(ParallelConfigError::ZeroThreshold));
+
+        for (success, failure) in [(1, 1), (2, 2), (3, 3)] {
+            check!(Parallel::new(success, failure, children()).is_ok());
+        }
+        let mut runner = BehaviorRunner::from_node(Parallel::new(2, 2, children()).unwrap());
+        check!(runner.proceed(&mut ()) == None);
+        check!(runner.proceed(&mut ()) == Some(true));
+
+        // Unchecked, an unreachable success threshold just fails
+        let mut runner = BehaviorRunner::from_node(Parallel::new_unchecked(4, 3, children()));
+        check!(runner.proceed(&mut ()) == Some(false));
+    }
+
+    #[test]
+    fn parallel_aborts_children_left_running() {
+        use crate::composite::tests::{Context, Idle};
+        use crate::testing::{AlwaysFail, SucceedAfter};
+
+        // Met before the later children are ticked again
+        let mut runner = BehaviorRunner::from_node(
+            Parallel::new(1, 3, [SucceedAfter(1).arc(), Idle(2).arc(), Idle(3).arc()]).unwrap(),
+        );
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == None);
+        check!(context.stack.is_empty());
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(context.stack == vec![2, 3]);
+
+        // Met after an earlier child already started running
+        let mut runner = BehaviorRunner::from_node(
+            Parallel::new(2, 2, [Idle(1).arc(), AlwaysFail.arc(), AlwaysFail.arc()]).unwrap(),
+        );
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == Some(false));
+        check!(context.stack == vec![1]);
+    }
+
+    #[test]
+    fn parallel_two_of_three() {
+        let wait = |index, time, terminal| {
+            PaydayWait {
+                index,
+                payload: index + 1,
+                time,
+                terminal,
+            }
+            .arc()
+        };
+
+        let mut runner = BehaviorRunner::from_node(
+            Parallel::new(
+                2,
+                2,
+                [wait(0, 1, false), wait(1, 3, false), wait(2, 5, false)],
+            )
+            .unwrap(),
+        );
+        let mut diem = Diem::default();
+        check!(runner.proceed(&mut diem) == None);
+        diem.day = 2;
+        check!(runner.proceed(&mut diem) == None);
+        check!(diem.paydays == vec![1]);
+        // The second success finishes the parallel before the last child is
+        // polled again
+        diem.day = 6;
+        check!(runner.proceed(&mut diem) == Some(true));
+        check!(diem.paydays == vec![1, 2]);
+
+        let mut runner = BehaviorRunner::from_node(
+            Parallel::new(
+                2,
+                2,
+                [wait(0, 1, true), wait(1, 5, false), wait(2, 3, true)],
+            )
+            .unwrap(),
+        );
+        let mut diem = Diem {
+            day: 2,
+            ..Default::default()
+        };
+        check!(runner.proceed(&mut diem) == None);
+        diem.day = 4;
+        check!(runner.proceed(&mut diem) == Some(false));
+        check!(diem.paydays == vec![1, 0, 3]);
+    }
+
+    #[derive(Debug, Default)]
+    struct Squad {
+        members: Vec<Member>,
+    }
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Member {
+        health: u32,
+        heals: u32,
+        aborted: bool,
+    }
+
+    /// Heals a member by one point per tick up to full health
+    #[derive(Debug)]
+    struct Heal;
+
+    impl BehaviorNode<Member> for Heal {
+        fn tick(self: Arc<Self>, member: &mut Member) -> NodeResult<Member> {
+            if member.health >= 3 {
+                return NodeResult::Success;
+            }
+            member.health += 1;
+            member.heals += 1;
+            NodeResult::Running(self)
+        }
+
+        fn on_abort(&self, member: &mut Member) {
+            member.aborted = true;
+        }
+    }
+
+    fn squad(health: [u32; 3]) -> Squad {
+        Squad {
+            members: health
+                .into_iter()
+                .map(|health| Member {
+                    health,
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn parallel_over_processes_every_item() {
+        let mut runner = BehaviorRunner::from_node(ParallelOver::new(
+            |squad: &mut Squad| squad.members.as_mut_slice(),
+            FanOutPolicy::AllSucceed,
+            Heal.arc(),
+        ));
+        let mut squad = squad([0, 2, 3]);
+
+        let mut ticks = 1;
+        while runner.proceed(&mut squad).is_none() {
+            ticks += 1;
+        }
+        check!(ticks == 4);
+        check!(squad.members.iter().all(|member| member.health == 3));
+        check!(
+            squad
+                .members
+                .iter()
+                .map(|member| member.heals)
+                .collect::<Vec<_>>()
+                == vec![3, 1, 0]
+        );
+    }
+
+    #[test]
+    fn parallel_over_shows_its_child_and_running_items() {
+        use crate::{composite::Sequence, visualize};
+
+        let tree = ParallelOver::new(
+            |squad: &mut Squad| squad.members.as_mut_slice(),
+            FanOutPolicy::AllSucceed,
+            Sequence::new([Heal.arc(), Heal.arc()]).arc(),
+        )
+        .arc();
+        check!(visualize::to_text(&tree) == "ParallelOver\n  Sequence\n    Heal\n    Heal\n");
+
+        // The full-health member is done at once; the other two keep going
+        let mut runner = BehaviorRunner::new(tree);
+        let mut squad = squad([0, 2, 3]);
+        check!(runner.proceed(&mut squad) == None);
+        let_assert!(Some(running) = runner.current_tick.as_ref());
+        check!(running.active_children().len() == 2);
+        check!(
+            visualize::debug_runner(&runner)
+                == "* ParallelOver\n*   Sequence\n*     Heal\n      Heal\n"
+        );
+    }
+
+    #[test]
+    fn parallel_over_any_succeeds_on_first() {
+        let mut runner = BehaviorRunner::from_node(ParallelOver::new(
+            |squad: &mut Squad| squad.members.as_mut_slice(),
+            FanOutPolicy::AnySucceed,
+            Heal.arc(),
+        ));
+        let mut squad = squad([0, 1, 2]);
+        check!(runner.proceed(&mut squad) == None);
+        check!(runner.proceed(&mut squad) == Some(true));
+        check!(squad.members[0].health == 2);
+    }
+
+    #[test]
+    fn parallel_over_aborts_items_left_running() {
+        let aborted = |squad: &Squad| {
+            squad
+                .members
+                .iter()
+                .map(|member| member.aborted)
+                .collect::<Vec<_>>()
+        };
+
+        // The full-health member wins while the others are still healing
+        let mut runner = BehaviorRunner::from_node(ParallelOver::new(
+            |squad: &mut Squad| squad.members.as_mut_slice(),
+            FanOutPolicy::AnySucceed,
+            Heal.arc(),
+        ));
+        let mut healing = squad([0, 1, 2]);
+        check!(runner.proceed(&mut healing) == None);
+        check!(runner.proceed(&mut healing) == Some(true));
+        check!(aborted(&healing) == vec![true, true, false]);
+
+        // Losing a member mid-run fails and aborts the rest
+        let mut runner = BehaviorRunner::from_node(ParallelOver::new(
+            |squad: &mut Squad| squad.members.as_mut_slice(),
+            FanOutPolicy::AllSucceed,
+            Heal.arc(),
+        ));
+        let mut healing = squad([0, 0, 0]);
+        check!(runner.proceed(&mut healing) == None);
+        healing.members.pop();
+        check!(runner.proceed(&mut healing) == Some(false));
+        check!(aborted(&healing) == vec![true, true]);
+    }
+
+    #[test]
+    fn parallel_sequence_reports_failed_child() {
+        use crate::testing::{FailAfter, SucceedAfter};
+        use std::sync::Mutex;
+
+        let report = Arc::new(Mutex::new(None));
+        let seen = report.clone();
+        let mut runner = BehaviorRunner::from_node(
+            ParallelSequence::new([
+                SucceedAfter(3).arc(),
+                SucceedAfter(0).arc(),
+                FailAfter(2).arc(),
+                SucceedAfter(1).arc(),
+            ])
+            .on_child_failure(move |_: &mut (), failed, succeeded| {
+                *seen.lock().unwrap() = Some((failed, succeeded.to_vec()));
+            }),
+        );
+
+        check!(runner.proceed(&mut ()) == None);
+        check!(runner.proceed(&mut ()) == None);
+        check!(*report.lock().unwrap() == None);
+        check!(runner.proceed(&mut ()) == Some(false));
+        check!(*report.lock().unwrap() == Some((2, vec![1, 3])));
+    }
+
+    #[test]
+    fn parallel_n_matches_slice_parallels() {
+        let children = || {
+            [
+                PaydayWait {
+                    index: 0,
+                    payload: 19,
+                    time: 5,
+                    ..Default::default()
+                }
+                .arc(),
+                PaydayWait {
+                    index: 1,
+                    payload: 42,
+                    terminal: true,
+                    ..Default::default()
+                }
+                .arc(),
+                PaydayWait {
+                    index: 3,
+                    time: 13,
+                    ..Default::default()
+                }
+                .arc(),
+            ]
+        };
+
+        for policy in [FanOutPolicy::AllSucceed, FanOutPolicy::AnySucceed] {
+            let mut fixed: BehaviorRunner<Diem> =
+                BehaviorRunner::from_node(ParallelN::<_, 3>::new(policy, children()));
+            let mut slice = match policy {
+                FanOutPolicy::AllSucceed => {
+                    BehaviorRunner::from_node(ParallelSequence::new(children()))
+                }
+                FanOutPolicy::AnySucceed => {
+                    BehaviorRunner::from_node(ParallelSelector::new(children()))
+                }
+            };
+
+            let mut fixed_diem = Diem::default();
+            let mut slice_diem = Diem::default();
+            for day in [0, 1, 6, 21, 22] {
+                fixed_diem.day = day;
+                slice_diem.day = day;
+                check!(fixed.proceed(&mut fixed_diem) == slice.proceed(&mut slice_diem));
+                check!(fixed_diem == slice_diem);
+                check!(fixed.active_path().len() == slice.active_path().len());
+            }
+        }
+    }
+
+    #[test]
+    fn parallel_n_aborts_occupied_slots() {
+        use crate::composite::tests::{Context, Idle};
+        use crate::testing::{FailAfter, SucceedAfter};
+
+        let mut runner = BehaviorRunner::from_node(ParallelN::new(
+            FanOutPolicy::AllSucceed,
+            [Idle(1).arc(), FailAfter(1).arc(), Idle(3).arc()],
+        ));
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == Some(false));
+        check!(context.stack == vec![1, 3]);
+
+        let mut runner = BehaviorRunner::from_node(ParallelN::new(
+            FanOutPolicy::AnySucceed,
+            [Idle(1).arc(), SucceedAfter(0).arc(), Idle(3).arc()],
+        ));
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == Some(true));
+        // The third child was never started
+        check!(context.stack == vec![1]);
+    }
 }
@@ -0,0 +1,773 @@
+//! Composites that attempt their children in a random order
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use super::sequence::read_resume;
+use super::{Selector, Sequence};
+use crate::rng::SplitMix64;
+use crate::state::{self, StateError};
+use crate::{fallible, BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+
+/// Where a randomized node gets the seed for each activation
+pub(crate) enum Seed<B> {
+    /// Derived from the blackboard, so replays against the same blackboard
+    /// pick the same order
+    Blackboard(Arc<dyn Fn(&B) -> u64 + Send + Sync>),
+    /// Advanced every activation from a stored seed
+    FreeRunning(Arc<AtomicU64>),
+}
+
+impl<B> Seed<B> {
+    fn next(&self, blackboard: &B) -> u64 {
+        match self {
+            Seed::Blackboard(seed) => seed(blackboard),
+            Seed::FreeRunning(state) => {
+                let prev = state.fetch_add(SplitMix64::GAMMA, Ordering::Relaxed);
+                SplitMix64::new(prev).next_u64()
+            }
+        }
+    }
+
+    /// The children in a fresh order, along with where each one was
+    fn shuffled(&self, sub: &[BehaviorArc<B>], blackboard: &B) -> Shuffled<B> {
+        let mut order = (0..sub.len()).collect::<Vec<_>>();
+        SplitMix64::new(self.next(blackboard)).shuffle(&mut order);
+        Shuffled::new(sub, order)
+    }
+}
+
+/// Children put in some order other than their own, as resumes need them
+pub(crate) struct Shuffled<B> {
+    pub(crate) seq: Arc<[BehaviorArc<B>]>,
+    pub(crate) order: Arc<[usize]>,
+}
+
+impl<B> Shuffled<B> {
+    pub(crate) fn new(sub: &[BehaviorArc<B>], order: Vec<usize>) -> Self {
+        Self {
+            seq: order.iter().map(|at| sub[*at].clone()).collect(),
+            order: Arc::from(order),
+        }
+    }
+}
+
+impl<B> Clone for Seed<B> {
+    fn clone(&self) -> Self {
+        match self {
+            Seed::Blackboard(seed) => Seed::Blackboard(seed.clone()),
+            Seed::FreeRunning(state) => Seed::FreeRunning(state.clone()),
+        }
+    }
+}
+
+impl<B> std::fmt::Debug for Seed<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Seed::Blackboard(_) => f.write_str("Blackboard"),
+            Seed::FreeRunning(state) => f
+                .debug_tuple("FreeRunning")
+                .field(&state.load(Ordering::Relaxed))
+                .finish(),
+        }
+    }
+}
+
+/// A [`Selector`] that tries its children in a random order
+///
+/// The order is shuffled once per activation and kept across resumes, so a
+/// long-running child never causes a reshuffle.
+///
+/// There are two seeding modes:
+/// - [`RandomSelector::seeded_by`] derives the seed from the blackboard on
+///   every activation. Identical blackboards give identical orders, which
+///   makes replays reproducible.
+/// - [`RandomSelector::free_running`] starts from a stored seed and advances
+///   it every activation. The sequence of orders is reproducible from the
+///   initial seed, but each activation differs from the last.
+///   [`RandomSelector::with_rng`] does the same starting from a generator,
+///   and collecting children into a `RandomSelector` starts from seed 0.
+pub struct RandomSelector<B> {
+    pub(crate) sub: Arc<[BehaviorArc<B>]>,
+    seed: Seed<B>,
+}
+
+impl<B> std::fmt::Debug for RandomSelector<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("RandomSelector<{:p}>", self.sub.as_ref()))
+            .field("sub", &self.sub)
+            .field("seed", &self.seed)
+            .finish()
+    }
+}
+
+impl<B> RandomSelector<B> {
+    pub fn seeded_by<F>(seed: F, children: impl IntoIterator<Item = BehaviorArc<B>>) -> Self
+    where
+        F: Fn(&B) -> u64 + Send + Sync + 'static,
+    {
+        Self {
+            sub: Arc::from(children.into_iter().collect::<Vec<_>>()),
+            seed: Seed::Blackboard(Arc::new(seed)),
+        }
+    }
+
+    pub fn free_running(seed: u64, children: impl IntoIterator<Item = BehaviorArc<B>>) -> Self {
+        Self {
+            sub: Arc::from(children.into_iter().collect::<Vec<_>>()),
+            seed: Seed::FreeRunning(Arc::new(AtomicU64::new(seed))),
+        }
+    }
+
+    /// Like [`RandomSelector::free_running`], continuing from wherever `rng`
+    /// is in its stream
+    pub fn with_rng(rng: SplitMix64, children: impl IntoIterator<Item = BehaviorArc<B>>) -> Self {
+        Self::free_running(rng.state(), children)
+    }
+}
+
+/// Free-running from seed 0
+impl<B, I: Into<BehaviorArc<B>>> FromIterator<I> for RandomSelector<B> {
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        Self::free_running(0, iter.into_iter().map(Into::into))
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for RandomSelector<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let Shuffled { seq, order } = self.seed.shuffled(&self.sub, blackboard);
+        for (idx, sub) in seq.iter().enumerate() {
+            let Some(res) = fallible::tick_child(sub.clone(), blackboard) else {
+                return NodeResult::Failure;
+            };
+            match res {
+                NodeResult::Failure => {}
+                NodeResult::Success => return NodeResult::Success,
+                NodeResult::Running(resume) => {
+                    return NodeResult::Running(Selector::resume(seq, Some(order), idx, resume))
+                }
+            }
+        }
+        NodeResult::Failure
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Selector
+    }
+
+    fn is_stateful(&self) -> bool {
+        matches!(self.seed, Seed::FreeRunning(_))
+    }
+
+    fn is_deterministic(&self) -> bool {
+        matches!(self.seed, Seed::Blackboard(_))
+            && self.children().iter().all(|child| child.is_deterministic())
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        read_resume(&self.sub, bytes, self.name(), Selector::resume)
+    }
+}
+
+/// A [`Sequence`] that runs its children in a random order
+///
+/// The counterpart of [`RandomSelector`], with the same seeding modes. The
+/// order is drawn once per activation and kept across resumes.
+pub struct RandomSequence<B> {
+    pub(crate) sub: Arc<[BehaviorArc<B>]>,
+    seed: Seed<B>,
+}
+
+impl<B> std::fmt::Debug for RandomSequence<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("RandomSequence<{:p}>", self.sub.as_ref()))
+            .field("sub", &self.sub)
+            .field("seed", &self.seed)
+            .finish()
+    }
+}
+
+impl<B> RandomSequence<B> {
+    pub fn seeded_by<F>(seed: F, children: impl IntoIterator<Item = BehaviorArc<B>>) -> Self
+    where
+        F: Fn(&B) -> u64 + Send + Sync + 'static,
+    {
+        Self {
+            sub: Arc::from(children.into_iter().collect::<Vec<_>>()),
+            seed: Seed::Blackboard(Arc::new(seed)),
+        }
+    }
+
+    pub fn free_running(seed: u64, children: impl IntoIterator<Item = BehaviorArc<B>>) -> Self {
+        Self {
+            sub: Arc::from(children.into_iter().collect::<Vec<_>>()),
+            seed: Seed::FreeRunning(Arc::new(AtomicU64::new(seed))),
+        }
+    }
+
+    pub fn with_rng(rng: SplitMix64, children: impl IntoIterator<Item = BehaviorArc<B>>) -> Self {
+        Self::free_running(rng.state(), children)
+    }
+}
+
+/// Free-running from seed 0
+impl<B, I: Into<BehaviorArc<B>>> FromIterator<I> for RandomSequence<B> {
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        Self::free_running(0, iter.into_iter().map(Into::into))
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for RandomSequence<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let Shuffled { seq, order } = self.seed.shuffled(&self.sub, blackboard);
+        for (idx, sub) in seq.iter().enumerate() {
+            let Some(res) = fallible::tick_child(sub.clone(), blackboard) else {
+                return NodeResult::Failure;
+            };
+            match res {
+                NodeResult::Success => {}
+                NodeResult::Failure => return NodeResult::Failure,
+                NodeResult::Running(resume) => {
+                    return NodeResult::Running(Sequence::resume(seq, Some(order), idx, resume))
+                }
+            }
+        }
+        NodeResult::Success
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Sequence
+    }
+
+    fn is_stateful(&self) -> bool {
+        matches!(self.seed, Seed::FreeRunning(_))
+    }
+
+    fn is_deterministic(&self) -> bool {
+        matches!(self.seed, Seed::Blackboard(_))
+            && self.children().iter().all(|child| child.is_deterministic())
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        read_resume(&self.sub, bytes, self.name(), Sequence::resume)
+    }
+}
+
+/// Runs its child only with probability `p`, failing otherwise
+///
+/// The roll happens when the child would be started; once the child is
+/// running, resumes go straight to it. A `p` of `0.0` (or less) always
+/// fails without ticking the child, and a `p` of `1.0` (or more) always
+/// runs it.
+///
+/// The roll is free-running from seed 0 unless [`Probability::seeded_by`]
+/// or [`Probability::with_rng`] say otherwise. With `seeded_by`, the seed is
+/// used as the roll directly: `0` is the lowest roll and `u64::MAX` the
+/// highest, which makes it easy to stub from a test.
+pub struct Probability<B> {
+    p: f64,
+    child: BehaviorArc<B>,
+    seed: Seed<B>,
+    running: Option<BehaviorArc<B>>,
+}
+
+impl<B> std::fmt::Debug for Probability<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Probability")
+            .field("p", &self.p)
+            .field("child", &self.child)
+            .field("seed", &self.seed)
+            .field("running", &self.running)
+            .finish()
+    }
+}
+
+impl<B> Probability<B> {
+    pub fn new(p: f64, child: BehaviorArc<B>) -> Self {
+        Self {
+            p,
+            child,
+            seed: Seed::FreeRunning(Arc::new(AtomicU64::new(0))),
+            running: None,
+        }
+    }
+
+    /// Reads each roll from the blackboard
+    pub fn seeded_by<F>(mut self, seed: F) -> Self
+    where
+        F: Fn(&B) -> u64 + Send + Sync + 'static,
+    {
+        self.seed = Seed::Blackboard(Arc::new(seed));
+        self
+    }
+
+    /// Draws rolls starting from wherever `rng` is in its stream
+    pub fn with_rng(mut self, rng: SplitMix64) -> Self {
+        self.seed = Seed::FreeRunning(Arc::new(AtomicU64::new(rng.state())));
+        self
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for Probability<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let node = match self.running.as_ref() {
+            Some(resume) => resume.clone(),
+            None => {
+                // The top 53 bits make a uniform roll in 0..1
+                let roll = (self.seed.next(blackboard) >> 11) as f64 / (1u64 << 53) as f64;
+                if roll >= self.p {
+                    return NodeResult::Failure;
+                }
+                self.child.clone()
+            }
+        };
+        match node.tick(blackboard) {
+            NodeResult::Running(resume) => NodeResult::Running(
+                Self {
+                    p: self.p,
+                    child: self.child.clone(),
+                    seed: self.seed.clone(),
+                    running: Some(resume),
+                }
+                .arc(),
+            ),
+            res => res,
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Decorator
+    }
+
+    fn is_stateful(&self) -> bool {
+        matches!(self.seed, Seed::FreeRunning(_))
+    }
+
+    fn is_deterministic(&self) -> bool {
+        matches!(self.seed, Seed::Blackboard(_))
+            && self.children().iter().all(|child| child.is_deterministic())
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.running
+            .as_ref()
+            .unwrap_or(&self.child)
+            .estimated_cost()
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.running.iter().cloned().collect()
+    }
+
+    fn hash_params(&self, state: &mut dyn std::hash::Hasher) {
+        state.write_u64(self.p.to_bits());
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_option(out, self.running.as_ref(), |out, resume| {
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let running = state::read_option(bytes, |bytes| self.child.deserialize_state(bytes))?;
+        Ok(Self {
+            p: self.p,
+            child: self.child.clone(),
+            seed: self.seed.clone(),
+            running,
+        }
+        .arc())
+    }
+}
+
+type Viable<B> = Arc<dyn Fn(&B) -> bool + Send + Sync>;
+
+/// A [`Selector`] that breaks ties between viable children at random
+///
+/// Each child comes with a weight and a cheap viability check. On activation
+/// the checks are run without ticking anything, and the children that pass
+/// are tried first, in an order drawn by weight: a child with weight 3 is
+/// three times as likely to go first as one with weight 1. Children that
+/// didn't pass are still tried afterwards, in their listed order, so
+/// viability only decides who gets a fair shot at going first. A child that
+/// is tried still has to succeed for the selector to succeed.
+///
+/// The order is fixed for the activation, and the seed is read from the
+/// blackboard as in [`RandomSelector::seeded_by`].
+pub struct TieBreakSelector<B> {
+    pub(crate) sub: Arc<[BehaviorArc<B>]>,
+    weights: Arc<[(u32, Viable<B>)]>,
+    seed: Seed<B>,
+}
+
+impl<B> std::fmt::Debug for TieBreakSelector<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let weights = self.weights.iter().map(|(w, _)| w).collect::<Vec<_>>();
+        f.debug_struct(&format!("TieBreakSelector<{:p}>", self.sub.as_ref()))
+            .field("sub", &self.sub)
+            .field("weights", &weights)
+            .field("seed", &self.seed)
+            .finish()
+    }
+}
+
+impl<B> TieBreakSelector<B> {
+    pub fn seeded_by<S, F>(
+        seed: S,
+        children: impl IntoIterator<Item = (u32, F, BehaviorArc<B>)>,
+    ) -> Self
+    where
+        S: Fn(&B) -> u64 + Send + Sync + 'static,
+        F: Fn(&B) -> bool + Send + Sync + 'static,
+    {
+        let (weights, sub): (Vec<_>, Vec<_>) = children
+            .into_iter()
+            .map(|(weight, viable, child)| ((weight, Arc::new(viable) as Viable<B>), child))
+            .unzip();
+        Self {
+            sub: Arc::from(sub),
+            weights: Arc::from(weights),
+            seed: Seed::Blackboard(Arc::new(seed)),
+        }
+    }
+
+    fn order(&self, blackboard: &B) -> Shuffled<B> {
+        let (mut viable, rest): (Vec<_>, Vec<_>) =
+            (0..self.sub.len()).partition(|&idx| (self.weights[idx].1)(blackboard));
+
+        let mut rng = SplitMix64::new(self.seed.next(blackboard));
+        let mut order = Vec::with_capacity(self.sub.len());
+        while !viable.is_empty() {
+            let total = viable
+                .iter()
+                .map(|&idx| self.weights[idx].0 as u64)
+                .sum::<u64>();
+            let pick = if total == 0 {
+                0
+            } else {
+                let mut roll = rng.below(total);
+                viable
+                    .iter()
+                    .position(|&idx| {
+                        let weight = self.weights[idx].0 as u64;
+                        if roll < weight {
+                            true
+                        } else {
+                            roll -= weight;
+                            false
+                        }
+                    })
+                    .unwrap_or(0)
+            };
+            order.push(viable.remove(pick));
+        }
+        order.extend(rest);
+        Shuffled::new(&self.sub, order)
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for TieBreakSelector<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let Shuffled { seq, order } = self.order(blackboard);
+        for (idx, sub) in seq.iter().enumerate() {
+            let Some(res) = fallible::tick_child(sub.clone(), blackboard) else {
+                return NodeResult::Failure;
+            };
+            match res {
+                NodeResult::Failure => {}
+                NodeResult::Success => return NodeResult::Success,
+                NodeResult::Running(resume) => {
+                    return NodeResult::Running(Selector::resume(seq, Some(order), idx, resume))
+                }
+            }
+        }
+        NodeResult::Failure
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Selector
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn hash_params(&self, state: &mut dyn std::hash::Hasher) {
+        for (weight, _) in self.weights.iter() {
+            state.write_u32(*weight);
+        }
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        read_resume(&self.sub, bytes, self.name(), Selector::resume)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::BehaviorRunner;
+
+    #[derive(Debug, Default)]
+    struct Replay {
+        seed: u64,
+        attempts: Vec<usize>,
+    }
+
+    #[derive(Debug)]
+    struct Attempt(usize);
+    impl BehaviorNode<Replay> for Attempt {
+        fn tick(self: Arc<Self>, context: &mut Replay) -> NodeResult<Replay> {
+            context.attempts.push(self.0);
+            NodeResult::Failure
+        }
+    }
+
+    fn attempts() -> Vec<BehaviorArc<Replay>> {
+        (0..6).map(|i| Attempt(i).arc()).collect()
+    }
+
+    fn activate(runner: &mut BehaviorRunner<Replay>, seed: u64) -> Vec<usize> {
+        let mut context = Replay {
+            seed,
+            ..Default::default()
+        };
+        check!(runner.proceed(&mut context) == Some(false));
+        context.attempts
+    }
+
+    #[test]
+    fn blackboard_seed_is_reproducible() {
+        let mut runner = BehaviorRunner::from_node(RandomSelector::seeded_by(
+            |context: &Replay| context.seed,
+            attempts(),
+        ));
+        let first = activate(&mut runner, 7);
+        check!(first.len() == 6);
+        check!(activate(&mut runner, 7) == first);
+
+        let mut other = BehaviorRunner::from_node(RandomSelector::seeded_by(
+            |context: &Replay| context.seed,
+            attempts(),
+        ));
+        check!(activate(&mut other, 7) == first);
+        check!(activate(&mut other, 8) != first);
+    }
+
+    #[test]
+    fn free_running_seed_advances() {
+        let mut a = BehaviorRunner::from_node(RandomSelector::free_running(3, attempts()));
+        let mut b = BehaviorRunner::from_node(RandomSelector::free_running(3, attempts()));
+        let a_orders = (0..3).map(|_| activate(&mut a, 0)).collect::<Vec<_>>();
+        let b_orders = (0..3).map(|_| activate(&mut b, 0)).collect::<Vec<_>>();
+        check!(a_orders == b_orders);
+        check!(a_orders[0] != a_orders[1]);
+    }
+
+    #[test]
+    fn tie_break_follows_weights() {
+        let viable: fn(&Replay) -> bool = |_| true;
+        let not_viable: fn(&Replay) -> bool = |_| false;
+        let mut runner = BehaviorRunner::from_node(TieBreakSelector::seeded_by(
+            |context: &Replay| context.seed,
+            [
+                (1, viable, Attempt(0).arc()),
+                (5, not_viable, Attempt(1).arc()),
+                (3, viable, Attempt(2).arc()),
+            ],
+        ));
+
+        let mut firsts = [0; 3];
+        for seed in 0..400 {
+            let attempts = activate(&mut runner, seed);
+            // The non-viable child is only ever tried last
+            check!(attempts.len() == 3);
+            check!(attempts[2] == 1);
+            firsts[attempts[0]] += 1;
+        }
+        check!(firsts[1] == 0);
+        check!((60..140).contains(&firsts[0]));
+        check!((260..340).contains(&firsts[2]));
+
+        // Same seed, same order
+        check!(activate(&mut runner, 11) == activate(&mut runner, 11));
+    }
+
+    #[test]
+    fn fixed_rng_gives_fixed_order() {
+        let mut runner =
+            BehaviorRunner::from_node(RandomSelector::with_rng(SplitMix64::new(5), attempts()));
+        check!(activate(&mut runner, 0) == vec![0, 2, 5, 4, 1, 3]);
+
+        let mut collected =
+            BehaviorRunner::from_node(attempts().into_iter().collect::<RandomSelector<_>>());
+        let mut seeded = BehaviorRunner::from_node(RandomSelector::free_running(0, attempts()));
+        check!(activate(&mut collected, 0) == activate(&mut seeded, 0));
+    }
+
+    #[derive(Debug)]
+    struct Pass(usize);
+    impl BehaviorNode<Replay> for Pass {
+        fn tick(self: Arc<Self>, context: &mut Replay) -> NodeResult<Replay> {
+            context.attempts.push(self.0);
+            NodeResult::Success
+        }
+    }
+
+    #[test]
+    fn random_sequence_order_follows_seed() {
+        let passes = || (0..6).map(|i| Pass(i).arc()).collect::<Vec<_>>();
+        let run = |seed| {
+            let mut runner = BehaviorRunner::from_node(RandomSequence::with_rng(
+                SplitMix64::new(seed),
+                passes(),
+            ));
+            let mut context = Replay::default();
+            check!(runner.proceed(&mut context) == Some(true));
+            context.attempts
+        };
+        check!(run(9).len() == 6);
+        check!(run(9) == run(9));
+        check!(run(9) != run(10));
+
+        // A running child keeps the order it was started with
+        let mut runner = BehaviorRunner::from_node(RandomSequence::seeded_by(
+            |context: &Replay| context.seed,
+            [
+                Pass(0).arc(),
+                crate::testing::SucceedAfter(1).arc(),
+                Pass(1).arc(),
+            ],
+        ));
+        let mut first = Replay {
+            seed: 3,
+            ..Default::default()
+        };
+        check!(runner.proceed(&mut first) == None);
+        let mut second = Replay {
+            seed: 4,
+            attempts: first.attempts.clone(),
+        };
+        check!(runner.proceed(&mut second) == Some(true));
+        let mut replayed = Replay {
+            seed: 3,
+            ..Default::default()
+        };
+        let mut fresh = BehaviorRunner::from_node(RandomSequence::seeded_by(
+            |context: &Replay| context.seed,
+            [
+                Pass(0).arc(),
+                crate::testing::SucceedAfter(1).arc(),
+                Pass(1).arc(),
+            ],
+        ));
+        fresh.proceed(&mut replayed);
+        fresh.proceed(&mut replayed);
+        check!(second.attempts == replayed.attempts);
+    }
+
+    #[test]
+    fn probability_rolls_once_per_activation() {
+        let gate = |p| {
+            BehaviorRunner::from_node(
+                Probability::new(
+                    p,
+                    Sequence::new([Pass(0).arc(), crate::testing::SucceedAfter(1).arc()]).arc(),
+                )
+                .seeded_by(|context: &Replay| context.seed),
+            )
+        };
+        let run = |runner: &mut BehaviorRunner<Replay>, seed| {
+            let mut context = Replay {
+                seed,
+                ..Default::default()
+            };
+            let mut results = vec![runner.proceed(&mut context)];
+            if runner.is_running() {
+                // A roll that would fail doesn't interrupt the running child
+                context.seed = u64::MAX;
+                results.push(runner.proceed(&mut context));
+            }
+            (results, context.attempts)
+        };
+
+        let mut half = gate(0.5);
+        check!(run(&mut half, 0) == (vec![None, Some(true)], vec![0]));
+        check!(run(&mut half, u64::MAX / 4 * 3) == (vec![Some(false)], vec![]));
+
+        let mut never = gate(0.0);
+        check!(run(&mut never, 0) == (vec![Some(false)], vec![]));
+        let mut always = gate(1.0);
+        check!(run(&mut always, u64::MAX) == (vec![None, Some(true)], vec![0]));
+
+        // The default roll is free-running and reproducible
+        let mut a = BehaviorRunner::from_node(Probability::new(0.5, Pass(0).arc()));
+        let mut b = BehaviorRunner::from_node(
+            Probability::new(0.5, Pass(0).arc()).with_rng(SplitMix64::new(0)),
+        );
+        let a = (0..16).map(|_| activate_result(&mut a)).collect::<Vec<_>>();
+        let b = (0..16).map(|_| activate_result(&mut b)).collect::<Vec<_>>();
+        check!(a == b);
+        check!(a.contains(&Some(true)) && a.contains(&Some(false)));
+    }
+
+    fn activate_result(runner: &mut BehaviorRunner<Replay>) -> Option<bool> {
+        runner.proceed(&mut Replay::default())
+    }
+
+    #[test]
+    fn free_running_seeds_are_nondeterministic() {
+        use crate::composite::Sequence;
+
+        check!(Sequence::new(attempts()).is_deterministic());
+        check!(!RandomSelector::free_running(0, attempts()).is_deterministic());
+        check!(
+            RandomSelector::seeded_by(|context: &Replay| context.seed, attempts())
+                .is_deterministic()
+        );
+
+        let nested = Sequence::new([
+            Attempt(0).arc(),
+            RandomSelector::free_running(0, attempts()).arc(),
+        ]);
+        check!(!nested.is_deterministic());
+    }
+
+    #[test]
+    fn explanations_name_the_listed_child() {
+        use crate::testing::{AlwaysFail, AlwaysSucceed, FailAfter, SucceedAfter};
+
+        for seed in 0..8 {
+            let selector = RandomSelector::seeded_by(
+                move |_: &()| seed,
+                [AlwaysFail.arc(), SucceedAfter(1).arc()],
+            );
+            let mut runner = BehaviorRunner::from_node(selector).with_explanations();
+            while runner.proceed(&mut ()).is_none() {}
+            check!(runner.explanation() == Some("selected branch 1"));
+
+            let sequence = RandomSequence::seeded_by(
+                move |_: &()| seed,
+                [AlwaysSucceed.arc(), FailAfter(1).arc()],
+            );
+            let mut runner = BehaviorRunner::from_node(sequence).with_explanations();
+            while runner.proceed(&mut ()).is_none() {}
+            check!(runner.explanation() == Some("failed at step 1"));
+        }
+    }
+}
@@ -1,5 +1,8 @@
-use crate::{BehaviorArc, BehaviorNode, NodeResult};
+use crate::params::NodeParams;
+use crate::state::{self, StateError};
+use crate::{fallible, BehaviorArc, BehaviorNode, NodeKind, NodeResult};
 use std::fmt::Debug;
+use std::hash::Hasher;
 use std::sync::Arc;
 
 /// Repeats its child infintely
@@ -28,7 +31,11 @@ impl<B> Repeated<B> {
 impl<B: 'static> BehaviorNode<B> for Repeated<B> {
     fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
         if let Some(resume) = self.resume.as_ref() {
-            if let NodeResult::Running(resume) = resume.clone().tick(blackboard) {
+            // Don't start over on a child that just raised an error
+            let Some(res) = fallible::tick_child(resume.clone(), blackboard) else {
+                return NodeResult::Failure;
+            };
+            if let NodeResult::Running(resume) = res {
                 return NodeResult::Running(
                     Self {
                         resume: Some(resume),
@@ -38,7 +45,10 @@ impl<B: 'static> BehaviorNode<B> for Repeated<B> {
                 );
             }
         }
-        if let NodeResult::Running(resume) = self.child.clone().tick(blackboard) {
+        let Some(res) = fallible::tick_child(self.child.clone(), blackboard) else {
+            return NodeResult::Failure;
+        };
+        if let NodeResult::Running(resume) = res {
             return NodeResult::Running(
                 Self {
                     resume: Some(resume),
@@ -54,6 +64,52 @@ impl<B: 'static> BehaviorNode<B> for Repeated<B> {
             resume: None,
         }))
     }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Repeater
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.as_ref().unwrap_or(&self.child).estimated_cost()
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| self.child.value_eq(other.child.as_ref()))
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let [child] = <[_; 1]>::try_from(children).ok()?;
+        Some(Repeated::new(child).arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.resume.iter().cloned().collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_option(out, self.resume.as_ref(), |out, resume| {
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let resume = state::read_option(bytes, |bytes| self.child.deserialize_state(bytes))?;
+        Ok(Self {
+            resume,
+            child: self.child.clone(),
+        }
+        .arc())
+    }
 }
 
 /// Repeats its child a set number of times
@@ -73,6 +129,11 @@ impl<B> LimitedRepeated<B> {
             resume: None,
         }
     }
+
+    /// How many more times the child will be run
+    pub(crate) fn remaining(&self) -> usize {
+        self.limit.saturating_sub(self.completed)
+    }
 }
 
 impl<B> Debug for LimitedRepeated<B> {
@@ -94,7 +155,11 @@ impl<B: 'static> BehaviorNode<B> for LimitedRepeated<B> {
         }
 
         if let Some(resume) = self.resume.as_ref() {
-            match resume.clone().tick(blackboard) {
+            // Don't start over on a child that just raised an error
+            let Some(res) = fallible::tick_child(resume.clone(), blackboard) else {
+                return NodeResult::Failure;
+            };
+            match res {
                 NodeResult::Running(resume) => {
                     return NodeResult::Running(
                         Self {
@@ -111,7 +176,10 @@ impl<B: 'static> BehaviorNode<B> for LimitedRepeated<B> {
                 }
             }
         }
-        match self.child.clone().tick(blackboard) {
+        let Some(res) = fallible::tick_child(self.child.clone(), blackboard) else {
+            return NodeResult::Failure;
+        };
+        match res {
             NodeResult::Running(resume) => {
                 return NodeResult::Running(
                     Self {
@@ -136,6 +204,64 @@ impl<B: 'static> BehaviorNode<B> for LimitedRepeated<B> {
             completed,
         }))
     }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Repeater
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.as_ref().unwrap_or(&self.child).estimated_cost()
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            self.limit == other.limit && self.child.value_eq(other.child.as_ref())
+        })
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_usize(self.limit);
+    }
+
+    fn params(&self) -> NodeParams {
+        [("limit", self.limit.to_string())].into_iter().collect()
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let [child] = <[_; 1]>::try_from(children).ok()?;
+        Some(LimitedRepeated::new(self.limit, child).arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.resume.iter().cloned().collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_u64(out, self.completed as u64);
+        state::write_option(out, self.resume.as_ref(), |out, resume| {
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let completed = state::read_u64(bytes)? as usize;
+        let resume = state::read_option(bytes, |bytes| self.child.deserialize_state(bytes))?;
+        Ok(Self {
+            child: self.child.clone(),
+            limit: self.limit,
+            completed,
+            resume,
+        }
+        .arc())
+    }
 }
 
 /// Repeats its child until its child fails
@@ -164,7 +290,11 @@ impl<B> Debug for RepeatedUntilFailure<B> {
 impl<B: 'static> BehaviorNode<B> for RepeatedUntilFailure<B> {
     fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
         if let Some(resume) = self.resume.as_ref() {
-            match resume.clone().tick(blackboard) {
+            // Don't start over on a child that just raised an error
+            let Some(res) = fallible::tick_child(resume.clone(), blackboard) else {
+                return NodeResult::Failure;
+            };
+            match res {
                 NodeResult::Running(resume) => {
                     return NodeResult::Running(
                         Self {
@@ -178,7 +308,10 @@ impl<B: 'static> BehaviorNode<B> for RepeatedUntilFailure<B> {
                 _ => (),
             }
         }
-        match self.child.clone().tick(blackboard) {
+        let Some(res) = fallible::tick_child(self.child.clone(), blackboard) else {
+            return NodeResult::Failure;
+        };
+        match res {
             NodeResult::Running(resume) => NodeResult::Running(
                 Self {
                     resume: Some(resume),
@@ -199,20 +332,534 @@ impl<B: 'static> BehaviorNode<B> for RepeatedUntilFailure<B> {
             }
         }
     }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Repeater
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.as_ref().unwrap_or(&self.child).estimated_cost()
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| self.child.value_eq(other.child.as_ref()))
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let [child] = <[_; 1]>::try_from(children).ok()?;
+        Some(RepeatedUntilFailure::new(child).arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.resume.iter().cloned().collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_option(out, self.resume.as_ref(), |out, resume| {
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let resume = state::read_option(bytes, |bytes| self.child.deserialize_state(bytes))?;
+        Ok(Self {
+            resume,
+            child: self.child.clone(),
+        }
+        .arc())
+    }
+}
+
+/// Repeats its child until its child succeeds
+pub struct RepeatedUntilSuccess<B> {
+    resume: Option<BehaviorArc<B>>,
+    child: BehaviorArc<B>,
+}
+
+impl<B> RepeatedUntilSuccess<B> {
+    pub fn new(child: BehaviorArc<B>) -> Self {
+        Self {
+            child,
+            resume: None,
+        }
+    }
+}
+
+impl<B> Debug for RepeatedUntilSuccess<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RepeatedUntilSuccess")
+            .field("child", &self.child)
+            .finish()
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for RepeatedUntilSuccess<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        if let Some(resume) = self.resume.as_ref() {
+            // Don't start over on a child that just raised an error
+            let Some(res) = fallible::tick_child(resume.clone(), blackboard) else {
+                return NodeResult::Failure;
+            };
+            match res {
+                NodeResult::Running(resume) => {
+                    return NodeResult::Running(
+                        Self {
+                            resume: Some(resume),
+                            child: self.child.clone(),
+                        }
+                        .arc(),
+                    );
+                }
+                NodeResult::Success => return NodeResult::Success,
+                _ => (),
+            }
+        }
+        let Some(res) = fallible::tick_child(self.child.clone(), blackboard) else {
+            return NodeResult::Failure;
+        };
+        match res {
+            NodeResult::Running(resume) => NodeResult::Running(
+                Self {
+                    resume: Some(resume),
+                    child: self.child.clone(),
+                }
+                .arc(),
+            ),
+            NodeResult::Failure => {
+                // Restart whenever we fail
+                NodeResult::Running(Arc::new(Self {
+                    child: self.child.clone(),
+                    resume: None,
+                }))
+            }
+            NodeResult::Success => NodeResult::Success,
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Repeater
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.as_ref().unwrap_or(&self.child).estimated_cost()
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| self.child.value_eq(other.child.as_ref()))
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let [child] = <[_; 1]>::try_from(children).ok()?;
+        Some(RepeatedUntilSuccess::new(child).arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.resume.iter().cloned().collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_option(out, self.resume.as_ref(), |out, resume| {
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let resume = state::read_option(bytes, |bytes| self.child.deserialize_state(bytes))?;
+        Ok(Self {
+            resume,
+            child: self.child.clone(),
+        }
+        .arc())
+    }
+}
+
+/// Retries its child until it succeeds, giving up after `max_attempts`
+/// failures
+///
+/// A bounded [`RepeatedUntilSuccess`]. With no attempts allowed it fails
+/// without ticking its child.
+pub struct Retry<B> {
+    child: BehaviorArc<B>,
+    max_attempts: usize,
+    failed: usize,
+    resume: Option<BehaviorArc<B>>,
+}
+
+impl<B> Retry<B> {
+    pub fn new(max_attempts: usize, child: BehaviorArc<B>) -> Self {
+        Self {
+            child,
+            max_attempts,
+            failed: 0,
+            resume: None,
+        }
+    }
+
+    fn attempt(&self, failed: usize, resume: Option<BehaviorArc<B>>) -> Self {
+        Self {
+            child: self.child.clone(),
+            max_attempts: self.max_attempts,
+            failed,
+            resume,
+        }
+    }
+}
+
+impl<B> Debug for Retry<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Retry")
+            .field("child", &self.child)
+            .field("max_attempts", &self.max_attempts)
+            .field("failed", &self.failed)
+            .finish()
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for Retry<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        if self.failed >= self.max_attempts {
+            return NodeResult::Failure;
+        }
+
+        let node = self.resume.clone().unwrap_or_else(|| self.child.clone());
+        // Don't retry a child that just raised an error
+        let Some(res) = fallible::tick_child(node, blackboard) else {
+            return NodeResult::Failure;
+        };
+        match res {
+            NodeResult::Running(resume) => {
+                NodeResult::Running(self.attempt(self.failed, Some(resume)).arc())
+            }
+            NodeResult::Success => NodeResult::Success,
+            NodeResult::Failure if self.failed + 1 >= self.max_attempts => NodeResult::Failure,
+            NodeResult::Failure => {
+                // Start the next attempt on the next tick
+                NodeResult::Running(self.attempt(self.failed + 1, None).arc())
+            }
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Repeater
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.as_ref().unwrap_or(&self.child).estimated_cost()
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            self.max_attempts == other.max_attempts && self.child.value_eq(other.child.as_ref())
+        })
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_usize(self.max_attempts);
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let [child] = <[_; 1]>::try_from(children).ok()?;
+        Some(Retry::new(self.max_attempts, child).arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.resume.iter().cloned().collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_u64(out, self.failed as u64);
+        state::write_option(out, self.resume.as_ref(), |out, resume| {
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let failed = state::read_u64(bytes)? as usize;
+        let resume = state::read_option(bytes, |bytes| self.child.deserialize_state(bytes))?;
+        Ok(self.attempt(failed, resume).arc())
+    }
+}
+
+type BreakWhen<B> = Arc<dyn Fn(&B) -> bool + Send + Sync>;
+
+/// Repeats its child until `break_when` says to stop, then succeeds
+///
+/// The condition is only checked between iterations: before the first one,
+/// and whenever the child finishes, whatever its result. A child that is
+/// running is never cut short. [`RepeatedUntil::at_most`] adds a count
+/// limit as well.
+pub struct RepeatedUntil<B> {
+    child: BehaviorArc<B>,
+    break_when: BreakWhen<B>,
+    limit: Option<usize>,
+    completed: usize,
+    resume: Option<BehaviorArc<B>>,
+}
+
+impl<B> RepeatedUntil<B> {
+    pub fn new<F>(break_when: F, child: BehaviorArc<B>) -> Self
+    where
+        F: Fn(&B) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            child,
+            break_when: Arc::new(break_when),
+            limit: None,
+            completed: 0,
+            resume: None,
+        }
+    }
+
+    /// Also stops, succeeding, after `limit` iterations
+    pub fn at_most(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn iteration(&self, completed: usize, resume: Option<BehaviorArc<B>>) -> Self {
+        Self {
+            child: self.child.clone(),
+            break_when: self.break_when.clone(),
+            limit: self.limit,
+            completed,
+            resume,
+        }
+    }
+}
+
+impl<B> Debug for RepeatedUntil<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RepeatedUntil")
+            .field("child", &self.child)
+            .field("limit", &self.limit)
+            .field("completed", &self.completed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for RepeatedUntil<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let node = match self.resume.as_ref() {
+            Some(resume) => resume.clone(),
+            None if self.limit.is_some_and(|limit| self.completed >= limit)
+                || (self.break_when)(blackboard) =>
+            {
+                return NodeResult::Success
+            }
+            None => self.child.clone(),
+        };
+        let Some(res) = fallible::tick_child(node, blackboard) else {
+            return NodeResult::Failure;
+        };
+        match res {
+            NodeResult::Running(resume) => {
+                NodeResult::Running(self.iteration(self.completed, Some(resume)).arc())
+            }
+            _ => {
+                let completed = self.completed + 1;
+                if self.limit.is_some_and(|limit| completed >= limit)
+                    || (self.break_when)(blackboard)
+                {
+                    return NodeResult::Success;
+                }
+                // Start the next iteration on the next tick
+                NodeResult::Running(self.iteration(completed, None).arc())
+            }
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Repeater
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.as_ref().unwrap_or(&self.child).estimated_cost()
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_usize(self.limit.unwrap_or(usize::MAX));
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.resume.iter().cloned().collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_u64(out, self.completed as u64);
+        state::write_option(out, self.resume.as_ref(), |out, resume| {
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let completed = state::read_u64(bytes)? as usize;
+        let resume = state::read_option(bytes, |bytes| self.child.deserialize_state(bytes))?;
+        Ok(self.iteration(completed, resume).arc())
+    }
+}
+
+/// Repeats its body infinitely, running a separate node between iterations
+///
+/// The body runs to completion, then `between` runs to completion, and so on.
+pub struct RepeatedWith<B> {
+    resume: Option<BehaviorArc<B>>,
+    body: BehaviorArc<B>,
+    between: BehaviorArc<B>,
+    in_between: bool,
+}
+
+impl<B> RepeatedWith<B> {
+    pub fn new(body: BehaviorArc<B>, between: BehaviorArc<B>) -> Self {
+        Self {
+            resume: None,
+            body,
+            between,
+            in_between: false,
+        }
+    }
+
+    fn phase(&self, in_between: bool, resume: Option<BehaviorArc<B>>) -> Self {
+        Self {
+            resume,
+            body: self.body.clone(),
+            between: self.between.clone(),
+            in_between,
+        }
+    }
+}
+
+impl<B> Debug for RepeatedWith<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RepeatedWith")
+            .field("body", &self.body)
+            .field("between", &self.between)
+            .field("in_between", &self.in_between)
+            .finish()
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for RepeatedWith<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let mut in_between = self.in_between;
+        if let Some(resume) = self.resume.as_ref() {
+            // Don't start over on a child that just raised an error
+            let Some(res) = fallible::tick_child(resume.clone(), blackboard) else {
+                return NodeResult::Failure;
+            };
+            if let NodeResult::Running(resume) = res {
+                return NodeResult::Running(self.phase(in_between, Some(resume)).arc());
+            }
+            in_between = !in_between;
+        }
+        let node = if in_between {
+            &self.between
+        } else {
+            &self.body
+        };
+        let Some(res) = fallible::tick_child(node.clone(), blackboard) else {
+            return NodeResult::Failure;
+        };
+        if let NodeResult::Running(resume) = res {
+            return NodeResult::Running(self.phase(in_between, Some(resume)).arc());
+        }
+
+        // Move on to the other phase, cuz we never end
+        NodeResult::Running(self.phase(!in_between, None).arc())
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Repeater
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        match self.resume.as_ref() {
+            Some(resume) => resume.estimated_cost(),
+            None if self.in_between => self.between.estimated_cost(),
+            None => self.body.estimated_cost(),
+        }
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            self.body.value_eq(other.body.as_ref()) && self.between.value_eq(other.between.as_ref())
+        })
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.body.clone(), self.between.clone()]
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.resume.iter().cloned().collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_flag(out, self.in_between);
+        state::write_option(out, self.resume.as_ref(), |out, resume| {
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let in_between = state::read_flag(bytes)?;
+        let phase = if in_between {
+            &self.between
+        } else {
+            &self.body
+        };
+        let resume = state::read_option(bytes, |bytes| phase.deserialize_state(bytes))?;
+        Ok(self.phase(in_between, resume).arc())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
         composite::{
-            tests::{test_with_context, Context},
+            tests::{test_with_context, Context, PushAfter},
             Sequence,
         },
         BehaviorRunner,
     };
 
     use super::*;
-    use assert2::check;
+    use assert2::{check, let_assert};
 
     #[derive(Debug)]
     struct Push1;
@@ -253,6 +900,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn repeated_with_alternates_body_and_between() {
+        let runner = BehaviorRunner::new(
+            RepeatedWith::new(
+                Push1.arc(),
+                PushAfter {
+                    steps: 0,
+                    payload: 0,
+                    fail: false,
+                }
+                .arc(),
+            )
+            .arc(),
+        );
+        let (res, context) = test_with_context(|| Context { stack: Vec::new() }, runner, 5);
+        check!(res == None);
+        check!(context.stack == vec![1, 0, 1, 0, 1, 0]);
+
+        let runner = BehaviorRunner::new(
+            RepeatedWith::new(
+                PushAfter {
+                    steps: 2,
+                    payload: 1,
+                    fail: false,
+                }
+                .arc(),
+                PushAfter {
+                    steps: 1,
+                    payload: 0,
+                    fail: false,
+                }
+                .arc(),
+            )
+            .arc(),
+        );
+        let (res, context) = test_with_context(|| Context { stack: Vec::new() }, runner, 6);
+        check!(res == None);
+        check!(context.stack == vec![1, 0, 1, 0]);
+    }
+
     #[test]
     fn limited_repeat_repeats_to_limit() {
         let runner = BehaviorRunner::new(LimitedRepeated::new(3, Push1.arc()).arc());
@@ -276,4 +963,158 @@ mod tests {
         check!(res == Some(true));
         check!(context.stack == vec![1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144]);
     }
+
+    #[test]
+    fn limited_repeated_value_eq() {
+        use crate::testing::AlwaysSucceed;
+
+        let a = LimitedRepeated::<Context>::new(3, AlwaysSucceed.arc()).arc();
+        let b = LimitedRepeated::<Context>::new(3, AlwaysSucceed.arc()).arc();
+        let c = LimitedRepeated::<Context>::new(4, AlwaysSucceed.arc()).arc();
+        check!(a.value_eq(b.as_ref()));
+        check!(!a.value_eq(c.as_ref()));
+        check!(!a.value_eq(Repeated::new(AlwaysSucceed.arc()).arc().as_ref()));
+    }
+
+    #[test]
+    fn limited_repeated_state_round_trips() {
+        use crate::{composite::IterAction, testing::SucceedAfter};
+
+        let template = LimitedRepeated::<()>::new(3, SucceedAfter(2).arc()).arc();
+        let mut node = template.clone();
+        for _ in 0..4 {
+            let_assert!(NodeResult::Running(next) = node.tick(&mut ()));
+            node = next;
+        }
+
+        let mut bytes = vec![];
+        check!(node.serialize_state(&mut bytes) == Ok(()));
+        let_assert!(Ok(restored) = template.deserialize_state(&mut bytes.as_slice()));
+        let restored_node = restored.downcast_ref::<LimitedRepeated<()>>().unwrap();
+        check!(restored_node.completed == 1);
+
+        let mut again = vec![];
+        check!(restored.serialize_state(&mut again) == Ok(()));
+        check!(again == bytes);
+
+        // Both copies finish on the same tick
+        let (mut a, mut b) = (node, restored);
+        loop {
+            match (a.tick(&mut ()), b.tick(&mut ())) {
+                (NodeResult::Running(next_a), NodeResult::Running(next_b)) => {
+                    (a, b) = (next_a, next_b);
+                }
+                (a, b) => {
+                    check!(a.kind() == b.kind());
+                    break;
+                }
+            }
+        }
+
+        // Truncated state is an error rather than a fresh node
+        let_assert!(Err(error) = template.deserialize_state(&mut &bytes[..4]));
+        check!(error == StateError::Truncated);
+
+        // As is a child that can't save its progress
+        let template =
+            LimitedRepeated::new(3, IterAction::new(0..2, |_: &mut (), _| {}).arc()).arc();
+        let_assert!(NodeResult::Running(node) = template.clone().tick(&mut ()));
+        let_assert!(Err(error) = node.serialize_state(&mut vec![]));
+        check!(error == StateError::Unsupported("IterAction"));
+    }
+
+    #[derive(Debug)]
+    struct FlakyPush;
+    impl BehaviorNode<Context> for FlakyPush {
+        fn tick(self: Arc<Self>, context: &mut Context) -> NodeResult<Context> {
+            context.stack.push(context.stack.len() as i32);
+            if context.stack.len() < 4 {
+                NodeResult::Failure
+            } else {
+                NodeResult::Success
+            }
+        }
+    }
+
+    #[test]
+    fn repeat_until_success_retries_failures() {
+        use crate::testing::SucceedAfter;
+
+        let runner = BehaviorRunner::from_node(RepeatedUntilSuccess::new(FlakyPush.arc()));
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 3);
+        check!(res == Some(true));
+        check!(context.stack == vec![0, 1, 2, 3]);
+
+        // Long-running attempts are resumed rather than restarted
+        let runner = BehaviorRunner::from_node(RepeatedUntilSuccess::new(
+            Sequence::new([SucceedAfter(2).arc(), FlakyPush.arc()]).arc(),
+        ));
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 20);
+        check!(res == Some(true));
+        check!(context.stack == vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn retry_gives_up_after_max_attempts() {
+        let runner = BehaviorRunner::from_node(Retry::new(4, FlakyPush.arc()));
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 10);
+        check!(res == Some(true));
+        check!(context.stack == vec![0, 1, 2, 3]);
+
+        let runner = BehaviorRunner::from_node(Retry::new(3, FlakyPush.arc()));
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 10);
+        check!(res == Some(false));
+        check!(context.stack == vec![0, 1, 2]);
+
+        // No attempts means the child is never ticked
+        let runner = BehaviorRunner::from_node(Retry::new(0, FlakyPush.arc()));
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 10);
+        check!(res == Some(false));
+        check!(context.stack.is_empty());
+
+        // A first-try success doesn't run the child again
+        let runner = BehaviorRunner::from_node(Retry::new(3, Push1.arc()));
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 10);
+        check!(res == Some(true));
+        check!(context.stack == vec![1]);
+    }
+
+    #[test]
+    fn repeated_until_breaks_between_iterations() {
+        // Stops at the iteration where the counter reaches 3
+        let runner = BehaviorRunner::from_node(RepeatedUntil::new(
+            |context: &Context| context.stack.len() >= 3,
+            Push1.arc(),
+        ));
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 10);
+        check!(res == Some(true));
+        check!(context.stack == vec![1, 1, 1]);
+
+        // The condition turns true partway through the second iteration, but
+        // the child still gets to finish it
+        let mut runner = BehaviorRunner::from_node(RepeatedUntil::new(
+            |context: &Context| context.stack.len() >= 2,
+            Sequence::new([Push1.arc(), crate::testing::SucceedAfter(1).arc()]).arc(),
+        ));
+        let mut context = Context { stack: vec![] };
+        let results = (0..4)
+            .map(|_| runner.proceed(&mut context))
+            .collect::<Vec<_>>();
+        check!(results == [None, None, None, Some(true)]);
+        check!(context.stack == vec![1, 1]);
+
+        // A count limit stops it first
+        let runner = BehaviorRunner::from_node(
+            RepeatedUntil::new(|_: &Context| false, Push1.arc()).at_most(2),
+        );
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 10);
+        check!(res == Some(true));
+        check!(context.stack == vec![1, 1]);
+
+        // Already true on activation means no iterations at all
+        let runner = BehaviorRunner::from_node(RepeatedUntil::new(|_: &Context| true, Push1.arc()));
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 10);
+        check!(res == Some(true));
+        check!(context.stack.is_empty());
+    }
 }
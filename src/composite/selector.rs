@@ -1,6 +1,18 @@
-use crate::{BehaviorArc, BehaviorNode, NodeResult};
+use crate::state::{self, StateError};
+use crate::{
+    children_value_eq, explain, fallible, BehaviorArc, BehaviorNode, NodeKind, NodeResult,
+};
 use std::sync::Arc;
 
+use super::sequence::{listed_index, read_resume, write_resume};
+use super::shared::{check_shared, SharedChildError};
+
+/// Ticks its children in order until one of them doesn't fail
+///
+/// A child that returns `Running` is committed to: later ticks resume it
+/// directly, and the children before it aren't looked at again until the
+/// selector is reactivated. See [`ReactiveSelector`] for a selector that
+/// keeps re-checking them.
 pub struct Selector<B> {
     pub(crate) sub: Arc<[BehaviorArc<B>]>,
 }
@@ -13,6 +25,12 @@ impl<B> std::fmt::Debug for Selector<B> {
     }
 }
 
+impl<B> Selector<B> {
+    pub fn new(children: impl IntoIterator<Item = BehaviorArc<B>>) -> Self {
+        children.into_iter().collect()
+    }
+}
+
 impl<B, I: Into<BehaviorArc<B>>> FromIterator<I> for Selector<B> {
     fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
         Self {
@@ -22,32 +40,121 @@ impl<B, I: Into<BehaviorArc<B>>> FromIterator<I> for Selector<B> {
 }
 
 impl<B: 'static> Selector<B> {
+    /// Like [`Selector::new`], but rejects a stateful child that appears twice
+    ///
+    /// See [`BehaviorNode::is_stateful`].
+    pub fn new_checked(
+        children: impl IntoIterator<Item = BehaviorArc<B>>,
+    ) -> Result<Self, SharedChildError> {
+        let node = Self::new(children);
+        check_shared(&node.sub)?;
+        Ok(node)
+    }
+
+    /// Builds a selector over `children` that falls back to `fallback`
+    ///
+    /// If none of the children has succeeded after `budget` ticks, or they all
+    /// fail, the fallback is ticked instead and its result is the selector's.
+    pub fn with_fallback(
+        children: impl IntoIterator<Item = BehaviorArc<B>>,
+        fallback: BehaviorArc<B>,
+        budget: usize,
+    ) -> FallbackSelector<B> {
+        FallbackSelector {
+            main: Selector::new(children).arc(),
+            fallback,
+            budget,
+            running: None,
+        }
+    }
+
+    /// `order` is as for [`super::Sequence`]'s resumes
     pub(crate) fn resume(
         seq: Arc<[BehaviorArc<B>]>,
+        order: Option<Arc<[usize]>>,
         index: usize,
         resume: BehaviorArc<B>,
     ) -> BehaviorArc<B> {
-        SelectorResume { seq, resume, index }.arc()
+        SelectorResume {
+            seq,
+            order,
+            resume,
+            index,
+        }
+        .arc()
     }
 }
 
 impl<B: 'static> BehaviorNode<B> for Selector<B> {
     fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
         for (idx, sub) in self.sub.iter().enumerate() {
-            match sub.clone().tick(blackboard) {
+            if !sub.is_enabled(blackboard) {
+                continue;
+            }
+            let Some(res) = fallible::tick_child(sub.clone(), blackboard) else {
+                return NodeResult::Failure;
+            };
+            match res {
                 NodeResult::Failure => {}
-                NodeResult::Success => return NodeResult::Success,
+                NodeResult::Success => {
+                    explain::note(|out| out.push_str(&format!("selected branch {idx}")));
+                    return NodeResult::Success;
+                }
                 NodeResult::Running(resume) => {
-                    return NodeResult::Running(Self::resume(self.sub.clone(), idx, resume))
+                    return NodeResult::Running(Self::resume(self.sub.clone(), None, idx, resume))
                 }
             }
         }
         NodeResult::Failure
     }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Selector
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.sub.first().map_or(1, |child| child.estimated_cost())
+    }
+
+    fn success_probability(&self, blackboard: &B) -> f32 {
+        let all_fail = self
+            .sub
+            .iter()
+            .filter(|child| child.is_enabled(blackboard))
+            .map(|child| 1.0 - child.success_probability(blackboard))
+            .product::<f32>();
+        1.0 - all_fail
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| children_value_eq(&self.sub, &other.sub))
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        Some(Selector::new(children).arc())
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        read_resume(&self.sub, bytes, self.name(), Selector::resume)
+    }
 }
 
+/// A running [`Selector`], waiting on the branch at `index`
+///
+/// Reused and rebuilt like [`super::sequence::SequenceResume`].
 pub(crate) struct SelectorResume<B> {
     pub(crate) seq: Arc<[BehaviorArc<B>]>,
+    pub(crate) order: Option<Arc<[usize]>>,
     pub(crate) resume: BehaviorArc<B>,
     pub(crate) index: usize,
 }
@@ -64,33 +171,358 @@ impl<B> std::fmt::Debug for SelectorResume<B> {
 impl<B: 'static> BehaviorNode<B> for SelectorResume<B> {
     fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
         // Tick the node we want to resume on
-        match self.resume.clone().tick(blackboard) {
+        let Some(res) = fallible::tick_child(self.resume.clone(), blackboard) else {
+            return NodeResult::Failure;
+        };
+        match res {
             NodeResult::Failure => {}
-            NodeResult::Success => return NodeResult::Success,
+            NodeResult::Success => {
+                let branch = listed_index(self.order.as_deref(), self.index);
+                explain::note(|out| out.push_str(&format!("selected branch {branch}")));
+                return NodeResult::Success;
+            }
+            // Nothing changed below us, so neither did we
+            NodeResult::Running(resume) if Arc::ptr_eq(&resume, &self.resume) => {
+                return self.running()
+            }
             NodeResult::Running(resume) => {
-                return NodeResult::Running(Selector::resume(self.seq.clone(), self.index, resume))
+                return NodeResult::Running(Selector::resume(
+                    self.seq.clone(),
+                    self.order.clone(),
+                    self.index,
+                    resume,
+                ))
             }
         }
         for (idx, sub) in self.seq.iter().enumerate().skip(self.index + 1) {
-            match sub.clone().tick(blackboard) {
+            if !sub.is_enabled(blackboard) {
+                continue;
+            }
+            let Some(res) = fallible::tick_child(sub.clone(), blackboard) else {
+                return NodeResult::Failure;
+            };
+            match res {
                 NodeResult::Failure => {}
-                NodeResult::Success => return NodeResult::Success,
+                NodeResult::Success => {
+                    let branch = listed_index(self.order.as_deref(), idx);
+                    explain::note(|out| out.push_str(&format!("selected branch {branch}")));
+                    return NodeResult::Success;
+                }
                 NodeResult::Running(resume) => {
-                    return NodeResult::Running(Selector::resume(self.seq.clone(), idx, resume))
+                    return NodeResult::Running(Selector::resume(
+                        self.seq.clone(),
+                        self.order.clone(),
+                        idx,
+                        resume,
+                    ))
+                }
+            }
+        }
+        NodeResult::Failure
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Selector
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.estimated_cost()
+    }
+
+    fn success_probability(&self, blackboard: &B) -> f32 {
+        let rest_fail = self.seq[self.index + 1..]
+            .iter()
+            .filter(|child| child.is_enabled(blackboard))
+            .map(|child| 1.0 - child.success_probability(blackboard))
+            .product::<f32>();
+        1.0 - (1.0 - self.resume.success_probability(blackboard)) * rest_fail
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.resume.clone()]
+    }
+
+    fn active_origins(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.seq[self.index].clone()]
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        write_resume(out, self.order.as_deref(), self.index, &self.resume)
+    }
+}
+
+/// A [`Selector`] that re-checks higher-priority children every tick
+///
+/// While a child is running, each tick first re-runs the children before it
+/// from scratch. If one of them succeeds or starts running, the running child
+/// is abandoned ([`BehaviorNode::on_abort`] is called along its active spine)
+/// and the earlier child takes over. Otherwise the running child is resumed.
+pub struct ReactiveSelector<B> {
+    pub(crate) sub: Arc<[BehaviorArc<B>]>,
+    running: Option<(usize, BehaviorArc<B>)>,
+}
+
+impl<B> std::fmt::Debug for ReactiveSelector<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("ReactiveSelector<{:p}>", self.sub.as_ref()))
+            .field("sub", &self.sub)
+            .field("running", &self.running)
+            .finish()
+    }
+}
+
+impl<B> ReactiveSelector<B> {
+    pub fn new(children: impl IntoIterator<Item = BehaviorArc<B>>) -> Self {
+        children.into_iter().collect()
+    }
+}
+
+impl<B, I: Into<BehaviorArc<B>>> FromIterator<I> for ReactiveSelector<B> {
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        Self {
+            sub: Arc::from(iter.into_iter().map(Into::into).collect::<Vec<_>>()),
+            running: None,
+        }
+    }
+}
+
+impl<B: 'static> ReactiveSelector<B> {
+    fn running_at(&self, index: usize, resume: BehaviorArc<B>) -> NodeResult<B> {
+        NodeResult::Running(
+            Self {
+                sub: self.sub.clone(),
+                running: Some((index, resume)),
+            }
+            .arc(),
+        )
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for ReactiveSelector<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        for (idx, sub) in self.sub.iter().enumerate() {
+            let node = match self.running.as_ref() {
+                Some((running, resume)) if *running == idx => resume.clone(),
+                _ if !sub.is_enabled(blackboard) => continue,
+                _ => sub.clone(),
+            };
+            let res = fallible::tick_child(node, blackboard);
+            if let Some((running, resume)) = self.running.as_ref() {
+                // An earlier child took over
+                if idx < *running && !matches!(res, Some(NodeResult::Failure)) {
+                    crate::abort_spine(resume, blackboard);
                 }
             }
+            let Some(res) = res else {
+                return NodeResult::Failure;
+            };
+            match res {
+                NodeResult::Failure => {}
+                NodeResult::Success => return NodeResult::Success,
+                NodeResult::Running(resume) => return self.running_at(idx, resume),
+            }
         }
         NodeResult::Failure
     }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Selector
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.sub.first().map_or(1, |child| child.estimated_cost())
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| children_value_eq(&self.sub, &other.sub))
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        Some(ReactiveSelector::new(children).arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.running
+            .iter()
+            .map(|(_, resume)| resume.clone())
+            .collect()
+    }
+
+    fn active_origins(&self) -> Vec<BehaviorArc<B>> {
+        self.running
+            .iter()
+            .map(|(idx, _)| self.sub[*idx].clone())
+            .collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_option(out, self.running.as_ref(), |out, (index, resume)| {
+            state::write_u64(out, *index as u64);
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let running = state::read_option(bytes, |bytes| {
+            let index = state::read_index(bytes, self.sub.len(), self.name())?;
+            Ok((index, self.sub[index].deserialize_state(bytes)?))
+        })?;
+        Ok(Self {
+            sub: self.sub.clone(),
+            running,
+        }
+        .arc())
+    }
+}
+
+/// A selector with a last-resort child (see [`Selector::with_fallback`])
+///
+/// Once the budget runs out, the still-running children are aborted (see
+/// [`BehaviorNode::on_abort`]) before the fallback is ticked.
+pub struct FallbackSelector<B> {
+    main: BehaviorArc<B>,
+    fallback: BehaviorArc<B>,
+    budget: usize,
+    /// The ticks left and the running selector
+    running: Option<(usize, BehaviorArc<B>)>,
+}
+
+impl<B> std::fmt::Debug for FallbackSelector<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FallbackSelector")
+            .field("main", &self.main)
+            .field("fallback", &self.fallback)
+            .field("budget", &self.budget)
+            .field("running", &self.running)
+            .finish()
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for FallbackSelector<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let (remaining, node) = match self.running.as_ref() {
+            Some((remaining, resume)) => (*remaining, resume.clone()),
+            None => (self.budget, self.main.clone()),
+        };
+        // Out of patience with the preferred children
+        if remaining == 0 {
+            if self.running.is_some() {
+                crate::abort_spine(&node, blackboard);
+            }
+            return self.fallback.clone().tick(blackboard);
+        }
+        match fallible::tick_child(node, blackboard) {
+            None => NodeResult::Failure,
+            Some(NodeResult::Success) => NodeResult::Success,
+            Some(NodeResult::Failure) => self.fallback.clone().tick(blackboard),
+            Some(NodeResult::Running(resume)) => NodeResult::Running(
+                Self {
+                    main: self.main.clone(),
+                    fallback: self.fallback.clone(),
+                    budget: self.budget,
+                    running: Some((remaining - 1, resume)),
+                }
+                .arc(),
+            ),
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Selector
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        match self.running.as_ref() {
+            Some((0, _)) => self.fallback.estimated_cost(),
+            Some((_, resume)) => resume.estimated_cost(),
+            None if self.budget == 0 => self.fallback.estimated_cost(),
+            None => self.main.estimated_cost(),
+        }
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            self.budget == other.budget
+                && self.main.value_eq(other.main.as_ref())
+                && self.fallback.value_eq(other.fallback.as_ref())
+        })
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.main.clone(), self.fallback.clone()]
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let [main, fallback] = <[_; 2]>::try_from(children).ok()?;
+        Some(
+            Self {
+                main,
+                fallback,
+                budget: self.budget,
+                running: None,
+            }
+            .arc(),
+        )
+    }
+
+    fn hash_params(&self, state: &mut dyn std::hash::Hasher) {
+        state.write_usize(self.budget);
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.running
+            .iter()
+            .map(|(_, resume)| resume.clone())
+            .collect()
+    }
+
+    fn active_origins(&self) -> Vec<BehaviorArc<B>> {
+        self.running.iter().map(|_| self.main.clone()).collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_option(out, self.running.as_ref(), |out, (remaining, resume)| {
+            state::write_u64(out, *remaining as u64);
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let running = state::read_option(bytes, |bytes| {
+            let remaining = state::read_index(bytes, self.budget, self.name())?;
+            Ok((remaining, self.main.deserialize_state(bytes)?))
+        })?;
+        Ok(Self {
+            main: self.main.clone(),
+            fallback: self.fallback.clone(),
+            budget: self.budget,
+            running,
+        }
+        .arc())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use assert2::check;
+    use assert2::{check, let_assert};
 
     use super::*;
     use crate::{
-        composite::tests::{test_with_context, Context},
+        composite::tests::{test_with_context, Context, PushAfter},
         BehaviorNode, BehaviorRunner, NodeResult,
     };
 
@@ -148,4 +580,268 @@ mod tests {
         check!(res == Some(true));
         check!(context.stack == vec![1, 2]);
     }
+
+    #[test]
+    fn selector_new_accepts_any_iterator() {
+        let push = |payload| {
+            PushAfter {
+                steps: 1,
+                payload,
+                fail: payload < 2,
+            }
+            .arc()
+        };
+        let run = |sel: Selector<Context>| {
+            test_with_context(
+                || Context { stack: Vec::new() },
+                BehaviorRunner::from_node(sel),
+                10,
+            )
+        };
+
+        let (res, context) = run(Selector::new([push(1), push(2), push(3)]));
+        check!(res == Some(true));
+        check!(context.stack == vec![1, 2]);
+
+        let (res, context) = run(Selector::new(vec![push(1), push(2), push(3)]));
+        check!(res == Some(true));
+        check!(context.stack == vec![1, 2]);
+
+        let (res, context) = run(Selector::new((1..=3).map(push)));
+        check!(res == Some(true));
+        check!(context.stack == vec![1, 2]);
+    }
+
+    #[test]
+    fn fallback_runs_after_budget() {
+        use crate::testing::{FailAfter, SucceedAfter};
+
+        let tree = Selector::with_fallback(
+            [FailAfter(10).arc(), SucceedAfter(10).arc()],
+            PushAfter {
+                steps: 1,
+                payload: 3,
+                fail: false,
+            }
+            .arc(),
+            3,
+        );
+        let runner = BehaviorRunner::from_node(tree);
+
+        // Three ticks on the slow children, then two on the fallback
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 4);
+        check!(res == Some(true));
+        check!(context.stack == vec![3]);
+    }
+
+    #[test]
+    fn fallback_runs_when_children_fail() {
+        use crate::testing::AlwaysFail;
+
+        let tree = Selector::with_fallback(
+            [AlwaysFail.arc(), AlwaysFail.arc()],
+            PushAfter {
+                steps: 1,
+                payload: 5,
+                fail: false,
+            }
+            .arc(),
+            3,
+        );
+        let runner = BehaviorRunner::from_node(tree);
+
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 1);
+        check!(res == Some(true));
+        check!(context.stack == vec![5]);
+    }
+
+    #[test]
+    fn fallback_skipped_on_success() {
+        use crate::testing::SucceedAfter;
+
+        let tree = Selector::with_fallback(
+            [SucceedAfter(2).arc()],
+            PushAfter {
+                steps: 1,
+                payload: 7,
+                fail: false,
+            }
+            .arc(),
+            3,
+        );
+        let runner = BehaviorRunner::from_node(tree);
+
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 2);
+        check!(res == Some(true));
+        check!(context.stack == Vec::<i32>::new());
+    }
+
+    #[test]
+    fn fallback_aborts_the_running_children() {
+        use crate::composite::tests::Idle;
+        use crate::coverage::NodeId;
+
+        let tree = Selector::with_fallback(
+            [Idle(1).arc()],
+            PushAfter {
+                steps: 0,
+                payload: 3,
+                fail: false,
+            }
+            .arc(),
+            2,
+        );
+        let mut runner = BehaviorRunner::from_node(tree);
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == None);
+        // Still waiting on the main selector once its budget is spent
+        let main = NodeId::root().child(0);
+        check!(runner.active_node_ids() == vec![NodeId::root(), main.clone(), main.child(0)]);
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(context.stack == vec![1, 3]);
+    }
+
+    #[test]
+    fn fallback_state_round_trips() {
+        use crate::testing::{FailAfter, SucceedAfter};
+
+        let tree = Selector::with_fallback([FailAfter(5).arc()], SucceedAfter(1).arc(), 3).arc();
+        let_assert!(
+            NodeResult::Running(running) = tree.clone().tick(&mut Context { stack: vec![] })
+        );
+        let mut out = vec![];
+        check!(running.serialize_state(&mut out) == Ok(()));
+        let mut bytes = out.as_slice();
+        let_assert!(Ok(restored) = tree.deserialize_state(&mut bytes));
+        check!(bytes.is_empty());
+        let mut again = vec![];
+        check!(restored.serialize_state(&mut again) == Ok(()));
+        check!(again == out);
+    }
+
+    /// Succeeds while the context's stack is empty
+    #[derive(Debug)]
+    struct StackEmpty;
+
+    impl BehaviorNode<Context> for StackEmpty {
+        fn tick(self: Arc<Self>, context: &mut Context) -> NodeResult<Context> {
+            if context.stack.is_empty() {
+                NodeResult::Success
+            } else {
+                NodeResult::Failure
+            }
+        }
+    }
+
+    /// Pops the stack one element per tick, pushing a marker if aborted
+    #[derive(Debug)]
+    struct Drain;
+
+    impl BehaviorNode<Context> for Drain {
+        fn tick(self: Arc<Self>, context: &mut Context) -> NodeResult<Context> {
+            context.stack.pop();
+            NodeResult::Running(self)
+        }
+
+        fn on_abort(&self, context: &mut Context) {
+            context.stack.push(-1);
+        }
+    }
+
+    #[test]
+    fn committed_vs_reactive_running() {
+        // Drain empties the stack on its second tick, which makes StackEmpty
+        // pass from then on
+        let init = || Context { stack: vec![1, 2] };
+
+        let runner = BehaviorRunner::from_node(Selector::new([StackEmpty.arc(), Drain.arc()]));
+        let (res, context) = test_with_context(init, runner, 4);
+        // Committed to Drain, so never notices
+        check!(res == None);
+        check!(context.stack == Vec::<i32>::new());
+
+        let runner =
+            BehaviorRunner::from_node(ReactiveSelector::new([StackEmpty.arc(), Drain.arc()]));
+        let (res, context) = test_with_context(init, runner, 4);
+        // Re-checked on the third tick: StackEmpty passes and Drain is aborted
+        check!(res == Some(true));
+        check!(context.stack == vec![-1]);
+    }
+
+    #[test]
+    fn reactive_selector_preempts_running_child() {
+        use crate::composite::Condition;
+
+        /// Pushes a 0 per tick forever, pushing -1 if aborted
+        #[derive(Debug)]
+        struct Patrol;
+        impl BehaviorNode<Context> for Patrol {
+            fn tick(self: Arc<Self>, context: &mut Context) -> NodeResult<Context> {
+                context.stack.push(0);
+                self.running()
+            }
+
+            fn on_abort(&self, context: &mut Context) {
+                context.stack.push(-1);
+            }
+        }
+
+        let alarm = Condition::new(|context: &Context| context.stack.len() >= 3);
+        let mut runner =
+            BehaviorRunner::from_node(ReactiveSelector::new([alarm.arc(), Patrol.arc()]));
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == None);
+        // Child 0 fails twice more while Patrol is mid-run
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == None);
+        check!(context.stack == vec![0, 0, 0]);
+        // Then it succeeds, preempting Patrol
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(context.stack == vec![0, 0, 0, -1]);
+    }
+
+    #[test]
+    fn unchanged_resumes_are_reused() {
+        use crate::composite::Sequence;
+
+        #[derive(Debug)]
+        struct Hold;
+        impl BehaviorNode<Context> for Hold {
+            fn tick(self: Arc<Self>, _: &mut Context) -> NodeResult<Context> {
+                self.running()
+            }
+        }
+
+        let mut tree = Hold.arc();
+        for depth in 0..5 {
+            tree = if depth % 2 == 0 {
+                Sequence::new([tree]).arc()
+            } else {
+                Selector::new([tree]).arc()
+            };
+        }
+
+        let mut runner = BehaviorRunner::new(tree);
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == None);
+        let first = runner.active_path();
+        check!(first.len() == 6);
+        for _ in 0..3 {
+            check!(runner.proceed(&mut context) == None);
+            let path = runner.active_path();
+            // The whole running spine is the same allocation as before
+            check!(path.iter().zip(&first).all(|(a, b)| Arc::ptr_eq(a, b)));
+        }
+    }
+
+    #[test]
+    fn selector_probability_is_any_success() {
+        use crate::composite::tests::Chance;
+
+        let context = Context { stack: vec![] };
+        let sel = Selector::new([Chance(0.5).arc(), Chance(0.8).arc()]);
+        check!(sel.success_probability(&context) == 1.0 - 0.5 * 0.2);
+        check!(Selector::<Context>::new([]).success_probability(&context) == 0.0);
+    }
 }
@@ -1,6 +1,13 @@
-use crate::{BehaviorArc, BehaviorNode, NodeResult};
+use crate::clock::TickClock;
+use crate::state::{self, StateError};
+use crate::{
+    children_value_eq, explain, fallible, BehaviorArc, BehaviorNode, NodeKind, NodeResult,
+};
+use std::hash::Hasher;
 use std::sync::Arc;
 
+use super::shared::{check_shared, SharedChildError};
+
 pub struct Sequence<B> {
     pub(crate) sub: Arc<[BehaviorArc<B>]>,
 }
@@ -13,6 +20,12 @@ impl<B> std::fmt::Debug for Sequence<B> {
     }
 }
 
+impl<B> Sequence<B> {
+    pub fn new(children: impl IntoIterator<Item = BehaviorArc<B>>) -> Self {
+        children.into_iter().collect()
+    }
+}
+
 impl<B, I: Into<BehaviorArc<B>>> FromIterator<I> for Sequence<B> {
     fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
         Self {
@@ -22,36 +35,188 @@ impl<B, I: Into<BehaviorArc<B>>> FromIterator<I> for Sequence<B> {
 }
 
 impl<B: 'static> Sequence<B> {
+    /// Builds each child only once the sequence first reaches it
+    ///
+    /// Every builder is wrapped in a [`super::Lazy`], so children behind an
+    /// early failure are never constructed.
+    pub fn from_lazy<F>(builders: impl IntoIterator<Item = F>) -> Self
+    where
+        F: FnOnce() -> BehaviorArc<B> + Send + 'static,
+    {
+        builders
+            .into_iter()
+            .map(|build| super::Lazy::new(build).arc())
+            .collect()
+    }
+
+    /// Like [`Sequence::new`], but rejects a stateful child that appears twice
+    ///
+    /// See [`BehaviorNode::is_stateful`].
+    pub fn new_checked(
+        children: impl IntoIterator<Item = BehaviorArc<B>>,
+    ) -> Result<Self, SharedChildError> {
+        let node = Self::new(children);
+        check_shared(&node.sub)?;
+        Ok(node)
+    }
+
+    /// Shares a budget of `total_ticks` ticks across all children
+    ///
+    /// The sequence fails if it is still running after its `total_ticks`th
+    /// tick, no matter which child is using up the budget.
+    pub fn with_budget(self, total_ticks: usize) -> BudgetedSequence<B> {
+        BudgetedSequence {
+            inner: self.arc(),
+            budget: total_ticks,
+            running: None,
+        }
+    }
+
+    /// `order` says where each of `seq` sits among the children of the node
+    /// that started it, if that isn't simply in turn
     pub(crate) fn resume(
         seq: Arc<[BehaviorArc<B>]>,
+        order: Option<Arc<[usize]>>,
         index: usize,
         resume: BehaviorArc<B>,
     ) -> BehaviorArc<B> {
-        SequenceResume { seq, resume, index }.arc()
+        SequenceResume {
+            seq,
+            order,
+            resume,
+            index,
+        }
+        .arc()
     }
 }
 
 impl<B: 'static> BehaviorNode<B> for Sequence<B> {
     fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
         for (idx, sub) in self.sub.iter().enumerate() {
-            match sub.clone().tick(blackboard) {
+            if !sub.is_enabled(blackboard) {
+                continue;
+            }
+            let Some(res) = fallible::tick_child(sub.clone(), blackboard) else {
+                return NodeResult::Failure;
+            };
+            match res {
                 NodeResult::Success => {}
-                NodeResult::Failure => return NodeResult::Failure,
+                NodeResult::Failure => {
+                    explain::note(|out| out.push_str(&format!("failed at step {idx}")));
+                    return NodeResult::Failure;
+                }
                 NodeResult::Running(resume) => {
-                    return NodeResult::Running(Self::resume(self.sub.clone(), idx, resume))
+                    return NodeResult::Running(Self::resume(self.sub.clone(), None, idx, resume))
                 }
             }
         }
         NodeResult::Success
     }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Sequence
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.sub.first().map_or(1, |child| child.estimated_cost())
+    }
+
+    fn success_probability(&self, blackboard: &B) -> f32 {
+        self.sub
+            .iter()
+            .filter(|child| child.is_enabled(blackboard))
+            .map(|child| child.success_probability(blackboard))
+            .product()
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| children_value_eq(&self.sub, &other.sub))
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        Some(Sequence::new(children).arc())
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        read_resume(&self.sub, bytes, self.name(), Sequence::resume)
+    }
 }
 
+/// A running [`Sequence`], waiting on the child at `index`
+///
+/// Nested sequences and selectors each keep their own resume rather than one
+/// flattened path. When the running child hands itself back unchanged the
+/// whole spine is reused without allocating, but a child that returns a new
+/// node rebuilds one resume for every sequence and selector above it.
 pub(crate) struct SequenceResume<B> {
     pub(crate) seq: Arc<[BehaviorArc<B>]>,
+    pub(crate) order: Option<Arc<[usize]>>,
     pub(crate) resume: BehaviorArc<B>,
     pub(crate) index: usize,
 }
 
+/// Where the child at `index` of a resume's `seq` sits among the listed
+/// children, which `order` may have shuffled
+pub(crate) fn listed_index(order: Option<&[usize]>, index: usize) -> usize {
+    order.map_or(index, |order| order[index])
+}
+
+/// Writes the state of a [`SequenceResume`] or [`super::SelectorResume`]
+pub(crate) fn write_resume<B: 'static>(
+    out: &mut Vec<u8>,
+    order: Option<&[usize]>,
+    index: usize,
+    resume: &BehaviorArc<B>,
+) -> Result<(), StateError> {
+    state::write_option(out, order, |out, order| {
+        state::write_indices(out, order);
+        Ok(())
+    })?;
+    state::write_u64(out, index as u64);
+    resume.serialize_state(out)
+}
+
+/// Reads back what [`write_resume`] wrote, for a node with children `sub`
+pub(crate) fn read_resume<B: 'static>(
+    sub: &Arc<[BehaviorArc<B>]>,
+    bytes: &mut &[u8],
+    node: &'static str,
+    build: impl FnOnce(
+        Arc<[BehaviorArc<B>]>,
+        Option<Arc<[usize]>>,
+        usize,
+        BehaviorArc<B>,
+    ) -> BehaviorArc<B>,
+) -> Result<BehaviorArc<B>, StateError> {
+    let order = state::read_option(bytes, |bytes| {
+        let order = state::read_indices(bytes, sub.len(), node)?;
+        // Every child, each exactly once
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        if !sorted.iter().copied().eq(0..sub.len()) {
+            return Err(StateError::Invalid(node));
+        }
+        Ok(Arc::<[usize]>::from(order))
+    })?;
+    let seq = match order.as_ref() {
+        Some(order) => order.iter().map(|at| sub[*at].clone()).collect(),
+        None => sub.clone(),
+    };
+    let index = state::read_index(bytes, seq.len(), node)?;
+    let resume = seq[index].deserialize_state(bytes)?;
+    Ok(build(seq, order, index, resume))
+}
+
 impl<B> std::fmt::Debug for SequenceResume<B> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct(&format!("SequenceResume<{:p}>", self.seq.as_ref()))
@@ -64,36 +229,553 @@ impl<B> std::fmt::Debug for SequenceResume<B> {
 impl<B: 'static> BehaviorNode<B> for SequenceResume<B> {
     fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
         // Tick the node we want to resume on
-        match self.resume.clone().tick(blackboard) {
+        let Some(res) = fallible::tick_child(self.resume.clone(), blackboard) else {
+            return NodeResult::Failure;
+        };
+        match res {
             NodeResult::Success => {}
-            NodeResult::Failure => return NodeResult::Failure,
+            NodeResult::Failure => {
+                let step = listed_index(self.order.as_deref(), self.index);
+                explain::note(|out| out.push_str(&format!("failed at step {step}")));
+                return NodeResult::Failure;
+            }
+            // Nothing changed below us, so neither did we
+            NodeResult::Running(resume) if Arc::ptr_eq(&resume, &self.resume) => {
+                return self.running()
+            }
             NodeResult::Running(resume) => {
-                return NodeResult::Running(Sequence::resume(self.seq.clone(), self.index, resume))
+                return NodeResult::Running(Sequence::resume(
+                    self.seq.clone(),
+                    self.order.clone(),
+                    self.index,
+                    resume,
+                ))
             }
         }
         for (idx, sub) in self.seq.iter().enumerate().skip(self.index + 1) {
-            match sub.clone().tick(blackboard) {
+            if !sub.is_enabled(blackboard) {
+                continue;
+            }
+            let Some(res) = fallible::tick_child(sub.clone(), blackboard) else {
+                return NodeResult::Failure;
+            };
+            match res {
                 NodeResult::Success => {}
-                NodeResult::Failure => return NodeResult::Failure,
+                NodeResult::Failure => {
+                    let step = listed_index(self.order.as_deref(), idx);
+                    explain::note(|out| out.push_str(&format!("failed at step {step}")));
+                    return NodeResult::Failure;
+                }
                 NodeResult::Running(resume) => {
-                    return NodeResult::Running(Sequence::resume(self.seq.clone(), idx, resume))
+                    return NodeResult::Running(Sequence::resume(
+                        self.seq.clone(),
+                        self.order.clone(),
+                        idx,
+                        resume,
+                    ))
+                }
+            }
+        }
+        NodeResult::Success
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Sequence
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.estimated_cost()
+    }
+
+    fn success_probability(&self, blackboard: &B) -> f32 {
+        let rest = self.seq[self.index + 1..]
+            .iter()
+            .filter(|child| child.is_enabled(blackboard))
+            .map(|child| child.success_probability(blackboard))
+            .product::<f32>();
+        self.resume.success_probability(blackboard) * rest
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.resume.clone()]
+    }
+
+    fn active_origins(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.seq[self.index].clone()]
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        write_resume(out, self.order.as_deref(), self.index, &self.resume)
+    }
+}
+
+/// A [`Sequence`] that re-checks the children before its running child
+/// every tick
+///
+/// While a child is running, each tick first re-runs the children before it
+/// from scratch. If one of them fails or starts running, the running child
+/// is abandoned ([`BehaviorNode::on_abort`] is called along its active spine)
+/// and the sequence fails or runs from that earlier child instead. Otherwise
+/// the running child is resumed.
+pub struct ReactiveSequence<B> {
+    pub(crate) sub: Arc<[BehaviorArc<B>]>,
+    running: Option<(usize, BehaviorArc<B>)>,
+}
+
+impl<B> std::fmt::Debug for ReactiveSequence<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("ReactiveSequence<{:p}>", self.sub.as_ref()))
+            .field("sub", &self.sub)
+            .field("running", &self.running)
+            .finish()
+    }
+}
+
+impl<B> ReactiveSequence<B> {
+    pub fn new(children: impl IntoIterator<Item = BehaviorArc<B>>) -> Self {
+        children.into_iter().collect()
+    }
+}
+
+impl<B, I: Into<BehaviorArc<B>>> FromIterator<I> for ReactiveSequence<B> {
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        Self {
+            sub: Arc::from(iter.into_iter().map(Into::into).collect::<Vec<_>>()),
+            running: None,
+        }
+    }
+}
+
+impl<B: 'static> ReactiveSequence<B> {
+    fn running_at(&self, index: usize, resume: BehaviorArc<B>) -> NodeResult<B> {
+        NodeResult::Running(
+            Self {
+                sub: self.sub.clone(),
+                running: Some((index, resume)),
+            }
+            .arc(),
+        )
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for ReactiveSequence<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        for (idx, sub) in self.sub.iter().enumerate() {
+            let node = match self.running.as_ref() {
+                Some((running, resume)) if *running == idx => resume.clone(),
+                _ if !sub.is_enabled(blackboard) => continue,
+                _ => sub.clone(),
+            };
+            let res = fallible::tick_child(node, blackboard);
+            if let Some((running, resume)) = self.running.as_ref() {
+                // An earlier child stopped the sequence short
+                if idx < *running && !matches!(res, Some(NodeResult::Success)) {
+                    crate::abort_spine(resume, blackboard);
+                }
+            }
+            let Some(res) = res else {
+                return NodeResult::Failure;
+            };
+            match res {
+                NodeResult::Success => {}
+                NodeResult::Failure => return NodeResult::Failure,
+                NodeResult::Running(resume) => return self.running_at(idx, resume),
+            }
+        }
+        NodeResult::Success
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Sequence
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.sub.first().map_or(1, |child| child.estimated_cost())
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| children_value_eq(&self.sub, &other.sub))
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        Some(ReactiveSequence::new(children).arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.running
+            .iter()
+            .map(|(_, resume)| resume.clone())
+            .collect()
+    }
+
+    fn active_origins(&self) -> Vec<BehaviorArc<B>> {
+        self.running
+            .iter()
+            .map(|(idx, _)| self.sub[*idx].clone())
+            .collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_option(out, self.running.as_ref(), |out, (index, resume)| {
+            state::write_u64(out, *index as u64);
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let running = state::read_option(bytes, |bytes| {
+            let index = state::read_index(bytes, self.sub.len(), self.name())?;
+            Ok((index, self.sub[index].deserialize_state(bytes)?))
+        })?;
+        Ok(Self {
+            sub: self.sub.clone(),
+            running,
+        }
+        .arc())
+    }
+}
+
+/// A [`Sequence`] with a shared tick budget, see [`Sequence::with_budget`]
+///
+/// Running out of budget aborts the running sequence (see
+/// [`BehaviorNode::on_abort`]).
+pub struct BudgetedSequence<B> {
+    inner: BehaviorArc<B>,
+    budget: usize,
+    /// The ticks left and the running sequence
+    running: Option<(usize, BehaviorArc<B>)>,
+}
+
+impl<B> std::fmt::Debug for BudgetedSequence<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BudgetedSequence")
+            .field("inner", &self.inner)
+            .field("budget", &self.budget)
+            .field("running", &self.running)
+            .finish()
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for BudgetedSequence<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let (remaining, node) = match self.running.as_ref() {
+            Some((remaining, resume)) => (*remaining, resume.clone()),
+            None => (self.budget, self.inner.clone()),
+        };
+        if remaining == 0 {
+            return NodeResult::Failure;
+        }
+        match node.tick(blackboard) {
+            // That was the last tick we could afford
+            NodeResult::Running(resume) if remaining == 1 => {
+                crate::abort_spine(&resume, blackboard);
+                NodeResult::Failure
+            }
+            NodeResult::Running(resume) => NodeResult::Running(
+                Self {
+                    inner: self.inner.clone(),
+                    budget: self.budget,
+                    running: Some((remaining - 1, resume)),
                 }
+                .arc(),
+            ),
+            res => res,
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Decorator
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        match self.running.as_ref() {
+            Some((_, resume)) => resume.estimated_cost(),
+            None => self.inner.estimated_cost(),
+        }
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.inner.clone()]
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_usize(self.budget);
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.running
+            .iter()
+            .map(|(_, resume)| resume.clone())
+            .collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_option(out, self.running.as_ref(), |out, (remaining, resume)| {
+            state::write_u64(out, *remaining as u64);
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let running = state::read_option(bytes, |bytes| {
+            let remaining = state::read_index(bytes, self.budget, self.name())?;
+            Ok((remaining, self.inner.deserialize_state(bytes)?))
+        })?;
+        Ok(Self {
+            inner: self.inner.clone(),
+            budget: self.budget,
+            running,
+        }
+        .arc())
+    }
+}
+
+type Clock<B> = Arc<dyn TickClock<B>>;
+type RecordStep<B> = Arc<dyn Fn(&mut B, usize, u64) + Send + Sync>;
+
+/// A [`Sequence`] that measures how long each of its children took
+///
+/// `clock` is any [`TickClock`], such as a time field read off the blackboard
+/// or a [`crate::ProceedClock`]. When a child finishes, `record` is called with the child's index and the time
+/// that passed between its first tick and its last.
+pub struct TimedSequence<B> {
+    pub(crate) sub: Arc<[BehaviorArc<B>]>,
+    clock: Clock<B>,
+    record: RecordStep<B>,
+}
+
+impl<B> std::fmt::Debug for TimedSequence<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("TimedSequence<{:p}>", self.sub.as_ref()))
+            .field("sub", &self.sub)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> TimedSequence<B> {
+    pub fn new<R>(
+        clock: impl TickClock<B> + 'static,
+        record: R,
+        children: impl IntoIterator<Item = BehaviorArc<B>>,
+    ) -> Self
+    where
+        R: Fn(&mut B, usize, u64) + Send + Sync + 'static,
+    {
+        Self {
+            sub: Arc::from(children.into_iter().collect::<Vec<_>>()),
+            clock: Arc::new(clock),
+            record: Arc::new(record),
+        }
+    }
+
+    fn tick_from(
+        seq: &Arc<[BehaviorArc<B>]>,
+        clock: &Clock<B>,
+        record: &RecordStep<B>,
+        index: usize,
+        mut resume: Option<(u64, BehaviorArc<B>)>,
+        blackboard: &mut B,
+    ) -> NodeResult<B>
+    where
+        B: 'static,
+    {
+        for (idx, sub) in seq.iter().enumerate().skip(index) {
+            let (started, node) = resume
+                .take()
+                .unwrap_or_else(|| (clock.now(blackboard), sub.clone()));
+            let res = fallible::tick_child(node, blackboard);
+            if let Some(NodeResult::Running(resume)) = res {
+                return NodeResult::Running(
+                    TimedSequenceResume {
+                        seq: seq.clone(),
+                        clock: clock.clone(),
+                        record: record.clone(),
+                        index: idx,
+                        started,
+                        resume,
+                    }
+                    .arc(),
+                );
+            }
+            let elapsed = clock.now(blackboard).saturating_sub(started);
+            record(blackboard, idx, elapsed);
+            if !matches!(res, Some(NodeResult::Success)) {
+                return NodeResult::Failure;
             }
         }
         NodeResult::Success
     }
 }
 
+impl<B: 'static> BehaviorNode<B> for TimedSequence<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        Self::tick_from(&self.sub, &self.clock, &self.record, 0, None, blackboard)
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Sequence
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.sub.first().map_or(1, |child| child.estimated_cost())
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.sub.to_vec()
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let index = state::read_index(bytes, self.sub.len(), self.name())?;
+        let started = state::read_u64(bytes)?;
+        Ok(TimedSequenceResume {
+            seq: self.sub.clone(),
+            clock: self.clock.clone(),
+            record: self.record.clone(),
+            index,
+            started,
+            resume: self.sub[index].deserialize_state(bytes)?,
+        }
+        .arc())
+    }
+}
+
+pub(crate) struct TimedSequenceResume<B> {
+    seq: Arc<[BehaviorArc<B>]>,
+    clock: Clock<B>,
+    record: RecordStep<B>,
+    pub(crate) index: usize,
+    pub(crate) started: u64,
+    pub(crate) resume: BehaviorArc<B>,
+}
+
+impl<B> std::fmt::Debug for TimedSequenceResume<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("TimedSequenceResume<{:p}>", self.seq.as_ref()))
+            .field("resume", &self.resume)
+            .field("index", &self.index)
+            .field("started", &self.started)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for TimedSequenceResume<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        TimedSequence::tick_from(
+            &self.seq,
+            &self.clock,
+            &self.record,
+            self.index,
+            Some((self.started, self.resume.clone())),
+            blackboard,
+        )
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Sequence
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.estimated_cost()
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.resume.clone()]
+    }
+
+    fn active_origins(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.seq[self.index].clone()]
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_u64(out, self.index as u64);
+        state::write_u64(out, self.started);
+        self.resume.serialize_state(out)
+    }
+}
+
+/// Tuples of nodes run as a [`Sequence`] of their elements, left to right
+///
+/// [`BehaviorNode::arc`] (and so [`crate::BehaviorRunner::from_node`])
+/// turns the tuple into that sequence once, so nothing is re-wrapped per
+/// tick. A tuple put into an `Arc` by hand still works, but builds the
+/// sequence each activation.
+macro_rules! tuple_sequence {
+    ($($name:ident . $idx:tt),+) => {
+        impl<B: 'static, $($name),+> From<($($name,)+)> for Sequence<B>
+        where
+            $($name: Into<BehaviorArc<B>>),+
+        {
+            fn from(tuple: ($($name,)+)) -> Self {
+                Sequence::new([$(tuple.$idx.into()),+])
+            }
+        }
+
+        impl<B: 'static, $($name),+> BehaviorNode<B> for ($($name,)+)
+        where
+            $($name: Into<BehaviorArc<B>> + Clone + std::fmt::Debug + Send + Sync + 'static),+
+        {
+            fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+                Sequence::from((*self).clone()).arc().tick(blackboard)
+            }
+
+            fn name(&self) -> &'static str {
+                "Sequence"
+            }
+
+            fn kind(&self) -> NodeKind {
+                NodeKind::Sequence
+            }
+
+            fn estimated_cost(&self) -> u32 {
+                Sequence::from(self.clone()).estimated_cost()
+            }
+
+            fn children(&self) -> Vec<BehaviorArc<B>> {
+                vec![$(self.$idx.clone().into()),+]
+            }
+
+            fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+                Sequence::from(self.clone()).deserialize_state(bytes)
+            }
+
+            fn arc(self) -> BehaviorArc<B> {
+                Sequence::from(self).arc()
+            }
+        }
+    };
+}
+
+tuple_sequence!(T0.0, T1.1);
+tuple_sequence!(T0.0, T1.1, T2.2);
+tuple_sequence!(T0.0, T1.1, T2.2, T3.3);
+tuple_sequence!(T0.0, T1.1, T2.2, T3.3, T4.4);
+tuple_sequence!(T0.0, T1.1, T2.2, T3.3, T4.4, T5.5);
+tuple_sequence!(T0.0, T1.1, T2.2, T3.3, T4.4, T5.5, T6.6);
+tuple_sequence!(T0.0, T1.1, T2.2, T3.3, T4.4, T5.5, T6.6, T7.7);
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
-    use super::{BehaviorNode, NodeResult, Sequence};
+    use super::{
+        BehaviorNode, NodeResult, ReactiveSequence, Sequence, SharedChildError, TimedSequence,
+    };
+    use crate::testing::SucceedAfter;
     use crate::{
-        composite::tests::{test_with_context, Context},
-        BehaviorRunner,
+        composite::tests::{test_with_context, Context, Push, PushAfter},
+        BehaviorRunner, ManualClock, ProceedClock, TickCounter,
     };
-    use assert2::check;
+    use assert2::{check, let_assert};
     use glam::Vec2;
 
     #[derive(Debug)]
@@ -202,4 +884,370 @@ mod tests {
             check!(res.is_some_and(|v| v));
         }
     }
+
+    #[test]
+    fn sequence_new_accepts_any_iterator() {
+        let push = |payload| {
+            PushAfter {
+                steps: 1,
+                payload,
+                fail: false,
+            }
+            .arc()
+        };
+        let run = |seq: Sequence<Context>| {
+            test_with_context(
+                || Context { stack: Vec::new() },
+                BehaviorRunner::from_node(seq),
+                9,
+            )
+        };
+
+        let (res, context) = run(Sequence::new([push(1), push(2)]));
+        check!(res == Some(true));
+        check!(context.stack == vec![1, 2]);
+
+        let (res, context) = run(Sequence::new(vec![push(1), push(2)]));
+        check!(res == Some(true));
+        check!(context.stack == vec![1, 2]);
+
+        let (res, context) = run(Sequence::new((1..=2).map(push)));
+        check!(res == Some(true));
+        check!(context.stack == vec![1, 2]);
+    }
+
+    #[test]
+    fn budgeted_sequence_shares_budget() {
+        let tree = || {
+            Sequence::new([
+                SucceedAfter(3).arc(),
+                PushAfter {
+                    steps: 1,
+                    payload: 1,
+                    fail: false,
+                }
+                .arc(),
+                PushAfter {
+                    steps: 1,
+                    payload: 2,
+                    fail: false,
+                }
+                .arc(),
+            ])
+        };
+
+        let runner = BehaviorRunner::from_node(tree().with_budget(6));
+        let (res, context) = test_with_context(|| Context { stack: Vec::new() }, runner, 10);
+        check!(res == Some(true));
+        check!(context.stack == vec![1, 2]);
+
+        // The first child eats most of the budget, so the last one runs out
+        let runner = BehaviorRunner::from_node(tree().with_budget(5));
+        let (res, context) = test_with_context(|| Context { stack: Vec::new() }, runner, 10);
+        check!(res == Some(false));
+        check!(context.stack == vec![1]);
+    }
+
+    #[test]
+    fn budgeted_sequence_aborts_when_out_of_budget() {
+        use crate::composite::tests::Idle;
+        use crate::state::{self, StateError};
+        use crate::NodeKind;
+
+        let tree = Sequence::new([Idle(1).arc()]).with_budget(2).arc();
+        check!(tree.kind() == NodeKind::Decorator);
+        let mut runner = BehaviorRunner::new(tree.clone());
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == Some(false));
+        check!(context.stack == vec![1]);
+
+        // A remaining budget it could never have had is rejected
+        let mut out = vec![];
+        state::write_option(&mut out, Some(()), |out, ()| {
+            state::write_u64(out, 2);
+            Ok::<_, StateError>(())
+        })
+        .unwrap();
+        let mut bytes = out.as_slice();
+        check!(
+            tree.deserialize_state(&mut bytes).err()
+                == Some(StateError::Invalid("BudgetedSequence"))
+        );
+    }
+
+    #[derive(Debug, Default)]
+    struct Clocked {
+        tick: u64,
+        timings: Vec<(usize, u64)>,
+    }
+
+    #[test]
+    fn timed_sequence_records_step_durations() {
+        let mut runner = BehaviorRunner::from_node(TimedSequence::new(
+            |context: &Clocked| context.tick,
+            |context: &mut Clocked, step, elapsed| context.timings.push((step, elapsed)),
+            [
+                SucceedAfter(2).arc(),
+                SucceedAfter(0).arc(),
+                SucceedAfter(4).arc(),
+            ],
+        ));
+
+        let mut context = Clocked::default();
+        while runner.proceed(&mut context).is_none() {
+            context.tick += 1;
+        }
+        check!(context.tick == 6);
+        check!(context.timings == vec![(0, 2), (1, 0), (2, 4)]);
+    }
+
+    impl TickCounter for Clocked {
+        fn tick_count(&self) -> u64 {
+            self.tick
+        }
+
+        fn set_tick_count(&mut self, ticks: u64) {
+            self.tick = ticks;
+        }
+    }
+
+    #[test]
+    fn timed_sequence_takes_any_tick_clock() {
+        let record = |context: &mut Clocked, step, elapsed| context.timings.push((step, elapsed));
+        let children = || [SucceedAfter(2).arc(), SucceedAfter(1).arc()];
+
+        let mut runner =
+            BehaviorRunner::from_node(TimedSequence::new(ProceedClock, record, children()));
+        let mut context = Clocked::default();
+        while runner.proceed_counted(&mut context).is_none() {}
+        check!(context.timings == vec![(0, 2), (1, 1)]);
+
+        let clock = ManualClock::new(10);
+        let mut runner =
+            BehaviorRunner::from_node(TimedSequence::new(clock.clone(), record, children()));
+        let mut context = Clocked::default();
+        while runner.proceed(&mut context).is_none() {
+            clock.advance(5);
+        }
+        check!(context.timings == vec![(0, 10), (1, 5)]);
+    }
+
+    #[derive(Debug)]
+    struct Fail;
+
+    impl BehaviorNode<Context> for Fail {
+        fn tick(self: Arc<Self>, _context: &mut Context) -> NodeResult<Context> {
+            NodeResult::Failure
+        }
+    }
+
+    #[test]
+    fn tuples_are_sequences() {
+        let runner = BehaviorRunner::from_node((Push(1).arc(), Push(2).arc()));
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 0);
+        check!(res == Some(true));
+        check!(context.stack == vec![1, 2]);
+
+        let runner = BehaviorRunner::from_node((Push(1).arc(), Fail.arc(), Push(3).arc()));
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 0);
+        check!(res == Some(false));
+        check!(context.stack == vec![1]);
+
+        let push_twice = PushAfter {
+            steps: 1,
+            payload: 3,
+            fail: false,
+        };
+        let runner = BehaviorRunner::from_node((
+            Push(1).arc(),
+            Push(2).arc(),
+            push_twice.arc(),
+            Push(4).arc(),
+        ));
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 1);
+        check!(res == Some(true));
+        check!(context.stack == vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn tuples_track_their_running_child() {
+        use crate::coverage::NodeId;
+        use crate::testing::AlwaysSucceed;
+        use crate::visualize;
+
+        let tuple = (AlwaysSucceed.arc(), SucceedAfter(3).arc());
+        let mut runner = BehaviorRunner::new(tuple.clone().arc());
+        check!(runner.proceed(&mut ()) == None);
+        check!(runner.active_node_ids() == vec![NodeId::root(), NodeId::root().child(1)]);
+        check!(
+            visualize::debug_runner(&runner) == "* Sequence\n    AlwaysSucceed\n*   SucceedAfter\n"
+        );
+
+        // Wrapped by hand, the tuple hands out the same children every time
+        let tuple = Arc::new(tuple);
+        let (first, second) = (tuple.children(), tuple.children());
+        check!(first.iter().zip(&second).all(|(a, b)| Arc::ptr_eq(a, b)));
+        let mut runner = BehaviorRunner::new(tuple);
+        check!(runner.proceed(&mut ()) == None);
+        check!(runner.active_node_ids() == vec![NodeId::root(), NodeId::root().child(1)]);
+    }
+
+    #[test]
+    fn checked_constructors_reject_shared_stateful_children() {
+        use crate::composite::{IterAction, Selector};
+
+        let stateful = IterAction::new([1, 2], |context: &mut Context, item| {
+            context.stack.push(item)
+        })
+        .arc();
+        let stateless = SucceedAfter(1).arc();
+
+        let err = Sequence::new_checked([
+            stateless.clone(),
+            stateful.clone(),
+            stateless.clone(),
+            stateful.clone(),
+        ])
+        .err();
+        check!(
+            err == Some(SharedChildError {
+                name: "IterAction",
+                first: 1,
+                second: 3,
+            })
+        );
+        check!(Selector::new_checked([stateful.clone(), stateful.clone()]).is_err());
+
+        // Sharing a stateless node is harmless
+        check!(Sequence::new_checked([stateless.clone(), stateless, stateful]).is_ok());
+    }
+
+    #[test]
+    fn lazy_children_are_built_on_first_reach() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let built = Arc::new(AtomicUsize::new(0));
+        let builder = |fail: bool| {
+            let built = built.clone();
+            move || {
+                built.fetch_add(1, Ordering::Relaxed);
+                if fail {
+                    crate::testing::AlwaysFail.arc()
+                } else {
+                    SucceedAfter(1).arc()
+                }
+            }
+        };
+        let mut runner = BehaviorRunner::from_node(Sequence::from_lazy([
+            builder(false),
+            builder(true),
+            builder(false),
+        ]));
+
+        let mut context = ();
+        check!(built.load(Ordering::Relaxed) == 0);
+        check!(runner.proceed(&mut context) == None);
+        check!(built.load(Ordering::Relaxed) == 1);
+        check!(runner.proceed(&mut context) == Some(false));
+        // The third child sits behind a failure and is never built
+        check!(built.load(Ordering::Relaxed) == 2);
+
+        // Reactivating reuses the children already built
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == Some(false));
+        check!(built.load(Ordering::Relaxed) == 2);
+    }
+
+    #[test]
+    fn reactive_sequence_rechecks_guard() {
+        /// Passes until the stack holds a 1
+        #[derive(Debug)]
+        struct NoOnes;
+        impl BehaviorNode<Context> for NoOnes {
+            fn tick(self: Arc<Self>, context: &mut Context) -> NodeResult<Context> {
+                if context.stack.contains(&1) {
+                    NodeResult::Failure
+                } else {
+                    NodeResult::Success
+                }
+            }
+        }
+
+        /// Pushes a 1 per tick forever, pushing -1 if aborted
+        #[derive(Debug)]
+        struct PushOnes;
+        impl BehaviorNode<Context> for PushOnes {
+            fn tick(self: Arc<Self>, context: &mut Context) -> NodeResult<Context> {
+                context.stack.push(1);
+                self.running()
+            }
+
+            fn on_abort(&self, context: &mut Context) {
+                context.stack.push(-1);
+            }
+        }
+
+        let runner = BehaviorRunner::from_node(Sequence::new([NoOnes.arc(), PushOnes.arc()]));
+        let (res, context) = test_with_context(|| Context { stack: vec![] }, runner, 2);
+        // Committed to PushOnes, so the guard is never looked at again
+        check!(res == None);
+        check!(context.stack == vec![1, 1, 1]);
+
+        let mut runner =
+            BehaviorRunner::from_node(ReactiveSequence::new([NoOnes.arc(), PushOnes.arc()]));
+        let mut context = Context { stack: vec![] };
+        check!(runner.proceed(&mut context) == None);
+        check!(context.stack == vec![1]);
+        // The guard fails now, so the running child is aborted
+        check!(runner.proceed(&mut context) == Some(false));
+        check!(context.stack == vec![1, -1]);
+    }
+
+    #[test]
+    fn sequence_probability_is_the_product() {
+        use crate::composite::tests::Chance;
+
+        let context = Context { stack: vec![] };
+        let seq = Sequence::new([Chance(0.5).arc(), Chance(0.8).arc(), Chance(0.25).arc()]);
+        check!(seq.success_probability(&context) == 0.5 * 0.8 * 0.25);
+        check!(Sequence::<Context>::new([]).success_probability(&context) == 1.0);
+    }
+
+    #[test]
+    fn running_tree_state_round_trips() {
+        use crate::composite::LimitedRepeated;
+
+        let tree = Sequence::new([
+            SucceedAfter(1).arc(),
+            LimitedRepeated::new(3, SucceedAfter(2).arc()).arc(),
+            SucceedAfter(1).arc(),
+        ])
+        .arc();
+        let mut running = tree.clone();
+        for _ in 0..5 {
+            let_assert!(NodeResult::Running(resume) = running.tick(&mut ()));
+            running = resume;
+        }
+
+        let mut out = vec![];
+        check!(running.serialize_state(&mut out) == Ok(()));
+        let mut bytes = out.as_slice();
+        let_assert!(Ok(restored) = tree.deserialize_state(&mut bytes));
+        check!(bytes.is_empty());
+
+        let mut again = vec![];
+        check!(restored.serialize_state(&mut again) == Ok(()));
+        check!(again == out);
+
+        let ticks_left = |mut node: crate::BehaviorArc<()>| {
+            let mut ticks = 1;
+            while let NodeResult::Running(resume) = node.tick(&mut ()) {
+                node = resume;
+                ticks += 1;
+            }
+            ticks
+        };
+        check!(ticks_left(restored) == ticks_left(running));
+    }
 }
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use crate::BehaviorArc;
+
+/// A stateful child appears more than once in the same child list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedChildError {
+    /// The name of the shared child
+    pub name: &'static str,
+    /// Where it appears first
+    pub first: usize,
+    /// Where it appears again
+    pub second: usize,
+}
+
+impl std::fmt::Display for SharedChildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "stateful child {} is shared by positions {} and {}",
+            self.name, self.first, self.second
+        )
+    }
+}
+
+impl std::error::Error for SharedChildError {}
+
+/// Finds the first stateful child whose `Arc` appears twice in `children`
+pub(crate) fn check_shared<B>(children: &[BehaviorArc<B>]) -> Result<(), SharedChildError> {
+    for (second, child) in children.iter().enumerate() {
+        if !child.is_stateful() {
+            continue;
+        }
+        if let Some(first) = children[..second]
+            .iter()
+            .position(|other| Arc::ptr_eq(other, child))
+        {
+            return Err(SharedChildError {
+                name: child.name(),
+                first,
+                second,
+            });
+        }
+    }
+    Ok(())
+}
@@ -1,4 +1,5 @@
-use crate::{BehaviorArc, BehaviorNode, NodeResult};
+use crate::state::StateError;
+use crate::{BehaviorArc, BehaviorNode, NodeKind, NodeResult};
 use std::sync::Arc;
 
 /// Always succeedes.
@@ -37,6 +38,61 @@ impl<B: 'static> BehaviorNode<B> for Succeeder<B> {
             NodeResult::Success
         }
     }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Succeeder
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.child
+            .as_ref()
+            .map_or(1, |child| child.estimated_cost())
+    }
+
+    fn success_probability(&self, _blackboard: &B) -> f32 {
+        1.0
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            match (self.child.as_ref(), other.child.as_ref()) {
+                (Some(a), Some(b)) => a.value_eq(b.as_ref()),
+                (None, None) => true,
+                _ => false,
+            }
+        })
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.child.iter().cloned().collect()
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let [child] = <[_; 1]>::try_from(children).ok()?;
+        Some(Succeeder::new(child).arc())
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.child.iter().cloned().collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        match self.child.as_ref() {
+            Some(child) => child.serialize_state(out),
+            None => Err(StateError::Unsupported(self.name())),
+        }
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        match self.child.as_ref() {
+            Some(child) => Ok(Succeeder::new(child.deserialize_state(bytes)?).arc()),
+            None => Err(StateError::Unsupported(self.name())),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use crate::{fallible, BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+
+type KeyFn<B, K> = Arc<dyn Fn(&B) -> K + Send + Sync>;
+
+/// Ticks the branch matching a key computed from the blackboard
+///
+/// Like [`super::IfElse`], the key is computed once, when the node is
+/// activated, and the chosen branch runs to completion from there. A key
+/// without a branch ticks the default branch if there is one, and fails
+/// otherwise.
+pub struct Switch<B, K> {
+    key: KeyFn<B, K>,
+    /// In the order they were given, so [`BehaviorNode::children`] is stable
+    branches: Vec<(K, BehaviorArc<B>)>,
+    index: HashMap<K, usize>,
+    default: Option<BehaviorArc<B>>,
+}
+
+impl<B, K: std::fmt::Debug> std::fmt::Debug for Switch<B, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Switch")
+            .field("branches", &self.branches)
+            .field("default", &self.default)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B, K: Eq + Hash + Clone> Switch<B, K> {
+    /// If a key appears twice, its last branch is the one ticked
+    pub fn new<F>(key: F, branches: impl IntoIterator<Item = (K, BehaviorArc<B>)>) -> Self
+    where
+        F: Fn(&B) -> K + Send + Sync + 'static,
+    {
+        let branches = branches.into_iter().collect::<Vec<_>>();
+        let index = branches
+            .iter()
+            .enumerate()
+            .map(|(at, (key, _))| (key.clone(), at))
+            .collect();
+        Self {
+            key: Arc::new(key),
+            branches,
+            index,
+            default: None,
+        }
+    }
+
+    /// Ticks `branch` for keys without a branch of their own
+    pub fn with_default(mut self, branch: BehaviorArc<B>) -> Self {
+        self.default = Some(branch);
+        self
+    }
+}
+
+impl<B, K> BehaviorNode<B> for Switch<B, K>
+where
+    B: 'static,
+    K: Eq + Hash + Clone + std::fmt::Debug + Send + Sync + 'static,
+{
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        // As with IfElse, the branch's own resume carries on from here
+        let key = (self.key)(blackboard);
+        let branch = match self.index.get(&key) {
+            Some(&at) => &self.branches[at].1,
+            None => match self.default.as_ref() {
+                Some(default) => default,
+                None => return NodeResult::Failure,
+            },
+        };
+        fallible::tick_child(branch.clone(), blackboard).unwrap_or(NodeResult::Failure)
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Selector
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.children()
+            .iter()
+            .map(|branch| branch.estimated_cost())
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// The keyed branches in order, then the default one
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        self.branches
+            .iter()
+            .map(|(_, branch)| branch)
+            .chain(&self.default)
+            .cloned()
+            .collect()
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        if children.len() != self.children().len() {
+            return None;
+        }
+        let mut children = children.into_iter();
+        let branches = self
+            .branches
+            .iter()
+            .zip(children.by_ref())
+            .map(|((key, _), branch)| (key.clone(), branch))
+            .collect();
+        Some(
+            Self {
+                key: self.key.clone(),
+                branches,
+                index: self.index.clone(),
+                default: children.next(),
+            }
+            .arc(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::{tests::Context, Action},
+        testing::SucceedAfter,
+        BehaviorRunner,
+    };
+
+    fn push(n: i32) -> BehaviorArc<Context> {
+        Action::new(move |context: &mut Context| {
+            context.stack.push(n);
+            NodeResult::Success
+        })
+        .arc()
+    }
+
+    fn mode(context: &Context) -> i32 {
+        context.stack[0]
+    }
+
+    #[test]
+    fn switch_runs_the_matching_branch() {
+        let switch = || Switch::new(mode, [(1, push(10)), (2, push(20)), (3, push(30))]);
+
+        let mut runner = BehaviorRunner::from_node(switch());
+        for (key, pushed) in [(1, 10), (2, 20), (3, 30)] {
+            let mut context = Context { stack: vec![key] };
+            check!(runner.proceed(&mut context) == Some(true));
+            check!(context.stack == [key, pushed]);
+        }
+        let mut context = Context { stack: vec![4] };
+        check!(runner.proceed(&mut context) == Some(false));
+
+        let mut runner = BehaviorRunner::from_node(switch().with_default(push(0)));
+        let mut context = Context { stack: vec![4] };
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(context.stack == [4, 0]);
+    }
+
+    #[test]
+    fn switch_sticks_to_the_running_branch() {
+        let mut runner = BehaviorRunner::from_node(Switch::new(
+            mode,
+            [(1, SucceedAfter(1).arc()), (2, push(20))],
+        ));
+        let mut context = Context { stack: vec![1] };
+        check!(runner.proceed(&mut context) == None);
+
+        // Changing the key mid-branch doesn't switch branches
+        context.stack[0] = 2;
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(context.stack == [2]);
+    }
+}
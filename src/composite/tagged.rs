@@ -0,0 +1,183 @@
+use crate::state::StateError;
+use crate::{BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+use std::sync::Arc;
+
+/// Attaches string tags to its child
+///
+/// Tags are metadata only: ticking passes straight through to the child.
+/// Use [`find_tagged`] to query a tree for the nodes with a tag.
+pub struct Tagged<B> {
+    tags: Arc<[&'static str]>,
+    child: BehaviorArc<B>,
+}
+
+impl<B> std::fmt::Debug for Tagged<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tagged")
+            .field("tags", &self.tags)
+            .field("child", &self.child)
+            .finish()
+    }
+}
+
+impl<B> Tagged<B> {
+    pub fn new(tags: impl IntoIterator<Item = &'static str>, child: BehaviorArc<B>) -> Self {
+        Self {
+            tags: tags.into_iter().collect(),
+            child,
+        }
+    }
+
+    pub fn tags(&self) -> &[&'static str] {
+        &self.tags
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(&tag)
+    }
+
+    pub fn child(&self) -> &BehaviorArc<B> {
+        &self.child
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for Tagged<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        match self.child.clone().tick(blackboard) {
+            NodeResult::Running(child) => NodeResult::Running(
+                Self {
+                    tags: self.tags.clone(),
+                    child,
+                }
+                .arc(),
+            ),
+            res => res,
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Decorator
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.child.estimated_cost()
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn with_children(&self, children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        let [child] = <[_; 1]>::try_from(children).ok()?;
+        Some(
+            Self {
+                tags: self.tags.clone(),
+                child,
+            }
+            .arc(),
+        )
+    }
+
+    fn hash_params(&self, state: &mut dyn std::hash::Hasher) {
+        self.tags.iter().for_each(|tag| state.write(tag.as_bytes()));
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        self.child.serialize_state(out)
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        Ok(Self {
+            tags: self.tags.clone(),
+            child: self.child.deserialize_state(bytes)?,
+        }
+        .arc())
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            self.tags == other.tags && self.child.value_eq(other.child.as_ref())
+        })
+    }
+}
+
+/// Every node in the tree under `root` that was tagged with `tag`
+///
+/// Returns the tagged children themselves (not their [`Tagged`] wrappers),
+/// in depth-first order.
+pub fn find_tagged<B: 'static>(root: &BehaviorArc<B>, tag: &str) -> Vec<BehaviorArc<B>> {
+    let mut found = vec![];
+    let mut pending = vec![root.clone()];
+    while let Some(node) = pending.pop() {
+        if let Some(tagged) = node.downcast_ref::<Tagged<B>>() {
+            if tagged.has_tag(tag) {
+                found.push(tagged.child.clone());
+            }
+        }
+        pending.extend(node.children().into_iter().rev());
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::{Inverter, Selector, Sequence},
+        testing::{AlwaysFail, AlwaysSucceed, SucceedAfter},
+        BehaviorRunner,
+    };
+
+    #[test]
+    fn find_tagged_returns_every_tagged_node() {
+        let shoot = SucceedAfter(2).arc();
+        let melee = AlwaysFail.arc();
+        let patrol = AlwaysSucceed.arc();
+        let combat = Selector::new([
+            Tagged::new(["combat", "ranged"], shoot.clone()).arc(),
+            Tagged::new(["combat"], Inverter::new(melee.clone()).arc()).arc(),
+        ])
+        .arc();
+        let root: BehaviorArc<()> = Sequence::new([
+            Tagged::new(["combat"], combat.clone()).arc(),
+            Tagged::new(["idle"], patrol.clone()).arc(),
+        ])
+        .arc();
+
+        let found = find_tagged(&root, "combat");
+        check!(found.len() == 3);
+        check!(Arc::ptr_eq(&found[0], &combat));
+        check!(Arc::ptr_eq(&found[1], &shoot));
+        check!(found[2]
+            .children()
+            .iter()
+            .any(|child| Arc::ptr_eq(child, &melee)));
+
+        let found = find_tagged(&root, "idle");
+        check!(found.len() == 1);
+        check!(Arc::ptr_eq(&found[0], &patrol));
+        check!(find_tagged(&root, "stealth").is_empty());
+    }
+
+    #[test]
+    fn tagged_does_not_affect_ticking() {
+        let mut runner =
+            BehaviorRunner::from_node(Tagged::<()>::new(["slow"], SucceedAfter(1).arc()));
+        check!(runner.proceed(&mut ()) == None);
+        check!(runner.proceed(&mut ()) == Some(true));
+        check!(
+            !BehaviorRunner::from_node(Tagged::<()>::new(["fail"], AlwaysFail.arc()))
+                .run_once(&mut ())
+        );
+    }
+}
@@ -0,0 +1,670 @@
+//! Nodes that care about how much time has passed
+//!
+//! They all read the time through a [`TickClock`], so a test can drive them
+//! with a scripted clock or a [`crate::ManualClock`] instead of a real one.
+
+use std::hash::Hasher;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::clock::{TickClock, WallClock};
+use crate::state::{self, StateError};
+use crate::{BehaviorArc, BehaviorNode, NodeKind, NodeResult};
+
+type Clock<B> = Arc<dyn TickClock<B>>;
+
+/// What a [`Wait`] or [`Timeout`] measures time in
+enum Measure<B> {
+    Clock(Clock<B>),
+    /// The number of times the node itself was ticked
+    Ticks,
+}
+
+impl<B> Clone for Measure<B> {
+    fn clone(&self) -> Self {
+        match self {
+            Measure::Clock(clock) => Measure::Clock(clock.clone()),
+            Measure::Ticks => Measure::Ticks,
+        }
+    }
+}
+
+/// Runs until `duration` has passed since it was activated, then succeeds
+///
+/// [`Wait::new`] reads the time from a [`TickClock`], while [`Wait::ticks`]
+/// just counts its own ticks.
+pub struct Wait<B> {
+    measure: Measure<B>,
+    duration: u64,
+    /// The activation time, or how many ticks have passed
+    started: Option<u64>,
+}
+
+impl<B> std::fmt::Debug for Wait<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Wait")
+            .field("duration", &self.duration)
+            .field("started", &self.started)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> Wait<B> {
+    pub fn new(clock: impl TickClock<B> + 'static, duration: u64) -> Self {
+        Self {
+            measure: Measure::Clock(Arc::new(clock)),
+            duration,
+            started: None,
+        }
+    }
+
+    /// Runs for `ticks` ticks, then succeeds on the next one
+    ///
+    /// With zero ticks it succeeds on its first tick.
+    pub fn ticks(ticks: usize) -> Self {
+        Self {
+            measure: Measure::Ticks,
+            duration: ticks as u64,
+            started: None,
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for Wait<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let (elapsed, started) = match &self.measure {
+            Measure::Clock(clock) => {
+                let now = clock.now(blackboard);
+                let started = self.started.unwrap_or(now);
+                (now.saturating_sub(started), started)
+            }
+            Measure::Ticks => {
+                let ticked = self.started.unwrap_or(0);
+                (ticked, ticked + 1)
+            }
+        };
+        if elapsed >= self.duration {
+            return NodeResult::Success;
+        }
+        if self.started == Some(started) {
+            return self.running();
+        }
+        NodeResult::Running(
+            Self {
+                measure: self.measure.clone(),
+                duration: self.duration,
+                started: Some(started),
+            }
+            .arc(),
+        )
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_u64(self.duration);
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_option(out, self.started, write_mark)
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        Ok(Self {
+            measure: self.measure.clone(),
+            duration: self.duration,
+            started: state::read_option(bytes, state::read_u64)?,
+        }
+        .arc())
+    }
+}
+
+/// Writes a time (or tick count) for [`state::write_option`]
+fn write_mark(out: &mut Vec<u8>, mark: u64) -> Result<(), StateError> {
+    state::write_u64(out, mark);
+    Ok(())
+}
+
+/// Waits until `delay` has passed since it was first ticked, then runs its
+/// child as usual
+pub struct Delay<B> {
+    clock: Clock<B>,
+    /// In nanoseconds
+    delay: u64,
+    child: BehaviorArc<B>,
+    started: Option<u64>,
+    resume: Option<BehaviorArc<B>>,
+}
+
+impl<B> std::fmt::Debug for Delay<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Delay")
+            .field("delay", &Duration::from_nanos(self.delay))
+            .field("child", &self.child)
+            .field("started", &self.started)
+            .field("resume", &self.resume)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> Delay<B> {
+    /// Measures the delay with a [`WallClock`]
+    pub fn new(delay: Duration, child: BehaviorArc<B>) -> Self {
+        Self::with_clock(WallClock::default(), delay, child)
+    }
+
+    /// Measures the delay with `clock`, which must count nanoseconds
+    pub fn with_clock(
+        clock: impl TickClock<B> + 'static,
+        delay: Duration,
+        child: BehaviorArc<B>,
+    ) -> Self {
+        Self {
+            clock: Arc::new(clock),
+            delay: u64::try_from(delay.as_nanos()).unwrap_or(u64::MAX),
+            child,
+            started: None,
+            resume: None,
+        }
+    }
+
+    fn with_progress(&self, started: u64, resume: Option<BehaviorArc<B>>) -> Self {
+        Self {
+            clock: self.clock.clone(),
+            delay: self.delay,
+            child: self.child.clone(),
+            started: Some(started),
+            resume,
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for Delay<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let now = self.clock.now(blackboard);
+        let started = self.started.unwrap_or(now);
+        let node = match self.resume.as_ref() {
+            Some(resume) => resume.clone(),
+            None if now.saturating_sub(started) < self.delay => {
+                if self.started.is_some() {
+                    return self.running();
+                }
+                return NodeResult::Running(self.with_progress(started, None).arc());
+            }
+            None => self.child.clone(),
+        };
+        match node.tick(blackboard) {
+            NodeResult::Running(resume) => {
+                NodeResult::Running(self.with_progress(started, Some(resume)).arc())
+            }
+            res => res,
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Decorator
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.as_ref().unwrap_or(&self.child).estimated_cost()
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_u64(self.delay);
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.resume.iter().cloned().collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_option(out, self.started, write_mark)?;
+        state::write_option(out, self.resume.as_ref(), |out, resume| {
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let started = state::read_option(bytes, state::read_u64)?;
+        let resume = state::read_option(bytes, |bytes| self.child.deserialize_state(bytes))?;
+        Ok(Self {
+            clock: self.clock.clone(),
+            delay: self.delay,
+            child: self.child.clone(),
+            started,
+            resume,
+        }
+        .arc())
+    }
+}
+
+const NEVER: u64 = u64::MAX;
+
+/// Fails without ticking its child until `period` has passed since the
+/// child last finished
+///
+/// The cooldown belongs to the node, so it carries over between
+/// activations of the tree.
+pub struct Cooldown<B> {
+    clock: Clock<B>,
+    period: u64,
+    child: BehaviorArc<B>,
+    finished_at: Arc<AtomicU64>,
+    resume: Option<BehaviorArc<B>>,
+}
+
+impl<B> std::fmt::Debug for Cooldown<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cooldown")
+            .field("period", &self.period)
+            .field("child", &self.child)
+            .field("resume", &self.resume)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> Cooldown<B> {
+    pub fn new(clock: impl TickClock<B> + 'static, period: u64, child: BehaviorArc<B>) -> Self {
+        Self {
+            clock: Arc::new(clock),
+            period,
+            child,
+            finished_at: Arc::new(AtomicU64::new(NEVER)),
+            resume: None,
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for Cooldown<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let node = match self.resume.as_ref() {
+            Some(resume) => resume.clone(),
+            None => {
+                let finished_at = self.finished_at.load(Ordering::Relaxed);
+                let now = self.clock.now(blackboard);
+                if finished_at != NEVER && now.saturating_sub(finished_at) < self.period {
+                    return NodeResult::Failure;
+                }
+                self.child.clone()
+            }
+        };
+        match node.tick(blackboard) {
+            NodeResult::Running(resume) => NodeResult::Running(
+                Self {
+                    clock: self.clock.clone(),
+                    period: self.period,
+                    child: self.child.clone(),
+                    finished_at: self.finished_at.clone(),
+                    resume: Some(resume),
+                }
+                .arc(),
+            ),
+            res => {
+                let now = self.clock.now(blackboard);
+                self.finished_at.store(now, Ordering::Relaxed);
+                res
+            }
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Decorator
+    }
+
+    fn is_stateful(&self) -> bool {
+        true
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        self.resume.as_ref().unwrap_or(&self.child).estimated_cost()
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_u64(self.period);
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.resume.iter().cloned().collect()
+    }
+
+    /// Includes when the child last finished, which the node keeps between
+    /// activations
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_u64(out, self.finished_at.load(Ordering::Relaxed));
+        state::write_option(out, self.resume.as_ref(), |out, resume| {
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let finished_at = state::read_u64(bytes)?;
+        let resume = state::read_option(bytes, |bytes| self.child.deserialize_state(bytes))?;
+        self.finished_at.store(finished_at, Ordering::Relaxed);
+        Ok(Self {
+            clock: self.clock.clone(),
+            period: self.period,
+            child: self.child.clone(),
+            finished_at: self.finished_at.clone(),
+            resume,
+        }
+        .arc())
+    }
+}
+
+/// Fails if its child is still running once `limit` has passed since it
+/// was activated, aborting the child (see [`BehaviorNode::on_abort`])
+///
+/// [`Timeout::new`] reads the time from a [`TickClock`], while
+/// [`Timeout::ticks`] counts its own ticks, so it only advances while the
+/// timeout is actually being ticked.
+pub struct Timeout<B> {
+    measure: Measure<B>,
+    limit: u64,
+    child: BehaviorArc<B>,
+    /// The activation time (or the ticks so far) and the running child
+    running: Option<(u64, BehaviorArc<B>)>,
+}
+
+impl<B> std::fmt::Debug for Timeout<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Timeout")
+            .field("limit", &self.limit)
+            .field("child", &self.child)
+            .field("running", &self.running)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> Timeout<B> {
+    pub fn new(clock: impl TickClock<B> + 'static, limit: u64, child: BehaviorArc<B>) -> Self {
+        Self {
+            measure: Measure::Clock(Arc::new(clock)),
+            limit,
+            child,
+            running: None,
+        }
+    }
+
+    /// Fails once its child has been running for `max_ticks` ticks
+    pub fn ticks(max_ticks: usize, child: BehaviorArc<B>) -> Self {
+        Self {
+            measure: Measure::Ticks,
+            limit: max_ticks as u64,
+            child,
+            running: None,
+        }
+    }
+}
+
+impl<B: 'static> BehaviorNode<B> for Timeout<B> {
+    fn tick(self: Arc<Self>, blackboard: &mut B) -> NodeResult<B> {
+        let (mark, node) = match (self.running.as_ref(), &self.measure) {
+            (Some((mark, resume)), _) => (*mark, resume.clone()),
+            (None, Measure::Clock(clock)) => (clock.now(blackboard), self.child.clone()),
+            (None, Measure::Ticks) => (0, self.child.clone()),
+        };
+        match node.tick(blackboard) {
+            NodeResult::Running(resume) => {
+                let (mark, elapsed) = match &self.measure {
+                    Measure::Clock(clock) => (mark, clock.now(blackboard).saturating_sub(mark)),
+                    Measure::Ticks => (mark + 1, mark + 1),
+                };
+                if elapsed >= self.limit {
+                    crate::abort_spine(&resume, blackboard);
+                    return NodeResult::Failure;
+                }
+                NodeResult::Running(
+                    Self {
+                        measure: self.measure.clone(),
+                        limit: self.limit,
+                        child: self.child.clone(),
+                        running: Some((mark, resume)),
+                    }
+                    .arc(),
+                )
+            }
+            res => res,
+        }
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Decorator
+    }
+
+    fn estimated_cost(&self) -> u32 {
+        match self.running.as_ref() {
+            Some((_, resume)) => resume.estimated_cost(),
+            None => self.child.estimated_cost(),
+        }
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_u64(self.limit);
+    }
+
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        vec![self.child.clone()]
+    }
+
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        self.running
+            .iter()
+            .map(|(_, resume)| resume.clone())
+            .collect()
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_option(out, self.running.as_ref(), |out, (mark, resume)| {
+            state::write_u64(out, *mark);
+            resume.serialize_state(out)
+        })
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let running = state::read_option(bytes, |bytes| {
+            Ok((
+                state::read_u64(bytes)?,
+                self.child.deserialize_state(bytes)?,
+            ))
+        })?;
+        Ok(Self {
+            measure: self.measure.clone(),
+            limit: self.limit,
+            child: self.child.clone(),
+            running,
+        }
+        .arc())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{testing::SucceedAfter, BehaviorRunner, ManualClock, ProceedClock, TickCounter};
+
+    #[derive(Debug, Default)]
+    struct Scripted {
+        now: u64,
+        proceeds: u64,
+    }
+
+    impl TickCounter for Scripted {
+        fn tick_count(&self) -> u64 {
+            self.proceeds
+        }
+
+        fn set_tick_count(&mut self, ticks: u64) {
+            self.proceeds = ticks;
+        }
+    }
+
+    fn scripted(context: &Scripted) -> u64 {
+        context.now
+    }
+
+    /// Proceeds once at each scripted time, collecting the results
+    fn drive(runner: &mut BehaviorRunner<Scripted>, times: &[u64]) -> Vec<Option<bool>> {
+        let mut context = Scripted::default();
+        times
+            .iter()
+            .map(|&now| {
+                context.now = now;
+                runner.proceed(&mut context)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn wait_measures_from_activation() {
+        let mut runner = BehaviorRunner::from_node(Wait::new(scripted, 10));
+        check!(drive(&mut runner, &[5, 9, 14, 15, 16]) == [None, None, None, Some(true), None]);
+    }
+
+    #[test]
+    fn tick_wait_runs_for_its_ticks() {
+        for ticks in [0, 1, 4] {
+            let mut runner = BehaviorRunner::from_node(Wait::ticks(ticks));
+            let mut results = drive(&mut runner, &vec![0; ticks + 1]);
+            check!(results.pop() == Some(Some(true)));
+            check!(results.iter().all(Option::is_none));
+        }
+    }
+
+    #[test]
+    fn delay_holds_the_child_until_time_passes() {
+        let ms = |ms: u64| ms * 1_000_000;
+        let mut runner = BehaviorRunner::from_node(Delay::with_clock(
+            scripted,
+            Duration::from_millis(50),
+            SucceedAfter(1).arc(),
+        ));
+        check!(
+            drive(&mut runner, &[ms(100), ms(120), ms(149), ms(150), ms(151)])
+                == [None, None, None, None, Some(true)]
+        );
+
+        // The real clock works too, with no delay at all
+        let mut runner =
+            BehaviorRunner::from_node(Delay::new(Duration::ZERO, SucceedAfter(0).arc()));
+        check!(drive(&mut runner, &[0]) == [Some(true)]);
+    }
+
+    #[test]
+    fn delay_too_long_for_the_clock_never_ends() {
+        // A little over u64::MAX nanoseconds, which would wrap to about 0.3s
+        let mut runner = BehaviorRunner::from_node(Delay::with_clock(
+            scripted,
+            Duration::from_secs(18_446_744_074),
+            SucceedAfter(0).arc(),
+        ));
+        check!(drive(&mut runner, &[0, 1_000_000_000]) == [None, None]);
+    }
+
+    #[test]
+    fn cooldown_blocks_until_period_passes() {
+        let mut runner =
+            BehaviorRunner::from_node(Cooldown::new(scripted, 5, SucceedAfter(1).arc()));
+        check!(
+            drive(&mut runner, &[0, 1, 2, 5, 6, 7])
+                == [None, Some(true), Some(false), Some(false), None, Some(true)]
+        );
+    }
+
+    #[test]
+    fn manual_clock_drives_a_cooldown() {
+        let clock = ManualClock::new(100);
+        let mut runner =
+            BehaviorRunner::from_node(Cooldown::new(clock.clone(), 10, SucceedAfter(0).arc()));
+        let mut context = Scripted::default();
+        check!(runner.proceed(&mut context) == Some(true));
+        clock.advance(9);
+        check!(runner.proceed(&mut context) == Some(false));
+        clock.advance(1);
+        check!(runner.proceed(&mut context) == Some(true));
+        clock.set(115);
+        check!(runner.proceed(&mut context) == Some(false));
+    }
+
+    #[test]
+    fn cooldown_counts_from_when_a_slow_child_finishes() {
+        let clock = ManualClock::new(0);
+        let mut runner =
+            BehaviorRunner::from_node(Cooldown::new(clock.clone(), 5, SucceedAfter(2).arc()));
+        let mut context = Scripted::default();
+        let mut at = |now| {
+            clock.set(now);
+            runner.proceed(&mut context)
+        };
+        check!(at(0) == None);
+        check!(at(6) == None);
+        check!(at(8) == Some(true));
+        // Starting at 0 doesn't matter, only finishing at 8 does
+        check!(at(12) == Some(false));
+        check!(at(13) == None);
+    }
+
+    #[test]
+    fn timeout_fails_slow_children() {
+        let mut runner =
+            BehaviorRunner::from_node(Timeout::new(scripted, 3, SucceedAfter(5).arc()));
+        check!(drive(&mut runner, &[10, 11, 12, 13]) == [None, None, None, Some(false)]);
+
+        let mut runner =
+            BehaviorRunner::from_node(Timeout::new(scripted, 3, SucceedAfter(1).arc()));
+        check!(drive(&mut runner, &[10, 20]) == [None, Some(true)]);
+    }
+
+    #[test]
+    fn tick_timeout_counts_its_own_ticks() {
+        let mut runner = BehaviorRunner::from_node(Timeout::ticks(3, SucceedAfter(5).arc()));
+        check!(drive(&mut runner, &[0, 0, 0]) == [None, None, Some(false)]);
+
+        let mut runner = BehaviorRunner::from_node(Timeout::ticks(3, SucceedAfter(2).arc()));
+        check!(drive(&mut runner, &[0, 0, 0]) == [None, None, Some(true)]);
+    }
+
+    #[test]
+    fn timeout_aborts_the_child_it_gives_up_on() {
+        #[derive(Debug)]
+        struct Stuck;
+
+        impl BehaviorNode<Vec<&'static str>> for Stuck {
+            fn tick(
+                self: Arc<Self>,
+                _aborted: &mut Vec<&'static str>,
+            ) -> NodeResult<Vec<&'static str>> {
+                self.running()
+            }
+
+            fn on_abort(&self, aborted: &mut Vec<&'static str>) {
+                aborted.push("stuck");
+            }
+        }
+
+        let mut runner = BehaviorRunner::from_node(Timeout::ticks(2, Stuck.arc()));
+        let mut aborted = vec![];
+        check!(runner.proceed(&mut aborted) == None);
+        check!(aborted.is_empty());
+        check!(runner.proceed(&mut aborted) == Some(false));
+        check!(aborted == ["stuck"]);
+    }
+
+    #[test]
+    fn proceed_clock_counts_proceeds() {
+        let mut runner = BehaviorRunner::from_node(Wait::new(ProceedClock, 2));
+        let mut context = Scripted::default();
+        check!(runner.proceed_counted(&mut context) == None);
+        check!(runner.proceed_counted(&mut context) == None);
+        check!(runner.proceed_counted(&mut context) == Some(true));
+        check!(context.proceeds == 3);
+    }
+}
@@ -0,0 +1,335 @@
+//! Static enumeration of the paths a tree can take
+//!
+//! Useful for checking that every branch of a designer-authored tree can
+//! actually be reached.
+
+use crate::composite::{
+    Failer, Inverter, LimitedRepeated, Repeated, RepeatedUntilFailure, RepeatedUntilSuccess,
+    Selector, Sequence, Succeeder,
+};
+use crate::BehaviorArc;
+use std::sync::Arc;
+
+/// A node's position in a tree, as child indices starting from the root
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(pub Vec<usize>);
+
+impl NodeId {
+    /// The id of the root
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// The id of this node's `index`th child
+    pub fn child(&self, index: usize) -> Self {
+        let mut path = self.0.clone();
+        path.push(index);
+        Self(path)
+    }
+
+    /// Finds the node this id refers to under `root`
+    pub fn resolve<B>(&self, root: &BehaviorArc<B>) -> Option<BehaviorArc<B>> {
+        self.0.iter().try_fold(root.clone(), |node, &index| {
+            node.children().get(index).cloned()
+        })
+    }
+}
+
+impl std::fmt::Display for NodeId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "/")?;
+        for (i, index) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, "/")?;
+            }
+            write!(f, "{index}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The ids of the nodes in `root` that `live` (a running copy of `root`) is
+/// in the middle of, in pre-order
+///
+/// A running node is matched to its child through
+/// [`crate::BehaviorNode::active_origins`], or by position when its active
+/// children line up with the original's children. Where neither works,
+/// matching stops at that node.
+pub(crate) fn active_ids<B>(root: &BehaviorArc<B>, live: &BehaviorArc<B>) -> Vec<NodeId> {
+    let mut ids = vec![];
+    collect_active(root, live, NodeId::root(), &mut ids);
+    ids
+}
+
+fn collect_active<B>(
+    node: &BehaviorArc<B>,
+    live: &BehaviorArc<B>,
+    id: NodeId,
+    ids: &mut Vec<NodeId>,
+) {
+    ids.push(id.clone());
+    if node.is_leaf() {
+        return;
+    }
+
+    let children = node.children();
+    let active = live.active_children();
+    let origins = live.active_origins();
+    let matched: Vec<(usize, BehaviorArc<B>)> =
+        if !origins.is_empty() && origins.len() == active.len() {
+            origins
+                .iter()
+                .zip(active)
+                .filter_map(|(origin, active)| {
+                    let idx = children.iter().position(|c| Arc::ptr_eq(c, origin))?;
+                    Some((idx, active))
+                })
+                .collect()
+        } else if active.len() == children.len() {
+            active.into_iter().enumerate().collect()
+        } else {
+            vec![]
+        };
+
+    for (idx, active) in matched {
+        collect_active(&children[idx], &active, id.child(idx), ids);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum End {
+    Success,
+    Failure,
+    /// Stopped by the loop cap
+    Cut,
+}
+
+#[derive(Debug, Clone)]
+struct Trace {
+    leaves: Vec<NodeId>,
+    end: End,
+}
+
+impl Trace {
+    fn then(&self, next: &Trace) -> Trace {
+        let mut leaves = self.leaves.clone();
+        leaves.extend(next.leaves.iter().cloned());
+        Trace {
+            leaves,
+            end: next.end,
+        }
+    }
+}
+
+/// Lists the distinct sequences of leaves one activation of `root` can tick
+///
+/// Each leaf is assumed to be able to either succeed or fail. [`Sequence`],
+/// [`Selector`], [`Inverter`], [`Succeeder`], [`Failer`] and the repeaters
+/// are understood; any other node with one child passes its child's outcomes
+/// through, and any other node with several children is treated as an opaque
+/// leaf. Loops are unrolled at most `max_loops` times, and a path that hits
+/// the cap ends there.
+pub fn enumerate_paths<B: 'static>(root: &BehaviorArc<B>, max_loops: usize) -> Vec<Vec<NodeId>> {
+    let mut paths: Vec<Vec<NodeId>> = vec![];
+    for trace in traces(root, NodeId::root(), max_loops) {
+        if !paths.contains(&trace.leaves) {
+            paths.push(trace.leaves);
+        }
+    }
+    paths
+}
+
+fn traces<B: 'static>(node: &BehaviorArc<B>, id: NodeId, max_loops: usize) -> Vec<Trace> {
+    let child_traces = |index: usize| {
+        let child = &node.children()[index];
+        traces(child, id.child(index), max_loops)
+    };
+
+    if node.downcast_ref::<Sequence<B>>().is_some() {
+        chain(node.children().len(), End::Success, child_traces)
+    } else if node.downcast_ref::<Selector<B>>().is_some() {
+        chain(node.children().len(), End::Failure, child_traces)
+    } else if node.downcast_ref::<Inverter<B>>().is_some() {
+        let mut traces = child_traces(0);
+        for trace in &mut traces {
+            trace.end = match trace.end {
+                End::Success => End::Failure,
+                End::Failure => End::Success,
+                End::Cut => End::Cut,
+            };
+        }
+        traces
+    } else if let Some(forced) = forced_end(node) {
+        if node.children().is_empty() {
+            return vec![Trace {
+                leaves: vec![],
+                end: forced,
+            }];
+        }
+        let mut traces = child_traces(0);
+        for trace in &mut traces {
+            if trace.end != End::Cut {
+                trace.end = forced;
+            }
+        }
+        traces
+    } else if node.downcast_ref::<Repeated<B>>().is_some() {
+        unroll(child_traces(0), max_loops, |_| None)
+    } else if node.downcast_ref::<RepeatedUntilFailure<B>>().is_some() {
+        unroll(child_traces(0), max_loops, |end| {
+            (end == End::Failure).then_some(End::Success)
+        })
+    } else if node.downcast_ref::<RepeatedUntilSuccess<B>>().is_some() {
+        unroll(child_traces(0), max_loops, |end| {
+            (end == End::Success).then_some(End::Success)
+        })
+    } else if let Some(limited) = node.downcast_ref::<LimitedRepeated<B>>() {
+        let remaining = limited.remaining();
+        let mut traces = unroll(child_traces(0), remaining.min(max_loops), |_| None);
+        if remaining <= max_loops {
+            // Running out of iterations is how this loop finishes
+            for trace in &mut traces {
+                trace.end = End::Success;
+            }
+        }
+        traces
+    } else if node.children().len() == 1 {
+        child_traces(0)
+    } else {
+        [End::Success, End::Failure]
+            .into_iter()
+            .map(|end| Trace {
+                leaves: vec![id.clone()],
+                end,
+            })
+            .collect()
+    }
+}
+
+/// The result [`Succeeder`] and [`Failer`] report regardless of their child
+fn forced_end<B: 'static>(node: &BehaviorArc<B>) -> Option<End> {
+    if node.downcast_ref::<Succeeder<B>>().is_some() {
+        Some(End::Success)
+    } else if node.downcast_ref::<Failer<B>>().is_some() {
+        Some(End::Failure)
+    } else {
+        None
+    }
+}
+
+/// Runs children in order for as long as they end with `keep_going`
+fn chain(
+    count: usize,
+    keep_going: End,
+    mut child_traces: impl FnMut(usize) -> Vec<Trace>,
+) -> Vec<Trace> {
+    let mut partial = vec![Trace {
+        leaves: vec![],
+        end: keep_going,
+    }];
+    for index in 0..count {
+        let next = child_traces(index);
+        partial = partial
+            .into_iter()
+            .flat_map(|trace| {
+                if trace.end == keep_going {
+                    next.iter().map(|step| trace.then(step)).collect()
+                } else {
+                    vec![trace]
+                }
+            })
+            .collect();
+    }
+    partial
+}
+
+/// Repeats `body` up to `times` times, stopping early when `stop` maps an
+/// iteration's end to the loop's result
+fn unroll(body: Vec<Trace>, times: usize, stop: impl Fn(End) -> Option<End>) -> Vec<Trace> {
+    let mut done = vec![];
+    let mut partial = vec![Trace {
+        leaves: vec![],
+        end: End::Cut,
+    }];
+    for _ in 0..times {
+        let mut next = vec![];
+        for trace in partial {
+            for step in &body {
+                let trace = trace.then(step);
+                match (step.end, stop(step.end)) {
+                    (End::Cut, _) => done.push(trace),
+                    (_, Some(end)) => done.push(Trace { end, ..trace }),
+                    (_, None) => next.push(trace),
+                }
+            }
+        }
+        partial = next;
+    }
+    for trace in &mut partial {
+        trace.end = End::Cut;
+    }
+    done.extend(partial);
+    done
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        testing::{AlwaysFail, AlwaysSucceed, SucceedAfter},
+        BehaviorNode,
+    };
+
+    fn ids(paths: &[&[usize]]) -> Vec<NodeId> {
+        paths.iter().map(|path| NodeId(path.to_vec())).collect()
+    }
+
+    #[test]
+    fn selector_of_sequences() {
+        let tree: BehaviorArc<()> = Selector::new([
+            Sequence::new([AlwaysSucceed.arc(), SucceedAfter(1).arc()]).arc(),
+            Sequence::new([AlwaysFail.arc(), AlwaysSucceed.arc()]).arc(),
+        ])
+        .arc();
+
+        let paths = enumerate_paths(&tree, 4);
+        // The first sequence can stop after its first leaf or run both; the
+        // second sequence then runs one or both of its leaves, unless the
+        // first already succeeded
+        check!(paths.len() == 5);
+        check!(paths.contains(&ids(&[&[0, 0], &[0, 1]])));
+        check!(paths.contains(&ids(&[&[0, 0], &[1, 0], &[1, 1]])));
+        check!(paths.contains(&ids(&[&[0, 0], &[0, 1], &[1, 0]])));
+    }
+
+    #[test]
+    fn loops_are_capped() {
+        let forever: BehaviorArc<()> = Repeated::new(AlwaysSucceed.arc()).arc();
+        check!(enumerate_paths(&forever, 3) == vec![ids(&[&[0], &[0], &[0]])]);
+
+        let until: BehaviorArc<()> = RepeatedUntilFailure::new(AlwaysSucceed.arc()).arc();
+        check!(enumerate_paths(&until, 3).len() == 3);
+
+        let limited: BehaviorArc<()> = Sequence::new([
+            LimitedRepeated::new(2, AlwaysSucceed.arc()).arc(),
+            AlwaysFail.arc(),
+        ])
+        .arc();
+        check!(enumerate_paths(&limited, 3) == vec![ids(&[&[0, 0], &[0, 0], &[1]])]);
+    }
+
+    #[test]
+    fn node_ids_resolve() {
+        let leaf = SucceedAfter(3).arc();
+        let tree: BehaviorArc<()> =
+            Selector::new([AlwaysFail.arc(), Sequence::new([leaf.clone()]).arc()]).arc();
+        let id = NodeId::root().child(1).child(0);
+        check!(id.to_string() == "/1/0");
+        check!(id
+            .resolve(&tree)
+            .is_some_and(|node| std::sync::Arc::ptr_eq(&node, &leaf)));
+        check!(NodeId(vec![2]).resolve(&tree).is_none());
+    }
+}
@@ -0,0 +1,111 @@
+//! Comparing two versions of a tree
+//!
+//! Meant for hot reloading: knowing which parts of a tree changed tells a
+//! caller whether its running runners need resetting at all.
+
+use std::hash::Hasher;
+
+use crate::coverage::NodeId;
+use crate::{BehaviorArc, StableHasher};
+
+/// One difference between two versions of a tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeChange {
+    /// The new tree has a node here that the old one didn't
+    Added(NodeId),
+    /// The old tree had a node here that the new one doesn't
+    Removed(NodeId),
+    /// The node here was replaced or reconfigured, so everything under it
+    /// should be treated as changed too
+    Modified(NodeId),
+}
+
+/// Lists the changes that turn `old` into `new`
+///
+/// Nodes are matched up by position, and compared with
+/// [`crate::BehaviorNode::value_eq`]. A node that doesn't override it only
+/// equals itself, so rebuilding such a leaf counts as modifying it.
+pub fn diff<B>(old: &BehaviorArc<B>, new: &BehaviorArc<B>) -> Vec<TreeChange> {
+    let mut changes = vec![];
+    diff_at(old, new, NodeId::root(), &mut changes);
+    changes
+}
+
+fn diff_at<B>(
+    old: &BehaviorArc<B>,
+    new: &BehaviorArc<B>,
+    id: NodeId,
+    changes: &mut Vec<TreeChange>,
+) {
+    if old.value_eq(new.as_ref()) {
+        return;
+    }
+    if old.is_leaf() || new.is_leaf() || own_hash(old) != own_hash(new) {
+        changes.push(TreeChange::Modified(id));
+        return;
+    }
+    let found = changes.len();
+    let (old_children, new_children) = (old.children(), new.children());
+    for index in 0..old_children.len().max(new_children.len()) {
+        match (old_children.get(index), new_children.get(index)) {
+            (Some(old), Some(new)) => diff_at(old, new, id.child(index), changes),
+            (Some(_), None) => changes.push(TreeChange::Removed(id.child(index))),
+            (None, Some(_)) => changes.push(TreeChange::Added(id.child(index))),
+            (None, None) => unreachable!(),
+        }
+    }
+    // The children all match, so whatever differs is something the node
+    // doesn't hash (a closure, say)
+    if changes.len() == found {
+        changes.push(TreeChange::Modified(id));
+    }
+}
+
+/// Hashes a node's name and parameters, but not its children
+fn own_hash<B>(node: &BehaviorArc<B>) -> u64 {
+    let mut hasher = StableHasher::default();
+    hasher.write(node.name().as_bytes());
+    node.hash_params(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::{Inverter, Selector, Sequence},
+        testing::{AlwaysFail, AlwaysSucceed, SucceedAfter},
+        BehaviorNode,
+    };
+
+    fn tree(wait: u32) -> BehaviorArc<()> {
+        Selector::new([
+            AlwaysFail.arc(),
+            Sequence::new([
+                AlwaysSucceed.arc(),
+                Inverter::new(SucceedAfter(wait).arc()).arc(),
+            ])
+            .arc(),
+        ])
+        .arc()
+    }
+
+    #[test]
+    fn one_changed_leaf_is_one_modification() {
+        check!(diff(&tree(2), &tree(2)).is_empty());
+        check!(diff(&tree(2), &tree(3)) == [TreeChange::Modified(NodeId(vec![1, 1, 0]))]);
+    }
+
+    #[test]
+    fn children_are_added_removed_and_replaced() {
+        let short: BehaviorArc<()> = Sequence::new([AlwaysSucceed.arc()]).arc();
+        let long: BehaviorArc<()> = Sequence::new([AlwaysSucceed.arc(), AlwaysFail.arc()]).arc();
+        check!(diff(&short, &long) == [TreeChange::Added(NodeId(vec![1]))]);
+        check!(diff(&long, &short) == [TreeChange::Removed(NodeId(vec![1]))]);
+
+        let swapped: BehaviorArc<()> = Selector::new([AlwaysSucceed.arc()]).arc();
+        check!(diff(&short, &swapped) == [TreeChange::Modified(NodeId::root())]);
+    }
+}
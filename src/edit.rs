@@ -0,0 +1,89 @@
+//! Building modified copies of trees
+//!
+//! Trees are immutable, so these return a new node that shares every
+//! unchanged child with the original.
+
+use crate::BehaviorArc;
+
+/// Inserts `child` at `index` in the children of `root`
+///
+/// Returns `None` if `index` is past the end of the children, or if `root`
+/// can't be rebuilt with the extra child.
+pub fn insert_child<B>(
+    root: &BehaviorArc<B>,
+    index: usize,
+    child: BehaviorArc<B>,
+) -> Option<BehaviorArc<B>> {
+    let mut children = root.children();
+    if index > children.len() {
+        return None;
+    }
+    children.insert(index, child);
+    root.with_children(children)
+}
+
+/// Removes the child at `index` from the children of `root`
+///
+/// Returns `None` if there is no child at `index`, or if `root` can't be
+/// rebuilt without it.
+pub fn remove_child<B>(root: &BehaviorArc<B>, index: usize) -> Option<BehaviorArc<B>> {
+    let mut children = root.children();
+    if index >= children.len() {
+        return None;
+    }
+    children.remove(index);
+    root.with_children(children)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use assert2::{check, let_assert};
+
+    use super::*;
+    use crate::{
+        composite::{Inverter, Sequence},
+        testing::{AlwaysFail, AlwaysSucceed, SucceedAfter},
+        BehaviorNode, BehaviorRunner,
+    };
+
+    #[test]
+    fn insert_and_remove_children() {
+        let first = SucceedAfter(1).arc();
+        let last = AlwaysSucceed.arc();
+        let root: BehaviorArc<()> = Sequence::new([first.clone(), last.clone()]).arc();
+
+        let_assert!(Some(inserted) = insert_child(&root, 1, AlwaysFail.arc()));
+        let children = inserted.children();
+        check!(children.len() == 3);
+        check!(Arc::ptr_eq(&children[0], &first));
+        check!(children[1].name() == "AlwaysFail");
+        check!(Arc::ptr_eq(&children[2], &last));
+        check!(BehaviorRunner::new(inserted.clone()).proceed(&mut ()) == None);
+
+        let_assert!(Some(removed) = remove_child(&inserted, 0));
+        let children = removed.children();
+        check!(children.len() == 2);
+        check!(children[0].name() == "AlwaysFail");
+        check!(Arc::ptr_eq(&children[1], &last));
+        check!(!BehaviorRunner::new(removed).run_once(&mut ()));
+
+        // The original is untouched
+        check!(root.children().len() == 2);
+    }
+
+    #[test]
+    fn edits_are_bounds_checked() {
+        let root: BehaviorArc<()> = Sequence::new([AlwaysSucceed.arc()]).arc();
+        check!(insert_child(&root, 1, AlwaysFail.arc()).is_some());
+        check!(insert_child(&root, 2, AlwaysFail.arc()).is_none());
+        check!(remove_child(&root, 1).is_none());
+
+        // Decorators take exactly one child, and leaves none
+        let inverter: BehaviorArc<()> = Inverter::new(AlwaysSucceed.arc()).arc();
+        check!(insert_child(&inverter, 0, AlwaysFail.arc()).is_none());
+        check!(remove_child(&inverter, 0).is_none());
+        check!(insert_child::<()>(&AlwaysSucceed.arc(), 0, AlwaysFail.arc()).is_none());
+    }
+}
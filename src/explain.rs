@@ -0,0 +1,41 @@
+//! Collects the explanations nodes give while a runner with
+//! [`crate::BehaviorRunner::with_explanations`] is ticking
+
+use std::cell::RefCell;
+
+use crate::{BehaviorArc, NodeResult};
+
+thread_local! {
+    /// The lines gathered during the current proceed, if anyone is listening
+    static SINK: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, returning whatever was noted meanwhile
+pub(crate) fn collect<R>(f: impl FnOnce() -> R) -> (R, Vec<String>) {
+    let outer = SINK.with(|sink| sink.borrow_mut().replace(vec![]));
+    let res = f();
+    let lines = SINK.with(|sink| std::mem::replace(&mut *sink.borrow_mut(), outer));
+    (res, lines.unwrap_or_default())
+}
+
+/// Adds a line, if explanations are being collected
+pub(crate) fn note(line: impl FnOnce(&mut String)) {
+    SINK.with(|sink| {
+        if let Some(lines) = sink.borrow_mut().as_mut() {
+            let mut out = String::new();
+            line(&mut out);
+            if !out.is_empty() {
+                lines.push(out);
+            }
+        }
+    });
+}
+
+/// Ticks `child` and lets it explain the result once it has finished
+pub(crate) fn tick_child<B>(child: BehaviorArc<B>, blackboard: &mut B) -> NodeResult<B> {
+    let res = child.clone().tick(blackboard);
+    if !matches!(res, NodeResult::Running(_)) {
+        note(|out| child.explain(&res, out));
+    }
+    res
+}
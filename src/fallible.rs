@@ -0,0 +1,64 @@
+//! Carries errors raised by [`crate::composite::TryAction`] out to
+//! [`crate::BehaviorRunner::try_proceed`]
+//!
+//! The error travels beside the tick rather than through [`crate::NodeResult`],
+//! so nodes that never error keep the usual three results. Unlike a
+//! [`crate::composite::Bubble`], which fails its way up through the
+//! blackboard, an error has to stop composites that would carry on after a
+//! failure, and has to reach the runner whatever the blackboard is. So
+//! composites tick their children through [`tick_child`], which hands back
+//! `None` once an error is out and nothing else may run.
+
+use std::any::Any;
+use std::cell::RefCell;
+
+use crate::{explain, BehaviorArc, NodeResult};
+
+/// An error of any type, along with the name of that type
+pub(crate) struct Raised {
+    pub(crate) error: Box<dyn Any + Send>,
+    pub(crate) type_name: &'static str,
+}
+
+thread_local! {
+    /// The first error raised during the current proceed, if anyone is
+    /// listening
+    static SLOT: RefCell<Option<Option<Raised>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, returning the first error raised meanwhile
+pub(crate) fn collect<R>(f: impl FnOnce() -> R) -> (R, Option<Raised>) {
+    let outer = SLOT.with(|slot| slot.borrow_mut().replace(None));
+    let res = f();
+    let raised = SLOT.with(|slot| std::mem::replace(&mut *slot.borrow_mut(), outer));
+    (res, raised.flatten())
+}
+
+/// Records `error`, if errors are being collected and none was yet
+pub(crate) fn raise<E: Send + 'static>(error: E) {
+    SLOT.with(|slot| {
+        if let Some(raised) = slot.borrow_mut().as_mut() {
+            raised.get_or_insert_with(|| Raised {
+                error: Box::new(error),
+                type_name: std::any::type_name::<E>(),
+            });
+        }
+    });
+}
+
+/// Whether an error has been raised during the current proceed
+pub(crate) fn raised() -> bool {
+    SLOT.with(|slot| matches!(*slot.borrow(), Some(Some(_))))
+}
+
+/// Ticks a composite's `child`, or returns `None` if it finished after an
+/// error was raised
+///
+/// The composite should fail straight away on `None`, without ticking
+/// anything else.
+pub(crate) fn tick_child<B>(child: BehaviorArc<B>, blackboard: &mut B) -> Option<NodeResult<B>> {
+    match explain::tick_child(child, blackboard) {
+        NodeResult::Success | NodeResult::Failure if raised() => None,
+        res => Some(res),
+    }
+}
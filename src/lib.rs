@@ -1,8 +1,32 @@
 //! Create a simple behavior tree implementation
 
+pub mod access;
+pub mod analysis;
+#[cfg(feature = "async")]
+pub mod asynchronous;
+#[cfg(feature = "bevy")]
+pub mod bevy;
+pub mod builder;
+pub mod clock;
+pub mod compiled;
 pub mod composite;
+pub mod coverage;
+pub mod diff;
+pub mod edit;
+mod explain;
+mod fallible;
+pub mod params;
+pub mod rng;
+pub mod shape;
+pub mod state;
+pub mod testing;
+pub mod visualize;
 
+pub use clock::{ManualClock, ProceedClock, TickClock, TickCounter, WallClock};
+
+use std::hash::Hasher;
 use std::sync::Arc;
+use std::time::Instant;
 
 #[derive(Debug)]
 pub enum NodeResult<B> {
@@ -16,6 +40,68 @@ pub enum NodeResult<B> {
     Failure,
 }
 
+impl<B> std::fmt::Display for NodeResult<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeResult::Running(_) => f.write_str("running"),
+            NodeResult::Success => f.write_str("success"),
+            NodeResult::Failure => f.write_str("failure"),
+        }
+    }
+}
+
+/// The status of a [`NodeResult`] without the running node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeResultKind {
+    Running,
+    Success,
+    Failure,
+}
+
+impl<B> NodeResult<B> {
+    pub fn kind(&self) -> NodeResultKind {
+        match self {
+            NodeResult::Running(_) => NodeResultKind::Running,
+            NodeResult::Success => NodeResultKind::Success,
+            NodeResult::Failure => NodeResultKind::Failure,
+        }
+    }
+
+    /// Bridges a fallible action: `Ok` succeeds and `Err` fails
+    ///
+    /// The error itself is dropped, so log or store it first if it matters.
+    pub fn from_result<E>(result: Result<(), E>) -> Self {
+        match result {
+            Ok(()) => NodeResult::Success,
+            Err(_) => NodeResult::Failure,
+        }
+    }
+}
+
+/// The broad family a node belongs to, see [`BehaviorNode::kind`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NodeKind {
+    /// Ticks children in order until one fails
+    Sequence,
+    /// Ticks children in order until one succeeds
+    Selector,
+    /// Ticks all of its children on every tick
+    Parallel,
+    /// Flips its child's result
+    Inverter,
+    /// Succeeds whatever its child does
+    Succeeder,
+    /// Fails whatever its child does
+    Failer,
+    /// Ticks its child more than once
+    Repeater,
+    /// Any other node wrapping children
+    Decorator,
+    /// A node that does the actual work
+    Leaf,
+}
+
 pub type BehaviorArc<B> = Arc<dyn BehaviorNode<B>>;
 
 // This is our main "behavior tree" trait.
@@ -24,6 +110,228 @@ pub type BehaviorArc<B> = Arc<dyn BehaviorNode<B>>;
 pub trait BehaviorNode<B>: std::fmt::Debug + Send + Sync {
     fn tick(self: Arc<Self>, context: &mut B) -> NodeResult<B>;
 
+    /// A short, human-readable name for this node
+    ///
+    /// Defaults to the type name without its module path or generics.
+    fn name(&self) -> &'static str {
+        let full = std::any::type_name::<Self>();
+        let base = &full[..full.find('<').unwrap_or(full.len())];
+        &base[base.rfind("::").map_or(0, |i| i + 2)..]
+    }
+
+    /// Which family of node this is
+    ///
+    /// Cheaper than downcasting when a tool only needs to tell a sequence
+    /// from a selector. Running copies of a node report the same kind as the
+    /// node they came from.
+    fn kind(&self) -> NodeKind {
+        NodeKind::Leaf
+    }
+
+    /// A rough estimate of how expensive ticking this node is
+    ///
+    /// Used by [`BehaviorRunner::proceed_with_budget`]. Expensive leaves
+    /// should override this; decorators and composites report the cost of
+    /// the child they would tick next.
+    fn estimated_cost(&self) -> u32 {
+        1
+    }
+
+    /// The children of this node, in the order it would tick them
+    ///
+    /// Leaves have none.
+    fn children(&self) -> Vec<BehaviorArc<B>> {
+        Vec::new()
+    }
+
+    /// A label for the child at `index` in [`BehaviorNode::children`]
+    ///
+    /// Shown next to the child by the [`visualize`] helpers.
+    fn child_label(&self, index: usize) -> Option<&'static str> {
+        let _ = index;
+        None
+    }
+
+    /// Whether tools should treat this node as a leaf
+    ///
+    /// A decorator can return `true` to be drawn as a single opaque node even
+    /// though it has a child.
+    fn is_leaf(&self) -> bool {
+        self.children().is_empty()
+    }
+
+    /// The data this node and its descendants read and write
+    ///
+    /// Only consulted by outside schedulers (see [`access`]). Leaves that
+    /// touch blackboard data should declare it; the default unions the
+    /// children's access, which is what composites want.
+    fn access(&self) -> access::Access {
+        let mut access = access::Access::default();
+        for child in self.children() {
+            access.union(&child.access());
+        }
+        access
+    }
+
+    /// Describes why this node just finished with `result`
+    ///
+    /// Only called while a runner built with
+    /// [`BehaviorRunner::with_explanations`] is proceeding, and only for
+    /// children of composites that pass explanations along (the sequences
+    /// and selectors do). Write nothing to stay out of the explanation.
+    fn explain(&self, result: &NodeResult<B>, out: &mut String) {
+        let _ = (result, out);
+    }
+
+    /// Whether this node keeps progress inside itself rather than in the
+    /// nodes it returns through [`NodeResult::Running`]
+    ///
+    /// Such a node (one with atomic counters, say) must not appear twice in
+    /// a tree through the same `Arc`, since both places would share that
+    /// progress. Nodes opt in by returning `true`, and
+    /// [`composite::Sequence::new_checked`] and
+    /// [`composite::Selector::new_checked`] then reject the duplicate.
+    fn is_stateful(&self) -> bool {
+        false
+    }
+
+    /// A guess at how likely this node is to succeed if activated now
+    ///
+    /// Purely advisory, for planners comparing branches; ticking never
+    /// consults it. Leaves that know better than a coin flip should say so.
+    /// Sequences multiply their children's chances, selectors take the
+    /// chance that not every child fails, and disabled children (see
+    /// [`BehaviorNode::is_enabled`]) are left out of both.
+    fn success_probability(&self, blackboard: &B) -> f32 {
+        let _ = blackboard;
+        0.5
+    }
+
+    /// Whether sequences and selectors should tick this node at all
+    ///
+    /// A disabled child is skipped as if it weren't in the list: it neither
+    /// succeeds nor fails. It is checked each time the child is reached, so
+    /// a child that is already running carries on. See
+    /// [`composite::Gated`].
+    fn is_enabled(&self, blackboard: &B) -> bool {
+        let _ = blackboard;
+        true
+    }
+
+    /// Whether ticking this node against the same blackboard always goes
+    /// the same way
+    ///
+    /// Nodes drawing from randomness that isn't derived from the blackboard
+    /// (a free-running seed, say) return `false`. The default is `true` only
+    /// if every child is deterministic, so the answer for a whole tree comes
+    /// from its root. [`testing::replay`] mentions it when a replay diverges.
+    fn is_deterministic(&self) -> bool {
+        self.children().iter().all(|child| child.is_deterministic())
+    }
+
+    /// The children a running node is in the middle of ticking
+    ///
+    /// Following these from a running node down walks the active spine of
+    /// the tree (see [`BehaviorRunner::active_path`]). Only meaningful on a
+    /// node that was returned through [`NodeResult::Running`].
+    fn active_children(&self) -> Vec<BehaviorArc<B>> {
+        Vec::new()
+    }
+
+    /// The children of the original node that each of
+    /// [`BehaviorNode::active_children`] was started from
+    ///
+    /// Resume nodes, which only hold the one child they're waiting on,
+    /// override this so tools like [`visualize::debug_runner`] can find that
+    /// child in the tree as it was built. Nodes whose active children line up
+    /// with their [`BehaviorNode::children`] can leave it empty.
+    fn active_origins(&self) -> Vec<BehaviorArc<B>> {
+        Vec::new()
+    }
+
+    /// Writes the progress of this running node (not its configuration) to
+    /// `out`
+    ///
+    /// Call this on a node returned through [`NodeResult::Running`]; together
+    /// with [`BehaviorNode::deserialize_state`] it lets a running tree be
+    /// captured byte for byte, e.g. for lockstep networking. Nodes holding a
+    /// running child write that child's state after their own. Nodes that
+    /// can't capture their progress (closures, iterators) return
+    /// [`state::StateError::Unsupported`], which is the default.
+    fn serialize_state(&self, _out: &mut Vec<u8>) -> Result<(), state::StateError> {
+        Err(state::StateError::Unsupported(self.name()))
+    }
+
+    /// Rebuilds a running node from state written by
+    /// [`BehaviorNode::serialize_state`], using `self` as the template
+    ///
+    /// `self` is the node the running one was started from, as it was built.
+    /// Nodes that hand back a child's running node as their own, like
+    /// [`composite::IfElse`], can't tell which child it came from and return
+    /// [`state::StateError::Unsupported`], which is the default.
+    fn deserialize_state(&self, _bytes: &mut &[u8]) -> Result<BehaviorArc<B>, state::StateError> {
+        Err(state::StateError::Unsupported(self.name()))
+    }
+
+    /// Called when a running node is abandoned before it finished
+    ///
+    /// See [`BehaviorRunner::abort`].
+    fn on_abort(&self, _context: &mut B) {}
+
+    /// Builds a fresh copy of this node with `children` in place of its own
+    ///
+    /// Returns `None` for leaves, and for nodes that can't take that many
+    /// children (a decorator only takes one).
+    fn with_children(&self, _children: Vec<BehaviorArc<B>>) -> Option<BehaviorArc<B>> {
+        None
+    }
+
+    /// Feeds the parameters of this node (but not its children) into `state`
+    ///
+    /// Used by [`tree_hash`]. Nodes with parameters, like the limit of a
+    /// [`composite::LimitedRepeated`], should override this.
+    fn hash_params(&self, _state: &mut dyn Hasher) {}
+
+    /// The parameters of this node (but not its children), in the form
+    /// [`shape::TreeShape`] saves them
+    ///
+    /// Should cover whatever [`BehaviorNode::hash_params`] does, for nodes
+    /// that can be rebuilt from a [`shape::ShapeRegistry`].
+    fn params(&self) -> params::NodeParams {
+        params::NodeParams::new()
+    }
+
+    /// This node as [`std::any::Any`], so it can be downcast
+    ///
+    /// Nodes that want to be found by their concrete type return
+    /// `Some(self)`; the default `None` means `downcast_ref` on a
+    /// `dyn BehaviorNode` never matches this node.
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        None
+    }
+
+    /// Whether `other` is a node of the same type with the same parameters
+    ///
+    /// Nodes compare by identity unless they override this. Leaves should
+    /// compare their fields (downcasting `other`, which needs
+    /// [`BehaviorNode::as_any`]), and composites their children with
+    /// [`children_value_eq`].
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        std::ptr::addr_eq(self as *const Self, other as *const dyn BehaviorNode<B>)
+    }
+
+    /// Reports that this node is still running, unchanged
+    ///
+    /// Hands the existing `Arc` back instead of allocating a new node, so use
+    /// this whenever a leaf has no progress to record. Only build (and
+    /// [`BehaviorNode::arc`]) a new node when some field actually changed.
+    fn running(self: Arc<Self>) -> NodeResult<B>
+    where
+        Self: Sized + 'static,
+    {
+        NodeResult::Running(self)
+    }
+
     fn arc(self) -> BehaviorArc<B>
     where
         Self: Sized + Send + Sync + 'static,
@@ -32,11 +340,202 @@ pub trait BehaviorNode<B>: std::fmt::Debug + Send + Sync {
     }
 }
 
-#[derive(Debug)]
+impl<B> dyn BehaviorNode<B> + '_ {
+    /// Returns the node as a `T` if that is its concrete type
+    ///
+    /// Always `None` for nodes that don't override [`BehaviorNode::as_any`].
+    pub fn downcast_ref<T: BehaviorNode<B> + 'static>(&self) -> Option<&T> {
+        self.as_any()?.downcast_ref()
+    }
+}
+
+/// Compares two child lists pairwise with [`BehaviorNode::value_eq`]
+pub fn children_value_eq<B>(a: &[BehaviorArc<B>], b: &[BehaviorArc<B>]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.value_eq(b.as_ref()))
+}
+
+/// A content hash of a tree
+///
+/// Covers every node's name, parameters and children, so structurally equal
+/// trees hash the same from run to run.
+pub fn tree_hash<B>(root: &BehaviorArc<B>) -> u64 {
+    let mut hasher = StableHasher::default();
+    hasher.write(root.name().as_bytes());
+    root.hash_params(&mut hasher);
+    for child in root.children() {
+        hasher.write_u64(tree_hash(&child));
+    }
+    hasher.finish()
+}
+
+// FNV-1a, which unlike the std hashers is stable across Rust versions
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x100_0000_01b3);
+        }
+    }
+}
+
+/// Displays the [`BehaviorNode::name`] of a node
+pub struct NodeName<'a, B>(pub &'a dyn BehaviorNode<B>);
+
+impl<B> std::fmt::Display for NodeName<'_, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.name())
+    }
+}
+
+type CompleteCallback<B> = Box<dyn Fn(&mut B, bool) + Send + Sync>;
+type TransitionCallback<B> = Box<dyn FnMut(&dyn BehaviorNode<B>, NodeResultKind) + Send + Sync>;
+
 /// Takes care of executing a behavior tree
 pub struct BehaviorRunner<B> {
     tree: BehaviorArc<B>,
     current_tick: Option<BehaviorArc<B>>,
+    on_complete: Option<CompleteCallback<B>>,
+    on_transition: Option<TransitionCallback<B>>,
+    trace: Option<Vec<TraceEntry>>,
+    forced: Option<bool>,
+    /// Lines explained so far this activation, and the full explanation of
+    /// the last activation to finish
+    explanations: Option<(Vec<String>, String)>,
+    /// The runaway threshold, and how many proceeds in a row have ended
+    /// with the tree still running
+    runaway_guard: Option<(u64, u64)>,
+    last_result: Option<bool>,
+    hold_finished: bool,
+}
+
+/// Calls [`BehaviorNode::on_abort`] on a running node and everything on its
+/// active spine, outermost first
+pub(crate) fn abort_spine<B>(node: &BehaviorArc<B>, context: &mut B) {
+    let mut pending = vec![node.clone()];
+    while let Some(node) = pending.pop() {
+        pending.extend(node.active_children().into_iter().rev());
+        node.on_abort(context);
+    }
+}
+
+/// What one proceed did, as recorded by [`BehaviorRunner::with_trace`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// How many proceeds came before this one
+    pub proceed: u64,
+    /// Where the tree was paused afterwards (see
+    /// [`BehaviorRunner::active_node_ids`])
+    pub active: Vec<coverage::NodeId>,
+    /// What the proceed returned
+    pub result: Option<bool>,
+}
+
+/// Proceeds a runner once per item, see [`BehaviorRunner::ticks`]
+pub struct TickIter<'a, B> {
+    runner: &'a mut BehaviorRunner<B>,
+    context: &'a mut B,
+    finished: bool,
+}
+
+impl<B> std::fmt::Debug for TickIter<'_, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TickIter")
+            .field("runner", &self.runner)
+            .field("finished", &self.finished)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> Iterator for TickIter<'_, B> {
+    type Item = Option<bool>;
+
+    fn next(&mut self) -> Option<Option<bool>> {
+        if self.finished {
+            return None;
+        }
+        let res = self.runner.proceed(self.context);
+        self.finished = res.is_some();
+        Some(res)
+    }
+}
+
+impl<B> std::iter::FusedIterator for TickIter<'_, B> {}
+
+/// A tree kept running for longer than its runaway guard allows, see
+/// [`BehaviorRunner::with_runaway_guard`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunawayError {
+    /// How many proceeds in a row have left the tree running
+    pub running_ticks: u64,
+}
+
+impl std::fmt::Display for RunawayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "tree still running after {} proceeds in a row",
+            self.running_ticks
+        )
+    }
+}
+
+impl std::error::Error for RunawayError {}
+
+/// The error [`BehaviorRunner::try_proceed`] stopped the tree for
+#[derive(Debug)]
+pub enum TryProceedError<E> {
+    /// A [`composite::TryAction`] raised the `E` that was asked for
+    Raised(E),
+    /// A [`composite::TryAction`] raised an error of some other type, which
+    /// can be downcast from `error`
+    OtherType {
+        type_name: &'static str,
+        error: Box<dyn std::any::Any + Send>,
+    },
+}
+
+impl<E> TryProceedError<E> {
+    /// The raised error, if it was an `E`
+    pub fn raised(self) -> Option<E> {
+        match self {
+            TryProceedError::Raised(error) => Some(error),
+            TryProceedError::OtherType { .. } => None,
+        }
+    }
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for TryProceedError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TryProceedError::Raised(error) => error.fmt(f),
+            TryProceedError::OtherType { type_name, .. } => {
+                write!(f, "an action raised an error of type {type_name}")
+            }
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for TryProceedError<E> {}
+
+impl<B> std::fmt::Debug for BehaviorRunner<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BehaviorRunner")
+            .field("tree", &self.tree)
+            .field("current_tick", &self.current_tick)
+            .finish_non_exhaustive()
+    }
 }
 
 impl<B> BehaviorRunner<B> {
@@ -44,6 +543,14 @@ impl<B> BehaviorRunner<B> {
         Self {
             tree,
             current_tick: None,
+            on_complete: None,
+            on_transition: None,
+            trace: None,
+            forced: None,
+            explanations: None,
+            runaway_guard: None,
+            last_result: None,
+            hold_finished: false,
         }
     }
 
@@ -51,10 +558,96 @@ impl<B> BehaviorRunner<B> {
     where
         N: BehaviorNode<B> + 'static,
     {
-        Self {
-            tree: Arc::new(node),
-            current_tick: None,
-        }
+        Self::new(node.arc())
+    }
+
+    /// Calls `callback` whenever the whole tree reaches a terminal state
+    ///
+    /// The callback receives the context and whether the tree succeeded.
+    /// Trees that never complete (like [`composite::Repeated`]) never fire it.
+    pub fn on_complete<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&mut B, bool) + Send + Sync + 'static,
+    {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Calls `callback` whenever the node the runner ticks starts running or
+    /// finishes
+    ///
+    /// The callback receives the ticked node (the root, or the node it is
+    /// paused at) and its new status. Proceeds that resume a node which
+    /// stays running don't fire it, and neither do forced results.
+    pub fn on_transition<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(&dyn BehaviorNode<B>, NodeResultKind) + Send + Sync + 'static,
+    {
+        self.on_transition = Some(Box::new(callback));
+        self
+    }
+
+    /// Records a [`TraceEntry`] for every proceed from now on
+    ///
+    /// See [`testing::replay`] for checking a trace reproduces.
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(vec![]);
+        self
+    }
+
+    /// Collects [`BehaviorNode::explain`] lines while proceeding
+    ///
+    /// See [`BehaviorRunner::explanation`].
+    pub fn with_explanations(mut self) -> Self {
+        self.explanations = Some((vec![], String::new()));
+        self
+    }
+
+    /// Why the last activation to finish ended the way it did, one line per
+    /// explaining node in the order they finished
+    ///
+    /// `None` if explanations are off. Lines from every proceed of the
+    /// activation are included, not just the last one.
+    pub fn explanation(&self) -> Option<&str> {
+        self.explanations.as_ref().map(|(_, last)| last.as_str())
+    }
+
+    /// Flags trees that keep running for `max_running_ticks` proceeds in a
+    /// row, see [`BehaviorRunner::proceed_checked`]
+    ///
+    /// Meant to catch accidental infinite loops; a tree that is supposed to
+    /// run for a long time just needs a high threshold. The count starts
+    /// over whenever the tree finishes.
+    pub fn with_runaway_guard(mut self, max_running_ticks: u64) -> Self {
+        self.runaway_guard = Some((max_running_ticks, 0));
+        self
+    }
+
+    /// Stops at the first result instead of starting the tree over
+    ///
+    /// By default, proceeding a finished tree ticks it again from the root,
+    /// which is what looping callers rely on. With this, once the tree
+    /// finishes every proceed returns that result without ticking anything
+    /// (and without firing callbacks again) until [`BehaviorRunner::reset`].
+    pub fn hold_finished(mut self) -> Self {
+        self.hold_finished = true;
+        self
+    }
+
+    /// The result of the last activation to finish, if any has since the
+    /// runner was built or reset
+    pub fn last_result(&self) -> Option<bool> {
+        self.last_result
+    }
+
+    /// Whether the tree has finished and nothing has started since
+    pub fn is_finished(&self) -> bool {
+        self.last_result.is_some() && !self.is_running()
+    }
+
+    /// The trace recorded so far, if tracing is on
+    pub fn trace(&self) -> Option<&[TraceEntry]> {
+        self.trace.as_deref()
     }
 
     pub fn into_inner(self) -> BehaviorArc<B> {
@@ -65,8 +658,124 @@ impl<B> BehaviorRunner<B> {
         self.current_tick.is_some()
     }
 
-    fn tick_node(&mut self, node: &Arc<dyn BehaviorNode<B>>, context: &mut B) -> Option<bool> {
-        match node.clone().tick(context) {
+    /// The node the tree is paused at, if it is running
+    ///
+    /// Unlike [`BehaviorRunner::peek_next`], this never falls back to the
+    /// root.
+    pub fn current_node(&self) -> Option<&BehaviorArc<B>> {
+        self.current_tick.as_ref()
+    }
+
+    /// Every node on the active spine, starting at the running node
+    ///
+    /// Empty when the tree isn't running.
+    pub fn active_path(&self) -> Vec<BehaviorArc<B>> {
+        let mut path = vec![];
+        let mut pending = self.current_tick.iter().cloned().collect::<Vec<_>>();
+        while let Some(node) = pending.pop() {
+            pending.extend(node.active_children().into_iter().rev());
+            path.push(node);
+        }
+        path
+    }
+
+    /// The [`coverage::NodeId`]s of the nodes on the active spine, in
+    /// pre-order starting at the root
+    ///
+    /// Unlike [`BehaviorRunner::active_path`], these name nodes in the tree
+    /// as it was built, so they stay comparable between runs.
+    pub fn active_node_ids(&self) -> Vec<coverage::NodeId> {
+        match self.current_tick.as_ref() {
+            Some(live) => coverage::active_ids(&self.tree, live),
+            None => vec![],
+        }
+    }
+
+    /// The node the next proceed will tick: the running node, or the root
+    /// when the tree isn't running
+    ///
+    /// `None` when a forced result is pending, since that proceed ticks
+    /// nothing (see [`BehaviorRunner::force_success`]).
+    pub fn peek_next(&self) -> Option<&BehaviorArc<B>> {
+        if self.forced.is_some() {
+            None
+        } else {
+            Some(self.current_tick.as_ref().unwrap_or(&self.tree))
+        }
+    }
+
+    /// Abandons the running tree, so the next proceed starts from the root
+    ///
+    /// Every node on the [`BehaviorRunner::active_path`] gets
+    /// [`BehaviorNode::on_abort`] called, outermost first.
+    pub fn abort(&mut self, context: &mut B) {
+        if let Some(node) = self.current_tick.take() {
+            abort_spine(&node, context);
+        }
+    }
+
+    /// Drops any progress, so the next proceed starts from the root
+    ///
+    /// Unlike [`BehaviorRunner::abort`], nothing is told about being dropped,
+    /// which is why no context is needed. The last result, a pending forced
+    /// result and the runaway guard's count are cleared too.
+    pub fn reset(&mut self) {
+        self.current_tick = None;
+        self.forced = None;
+        self.last_result = None;
+        if let Some((current, _)) = self.explanations.as_mut() {
+            current.clear();
+        }
+        if let Some((_, running_ticks)) = self.runaway_guard.as_mut() {
+            *running_ticks = 0;
+        }
+    }
+
+    /// Aborts the running tree and makes the next proceed succeed
+    ///
+    /// The override is one-shot: the proceed after that starts the tree from
+    /// the root as usual.
+    pub fn force_success(&mut self, context: &mut B) {
+        self.force(context, true);
+    }
+
+    /// Aborts the running tree and makes the next proceed fail
+    ///
+    /// One-shot, like [`BehaviorRunner::force_success`].
+    pub fn force_failure(&mut self, context: &mut B) {
+        self.force(context, false);
+    }
+
+    fn force(&mut self, context: &mut B, success: bool) {
+        self.abort(context);
+        self.forced = Some(success);
+    }
+
+    fn step(&mut self, context: &mut B) -> Option<bool> {
+        if let Some(forced) = self.forced.take() {
+            Some(forced)
+        } else if let Some(bp) = self.current_tick.take() {
+            self.tick_node(&bp, true, context)
+        } else {
+            let node = self.tree.clone();
+            self.tick_node(&node, false, context)
+        }
+    }
+
+    fn tick_node(
+        &mut self,
+        node: &Arc<dyn BehaviorNode<B>>,
+        resumed: bool,
+        context: &mut B,
+    ) -> Option<bool> {
+        let res = node.clone().tick(context);
+        let status = res.kind();
+        if let Some(callback) = self.on_transition.as_mut() {
+            if !(resumed && status == NodeResultKind::Running) {
+                callback(node.as_ref(), status);
+            }
+        }
+        match res {
             NodeResult::Running(nbp) => {
                 self.current_tick = Some(nbp);
                 None
@@ -79,11 +788,854 @@ impl<B> BehaviorRunner<B> {
     // returns None -> still running
     // return Some(p) -> p true success, p false failure
     pub fn proceed(&mut self, context: &mut B) -> Option<bool> {
-        if let Some(bp) = self.current_tick.take() {
-            self.tick_node(&bp, context)
+        if self.hold_finished && self.forced.is_none() && self.is_finished() {
+            return self.last_result;
+        }
+        let res = if self.explanations.is_some() {
+            let (res, lines) = explain::collect(|| self.step(context));
+            if let Some((current, last)) = self.explanations.as_mut() {
+                current.extend(lines);
+                if res.is_some() {
+                    *last = current.join("\n");
+                    current.clear();
+                }
+            }
+            res
         } else {
-            let node = self.tree.clone();
-            self.tick_node(&node, context)
+            self.step(context)
+        };
+        if let Some((_, running_ticks)) = self.runaway_guard.as_mut() {
+            *running_ticks = if res.is_some() { 0 } else { *running_ticks + 1 };
+        }
+        if res.is_some() {
+            self.last_result = res;
+        }
+        if let (Some(success), Some(callback)) = (res, self.on_complete.as_ref()) {
+            callback(context, success);
+        }
+        if self.trace.is_some() {
+            let active = self.active_node_ids();
+            if let Some(trace) = self.trace.as_mut() {
+                trace.push(TraceEntry {
+                    proceed: trace.len() as u64,
+                    active,
+                    result: res,
+                });
+            }
+        }
+        res
+    }
+
+    /// Proceeds, returning the error a [`composite::TryAction`] raised
+    ///
+    /// The tree stops ticking as soon as the error is raised (see
+    /// [`composite::TryAction`] for which composites check), and anything
+    /// left running is aborted, so the next proceed starts from the root.
+    /// Only the first error of a proceed is kept. An error of a type other
+    /// than `E` comes back as [`TryProceedError::OtherType`].
+    pub fn try_proceed<E: Send + 'static>(
+        &mut self,
+        context: &mut B,
+    ) -> Result<Option<bool>, TryProceedError<E>> {
+        let (res, raised) = fallible::collect(|| self.proceed(context));
+        let Some(raised) = raised else {
+            return Ok(res);
+        };
+        self.abort(context);
+        Err(match raised.error.downcast::<E>() {
+            Ok(error) => TryProceedError::Raised(*error),
+            Err(error) => TryProceedError::OtherType {
+                type_name: raised.type_name,
+                error,
+            },
+        })
+    }
+
+    /// Proceeds, reporting a [`RunawayError`] once the runaway guard's
+    /// threshold is reached
+    ///
+    /// The tree is still proceeded; the error only reports that it has been
+    /// running suspiciously long, and keeps being reported on every proceed
+    /// until it finishes. Without a guard this never fails.
+    pub fn proceed_checked(&mut self, context: &mut B) -> Result<Option<bool>, RunawayError> {
+        let res = self.proceed(context);
+        match self.runaway_guard {
+            Some((max, running_ticks)) if res.is_none() && running_ticks >= max => {
+                Err(RunawayError { running_ticks })
+            }
+            _ => Ok(res),
+        }
+    }
+
+    /// Bumps the blackboard's tick counter, then proceeds
+    ///
+    /// Pairs with [`ProceedClock`] for trees that measure time in
+    /// proceeds.
+    pub fn proceed_counted(&mut self, context: &mut B) -> Option<bool>
+    where
+        B: TickCounter,
+    {
+        context.set_tick_count(context.tick_count() + 1);
+        self.proceed(context)
+    }
+
+    /// Proceeds against the part of `outer` that `project` picks out
+    ///
+    /// Lets a runner built for a sub-context be driven from a larger one
+    /// without wrapping any of its nodes.
+    pub fn proceed_projected<O, F>(&mut self, outer: &mut O, project: F) -> Option<bool>
+    where
+        F: FnOnce(&mut O) -> &mut B,
+    {
+        self.proceed(project(outer))
+    }
+
+    /// Proceeds once per item, until the tree finishes
+    ///
+    /// Yields `None` for every proceed that leaves the tree running, then
+    /// the `Some` result of the one that finishes it, and then stops.
+    pub fn ticks<'a>(&'a mut self, context: &'a mut B) -> TickIter<'a, B> {
+        TickIter {
+            runner: self,
+            context,
+            finished: false,
+        }
+    }
+
+    /// Proceeds exactly once, expecting the tree to finish
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tree is still running after the proceed.
+    pub fn run_once(&mut self, context: &mut B) -> bool {
+        self.proceed(context).expect(
+            "run_once expected the tree to finish in a single tick, but it is still running",
+        )
+    }
+
+    /// Keeps proceeding while the tree is running and the budget allows
+    ///
+    /// Each proceed spends the [`BehaviorNode::estimated_cost`] of the node
+    /// about to be ticked. The tree is always proceeded at least once, even
+    /// if that single tick costs more than `budget`.
+    pub fn proceed_with_budget(&mut self, context: &mut B, budget: u32) -> Option<bool> {
+        let mut remaining = budget;
+        let mut ticked = false;
+        loop {
+            let cost = self
+                .current_tick
+                .as_ref()
+                .unwrap_or(&self.tree)
+                .estimated_cost();
+            if ticked && cost > remaining {
+                return None;
+            }
+            remaining = remaining.saturating_sub(cost);
+            ticked = true;
+            if let Some(res) = self.proceed(context) {
+                return Some(res);
+            }
+        }
+    }
+
+    /// Keeps proceeding while the tree is running and `deadline` hasn't passed
+    ///
+    /// The time-based counterpart of [`BehaviorRunner::proceed_with_budget`].
+    /// The deadline is only checked between proceeds, so a single slow tick
+    /// can overrun it, and the tree is always proceeded at least once.
+    pub fn proceed_within(&mut self, context: &mut B, deadline: Instant) -> Option<bool> {
+        loop {
+            if let Some(res) = self.proceed(context) {
+                return Some(res);
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+        }
+    }
+}
+
+/// Runs one tree for several agents at once
+///
+/// Each context gets its own resume slot, so the agents share the tree but
+/// advance through it independently.
+pub struct MultiRunner<B> {
+    tree: BehaviorArc<B>,
+    slots: Vec<Option<BehaviorArc<B>>>,
+}
+
+impl<B> std::fmt::Debug for MultiRunner<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiRunner")
+            .field("tree", &self.tree)
+            .field("slots", &self.slots)
+            .finish()
+    }
+}
+
+impl<B> MultiRunner<B> {
+    pub fn new(tree: BehaviorArc<B>) -> Self {
+        Self {
+            tree,
+            slots: Vec::new(),
+        }
+    }
+
+    /// Proceeds the tree once for every context
+    ///
+    /// The `i`th context always uses the `i`th resume slot. Slots are added
+    /// as more contexts show up, and dropped if fewer are passed in.
+    pub fn proceed_many(&mut self, contexts: &mut [B]) -> Vec<Option<bool>> {
+        self.slots.resize(contexts.len(), None);
+        self.slots
+            .iter_mut()
+            .zip(contexts.iter_mut())
+            .map(|(slot, context)| {
+                let node = slot.take().unwrap_or_else(|| self.tree.clone());
+                match node.tick(context) {
+                    NodeResult::Running(resume) => {
+                        *slot = Some(resume);
+                        None
+                    }
+                    NodeResult::Success => Some(true),
+                    NodeResult::Failure => Some(false),
+                }
+            })
+            .collect()
+    }
+
+    pub fn is_running(&self, agent: usize) -> bool {
+        self.slots.get(agent).is_some_and(Option::is_some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+
+    use super::*;
+    use crate::composite::{Inverter, LimitedRepeated, Repeated, Sequence, Succeeder};
+
+    #[test]
+    fn proceed_projected_uses_sub_context() {
+        struct World {
+            frame: u32,
+            agent: Completions,
+        }
+
+        let mut world = World {
+            frame: 0,
+            agent: Completions::default(),
+        };
+        let mut runner =
+            BehaviorRunner::from_node(Sequence::new([CountTick.arc(), CountTick.arc()]))
+                .on_complete(|context: &mut Completions, success| context.results.push(success));
+        for _ in 0..3 {
+            world.frame += 1;
+            check!(runner.proceed_projected(&mut world, |world| &mut world.agent) == Some(true));
+        }
+        check!(world.frame == 3);
+        check!(world.agent.ticks == 6);
+        check!(world.agent.results == vec![true; 3]);
+    }
+
+    #[test]
+    fn proceed_within_yields_at_deadline() {
+        let mut context = Completions::default();
+        let mut runner = BehaviorRunner::from_node(Cheap);
+        let deadline = Instant::now() + std::time::Duration::from_millis(5);
+        check!(runner.proceed_within(&mut context, deadline) == None);
+        check!(Instant::now() >= deadline);
+        check!(context.ticks > 1);
+
+        // A deadline in the past still gets one proceed
+        let ticks = context.ticks;
+        check!(runner.proceed_within(&mut context, Instant::now()) == None);
+        check!(context.ticks == ticks + 1);
+
+        let mut runner = BehaviorRunner::from_node(LimitedRepeated::new(50, CountTick.arc()));
+        let deadline = Instant::now() + std::time::Duration::from_secs(60);
+        check!(runner.proceed_within(&mut context, deadline) == Some(true));
+    }
+
+    #[derive(Debug, Default)]
+    struct Completions {
+        ticks: usize,
+        results: Vec<bool>,
+    }
+
+    #[derive(Debug)]
+    struct CountTick;
+    impl BehaviorNode<Completions> for CountTick {
+        fn tick(self: Arc<Self>, context: &mut Completions) -> NodeResult<Completions> {
+            context.ticks += 1;
+            NodeResult::Success
+        }
+    }
+
+    #[test]
+    fn node_result_display() {
+        let running = NodeResult::<()>::Running(Succeeder::default().arc());
+        check!(running.to_string() == "running");
+        check!(NodeResult::<()>::Success.to_string() == "success");
+        check!(NodeResult::<()>::Failure.to_string() == "failure");
+    }
+
+    #[test]
+    fn node_result_from_result() {
+        #[derive(Debug)]
+        struct Withdraw(usize);
+        impl BehaviorNode<Completions> for Withdraw {
+            fn tick(self: Arc<Self>, context: &mut Completions) -> NodeResult<Completions> {
+                NodeResult::from_result(
+                    context
+                        .ticks
+                        .checked_sub(self.0)
+                        .map(|left| context.ticks = left)
+                        .ok_or("insufficient funds"),
+                )
+            }
+        }
+
+        let mut context = Completions {
+            ticks: 5,
+            ..Default::default()
+        };
+        check!(BehaviorRunner::from_node(Withdraw(3)).run_once(&mut context));
+        check!(context.ticks == 2);
+        check!(!BehaviorRunner::from_node(Withdraw(3)).run_once(&mut context));
+        check!(context.ticks == 2);
+    }
+
+    #[test]
+    fn node_name_display() {
+        let leaf = Succeeder::<()>::default().arc();
+        check!(NodeName(leaf.as_ref()).to_string() == "Succeeder");
+        let inverter = Inverter::new(leaf.clone());
+        check!(NodeName(&inverter).to_string() == "Inverter");
+        let seq = [leaf].into_iter().collect::<Sequence<_>>();
+        check!(NodeName(&seq).to_string() == "Sequence");
+    }
+
+    #[derive(Debug)]
+    struct Expensive;
+    impl BehaviorNode<Completions> for Expensive {
+        fn tick(self: Arc<Self>, context: &mut Completions) -> NodeResult<Completions> {
+            context.ticks += 1;
+            NodeResult::Running(self)
+        }
+
+        fn estimated_cost(&self) -> u32 {
+            5
+        }
+    }
+
+    #[derive(Debug)]
+    struct Cheap;
+    impl BehaviorNode<Completions> for Cheap {
+        fn tick(self: Arc<Self>, context: &mut Completions) -> NodeResult<Completions> {
+            context.ticks += 1;
+            self.running()
+        }
+    }
+
+    #[test]
+    fn budget_spends_estimated_cost() {
+        let mut context = Completions::default();
+        let mut runner = BehaviorRunner::from_node(Expensive);
+        check!(runner.proceed_with_budget(&mut context, 12) == None);
+        check!(context.ticks == 2);
+
+        // Decorators and resumes report the cost of the leaf they drive
+        let mut context = Completions::default();
+        let mut runner =
+            BehaviorRunner::from_node(Sequence::new([CountTick.arc(), Expensive.arc()]));
+        check!(runner.proceed_with_budget(&mut context, 10) == None);
+        check!(context.ticks == 3);
+
+        let mut context = Completions::default();
+        let mut runner = BehaviorRunner::from_node(Cheap);
+        check!(runner.proceed_with_budget(&mut context, 12) == None);
+        check!(context.ticks == 12);
+    }
+
+    #[test]
+    fn budget_always_proceeds_once() {
+        let mut context = Completions::default();
+        let mut runner = BehaviorRunner::from_node(Expensive);
+        check!(runner.proceed_with_budget(&mut context, 3) == None);
+        check!(context.ticks == 1);
+
+        let mut runner = BehaviorRunner::from_node(CountTick);
+        check!(runner.proceed_with_budget(&mut context, 0) == Some(true));
+    }
+
+    #[derive(Debug, Default)]
+    struct Aborts(Vec<&'static str>);
+
+    #[derive(Debug)]
+    struct Tracked(&'static str, Option<BehaviorArc<Aborts>>);
+    impl BehaviorNode<Aborts> for Tracked {
+        fn tick(self: Arc<Self>, context: &mut Aborts) -> NodeResult<Aborts> {
+            match self.1.as_ref() {
+                Some(child) => match child.clone().tick(context) {
+                    NodeResult::Running(child) => {
+                        NodeResult::Running(Tracked(self.0, Some(child)).arc())
+                    }
+                    res => res,
+                },
+                None => NodeResult::Running(self),
+            }
+        }
+
+        fn active_children(&self) -> Vec<BehaviorArc<Aborts>> {
+            self.1.iter().cloned().collect()
+        }
+
+        fn on_abort(&self, context: &mut Aborts) {
+            context.0.push(self.0);
+        }
+    }
+
+    #[test]
+    fn abort_walks_active_spine() {
+        use crate::testing::AlwaysSucceed;
+
+        let mut runner = BehaviorRunner::from_node(Sequence::new([
+            AlwaysSucceed.arc(),
+            Tracked(
+                "outer",
+                Some(Inverter::new(Tracked("leaf", None).arc()).arc()),
+            )
+            .arc(),
+            Tracked("never", None).arc(),
+        ]));
+        let mut context = Aborts::default();
+        check!(runner.active_path().is_empty());
+        check!(runner.proceed(&mut context) == None);
+        let path = runner.active_path();
+        let names = path.iter().map(|node| node.name()).collect::<Vec<_>>();
+        check!(names == vec!["SequenceResume", "Tracked", "Inverter", "Tracked"]);
+
+        runner.abort(&mut context);
+        check!(context.0 == vec!["outer", "leaf"]);
+        check!(!runner.is_running());
+        check!(runner.active_path().is_empty());
+    }
+
+    #[test]
+    fn on_complete_fires_once_for_completing_tree() {
+        let mut runner = BehaviorRunner::new(
+            Inverter::new(LimitedRepeated::new(3, CountTick.arc()).arc()).arc(),
+        )
+        .on_complete(|context: &mut Completions, success| context.results.push(success));
+        let mut context = Completions::default();
+        while runner.proceed(&mut context).is_none() {}
+        check!(context.ticks == 3);
+        check!(context.results == vec![false]);
+    }
+
+    #[test]
+    fn on_complete_never_fires_for_repeated() {
+        let mut runner = BehaviorRunner::new(Repeated::new(CountTick.arc()).arc())
+            .on_complete(|context: &mut Completions, success| context.results.push(success));
+        let mut context = Completions::default();
+        for _ in 0..10 {
+            check!(runner.proceed(&mut context) == None);
+        }
+        check!(context.ticks == 10);
+        check!(context.results.is_empty());
+    }
+
+    #[test]
+    fn ticks_stop_after_the_result() {
+        use crate::testing::SucceedAfter;
+
+        let mut runner = BehaviorRunner::from_node(SucceedAfter(2));
+        let mut context = Completions::default();
+        check!(runner.ticks(&mut context).collect::<Vec<_>>() == [None, None, Some(true)]);
+        // Composes with the usual adapters, and starts over next time
+        check!(runner.ticks(&mut context).take(2).count() == 2);
+        check!(runner.ticks(&mut context).last() == Some(Some(true)));
+    }
+
+    #[test]
+    fn children_walk_the_whole_tree() {
+        use crate::composite::{ParallelSequence, Selector};
+        use crate::testing::{AlwaysFail, AlwaysSucceed, SucceedAfter};
+
+        fn count(node: &BehaviorArc<()>) -> usize {
+            1 + node.children().iter().map(count).sum::<usize>()
+        }
+
+        let tree: BehaviorArc<()> = Sequence::new([
+            Inverter::new(SucceedAfter(1).arc()).arc(),
+            LimitedRepeated::new(2, AlwaysFail.arc()).arc(),
+            Selector::new([
+                ParallelSequence::new([AlwaysSucceed.arc(), AlwaysFail.arc()]).arc(),
+                Succeeder::new(AlwaysSucceed.arc()).arc(),
+            ])
+            .arc(),
+        ])
+        .arc();
+        check!(count(&tree) == 11);
+    }
+
+    #[test]
+    fn value_eq_compares_structure() {
+        use crate::testing::{AlwaysFail, SucceedAfter};
+
+        let tree = |n| {
+            Sequence::new([
+                Inverter::new(SucceedAfter(n).arc()).arc(),
+                LimitedRepeated::new(2, AlwaysFail.arc()).arc(),
+            ])
+            .arc()
+        };
+        let a: BehaviorArc<()> = tree(1);
+        check!(a.value_eq(tree(1).as_ref()));
+        check!(!a.value_eq(tree(2).as_ref()));
+        check!(!a.value_eq(Succeeder::default().arc().as_ref()));
+
+        // Nodes without an override only equal themselves
+        let counted: BehaviorArc<Completions> = CountTick.arc();
+        check!(counted.value_eq(counted.as_ref()));
+        check!(!counted.value_eq(CountTick.arc().as_ref()));
+    }
+
+    #[test]
+    fn runner_takes_a_borrowing_context() {
+        #[derive(Debug)]
+        struct Append;
+
+        impl<'a> BehaviorNode<Vec<&'a str>> for Append {
+            fn tick(self: Arc<Self>, words: &mut Vec<&'a str>) -> NodeResult<Vec<&'a str>> {
+                words.push("tick");
+                NodeResult::Success
+            }
+        }
+
+        let mut words = vec![];
+        let mut runner = BehaviorRunner::from_node(Append);
+        check!(runner.proceed(&mut words) == Some(true));
+        check!(words == ["tick"]);
+
+        // Nothing to downcast without an `as_any` override
+        let node: BehaviorArc<Vec<&str>> = Append.arc();
+        check!(node.downcast_ref::<Append>().is_none());
+    }
+
+    #[test]
+    fn leaves_report_is_leaf() {
+        check!(BehaviorNode::<Completions>::is_leaf(&CountTick));
+        check!(!Inverter::new(CountTick.arc()).is_leaf());
+        check!(!Sequence::new([CountTick.arc()]).is_leaf());
+    }
+
+    #[test]
+    fn built_in_nodes_report_kind() {
+        use crate::composite::{Failer, ParallelSequence, Selector};
+        use crate::testing::{AlwaysSucceed, SucceedAfter};
+
+        let leaf = || SucceedAfter(1).arc();
+        check!(leaf().kind() == NodeKind::Leaf);
+        check!(Sequence::new([leaf()]).kind() == NodeKind::Sequence);
+        check!(Selector::new([leaf()]).kind() == NodeKind::Selector);
+        check!(ParallelSequence::new([leaf()]).kind() == NodeKind::Parallel);
+        check!(Inverter::new(leaf()).kind() == NodeKind::Inverter);
+        check!(Succeeder::new(leaf()).kind() == NodeKind::Succeeder);
+        check!(Failer::new(leaf()).kind() == NodeKind::Failer);
+        check!(Repeated::new(leaf()).kind() == NodeKind::Repeater);
+        check!(LimitedRepeated::new(2, leaf()).kind() == NodeKind::Repeater);
+        check!(
+            BehaviorNode::<()>::kind(&(AlwaysSucceed.arc(), AlwaysSucceed.arc()))
+                == NodeKind::Sequence
+        );
+
+        // The running copy keeps its template's kind
+        let_assert!(NodeResult::Running(resume) = Sequence::new([leaf()]).arc().tick(&mut ()));
+        check!(resume.kind() == NodeKind::Sequence);
+    }
+
+    #[test]
+    fn forced_result_is_one_shot() {
+        let mut runner = BehaviorRunner::from_node(crate::testing::SucceedAfter(2));
+        let mut context = Completions::default();
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.is_running());
+
+        runner.force_success(&mut context);
+        check!(!runner.is_running());
+        check!(runner.proceed(&mut context) == Some(true));
+
+        // Back to normal: the tree starts over from the root
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == None);
+        check!(runner.proceed(&mut context) == Some(true));
+
+        runner.force_failure(&mut context);
+        check!(runner.proceed(&mut context) == Some(false));
+        check!(runner.proceed(&mut context) == None);
+    }
+
+    #[test]
+    fn peek_next_follows_proceed() {
+        let tree: BehaviorArc<Completions> = Sequence::new([Cheap.arc()]).arc();
+        let mut runner = BehaviorRunner::new(tree.clone());
+        let mut context = Completions::default();
+        check!(runner
+            .peek_next()
+            .is_some_and(|next| Arc::ptr_eq(next, &tree)));
+
+        check!(runner.proceed(&mut context) == None);
+        let resume = runner.active_path()[0].clone();
+        check!(!Arc::ptr_eq(&resume, &tree));
+        check!(runner
+            .peek_next()
+            .is_some_and(|next| Arc::ptr_eq(next, &resume)));
+
+        runner.force_failure(&mut context);
+        check!(runner.peek_next().is_none());
+        check!(runner.proceed(&mut context) == Some(false));
+        check!(runner
+            .peek_next()
+            .is_some_and(|next| Arc::ptr_eq(next, &tree)));
+    }
+
+    #[test]
+    fn explanations_follow_the_finished_activation() {
+        use crate::composite::{Condition, Selector};
+        use crate::testing::{AlwaysSucceed, SucceedAfter};
+
+        let tree = Selector::new([
+            Sequence::new([
+                Condition::new(|health: &u32| *health < 20)
+                    .explained("health < 20")
+                    .arc(),
+                SucceedAfter(1).arc(),
+            ])
+            .arc(),
+            AlwaysSucceed.arc(),
+        ]);
+        let mut runner = BehaviorRunner::from_node(tree).with_explanations();
+        check!(runner.explanation() == Some(""));
+
+        let mut health = 50;
+        check!(runner.proceed(&mut health) == Some(true));
+        check!(
+            runner.explanation()
+                == Some("health < 20 → false\nfailed at step 0\nselected branch 1")
+        );
+
+        health = 10;
+        check!(runner.proceed(&mut health) == None);
+        // Still the previous activation's explanation while running
+        check!(runner
+            .explanation()
+            .is_some_and(|text| text.ends_with("branch 1")));
+        check!(runner.proceed(&mut health) == Some(true));
+        check!(runner.explanation() == Some("health < 20 → true\nselected branch 0"));
+
+        let mut quiet = BehaviorRunner::from_node(AlwaysSucceed);
+        check!(quiet.proceed(&mut ()) == Some(true));
+        check!(quiet.explanation().is_none());
+    }
+
+    #[test]
+    fn reset_restarts_from_the_root() {
+        #[derive(Debug)]
+        struct Count;
+        impl BehaviorNode<Completions> for Count {
+            fn tick(self: Arc<Self>, context: &mut Completions) -> NodeResult<Completions> {
+                context.ticks += 1;
+                NodeResult::Success
+            }
+        }
+
+        let mut runner = BehaviorRunner::from_node(LimitedRepeated::new(3, Count.arc()));
+        let mut context = Completions::default();
+        let run = |runner: &mut BehaviorRunner<Completions>, context: &mut Completions| {
+            let (mut proceeds, mut res) = (1, runner.proceed(context));
+            while res.is_none() {
+                proceeds += 1;
+                res = runner.proceed(context);
+            }
+            (proceeds, res, context.ticks)
+        };
+        check!(run(&mut runner, &mut context) == (4, Some(true), 3));
+        runner.reset();
+        check!(run(&mut runner, &mut context) == (4, Some(true), 6));
+
+        // Mid-run, the repetitions already done are forgotten
+        runner.proceed(&mut context);
+        check!(runner.is_running());
+        runner.reset();
+        check!(!runner.is_running());
+        check!(run(&mut runner, &mut context) == (4, Some(true), 10));
+    }
+
+    #[test]
+    fn runaway_guard_fires_at_threshold() {
+        let mut runner =
+            BehaviorRunner::from_node(Repeated::new(CountTick.arc())).with_runaway_guard(5);
+        let mut context = Completions::default();
+        for _ in 0..4 {
+            check!(runner.proceed_checked(&mut context) == Ok(None));
+        }
+        check!(runner.proceed_checked(&mut context) == Err(RunawayError { running_ticks: 5 }));
+        check!(runner.proceed_checked(&mut context) == Err(RunawayError { running_ticks: 6 }));
+        check!(context.ticks == 6);
+
+        // Finishing starts the count over
+        let mut runner = BehaviorRunner::from_node(LimitedRepeated::new(3, CountTick.arc()))
+            .with_runaway_guard(4);
+        for _ in 0..3 {
+            for _ in 0..3 {
+                check!(runner.proceed_checked(&mut context) == Ok(None));
+            }
+            check!(runner.proceed_checked(&mut context) == Ok(Some(true)));
+        }
+
+        let mut unguarded = BehaviorRunner::from_node(Repeated::new(CountTick.arc()));
+        for _ in 0..100 {
+            check!(unguarded.proceed_checked(&mut context) == Ok(None));
+        }
+    }
+
+    #[test]
+    fn transitions_are_reported_on_start_and_finish() {
+        use crate::testing::SucceedAfter;
+
+        let log = Arc::new(std::sync::Mutex::new(vec![]));
+        let mut runner = BehaviorRunner::from_node(SucceedAfter(2)).on_transition({
+            let log = log.clone();
+            move |node: &dyn BehaviorNode<Completions>, status| {
+                log.lock().unwrap().push((node.name(), status))
+            }
+        });
+        let mut context = Completions::default();
+        while runner.proceed(&mut context).is_none() {}
+        check!(
+            *log.lock().unwrap()
+                == [
+                    ("SucceedAfter", NodeResultKind::Running),
+                    ("SucceedAfter", NodeResultKind::Success),
+                ]
+        );
+    }
+
+    #[test]
+    fn current_node_is_the_paused_node() {
+        use crate::testing::SucceedAfter;
+
+        let mut runner = BehaviorRunner::new(
+            composite::Sequence::new([SucceedAfter(0).arc(), SucceedAfter(1).arc()]).arc(),
+        );
+        let mut context = Completions::default();
+        check!(runner.current_node().is_none());
+        check!(runner.proceed(&mut context) == None);
+        let_assert!(Some(node) = runner.current_node());
+        check!(format!("{node:?}").contains("SequenceResume"));
+        check!(runner.proceed(&mut context) == Some(true));
+        check!(runner.current_node().is_none());
+    }
+
+    #[test]
+    fn finished_trees_hold_only_when_asked() {
+        use crate::testing::SucceedAfter;
+
+        let mut context = Completions::default();
+        let mut looping = BehaviorRunner::from_node(SucceedAfter(1));
+        check!(looping.last_result().is_none());
+        check!(looping.proceed(&mut context) == None);
+        check!(looping.proceed(&mut context) == Some(true));
+        check!(looping.is_finished());
+        // Starts over by default
+        check!(looping.proceed(&mut context) == None);
+        check!(!looping.is_finished());
+        check!(looping.last_result() == Some(true));
+
+        let mut held = BehaviorRunner::from_node(SucceedAfter(1)).hold_finished();
+        check!(held.proceed(&mut context) == None);
+        check!(held.proceed(&mut context) == Some(true));
+        for _ in 0..3 {
+            check!(held.proceed(&mut context) == Some(true));
+            check!(held.is_finished());
         }
+        held.reset();
+        check!(held.last_result().is_none());
+        check!(held.proceed(&mut context) == None);
+        check!(held.proceed(&mut context) == Some(true));
+    }
+
+    #[test]
+    fn running_reuses_arc() {
+        let node: BehaviorArc<Completions> = Cheap.arc();
+        let mut runner = BehaviorRunner::new(node.clone());
+        let mut context = Completions::default();
+        for _ in 0..3 {
+            check!(runner.proceed(&mut context) == None);
+            check!(Arc::ptr_eq(&runner.active_path()[0], &node));
+        }
+    }
+
+    #[test]
+    fn run_once_returns_result() {
+        let mut context = Completions::default();
+        check!(BehaviorRunner::from_node(CountTick).run_once(&mut context));
+        check!(!BehaviorRunner::from_node(Inverter::new(CountTick.arc())).run_once(&mut context));
+    }
+
+    #[test]
+    #[should_panic(expected = "still running")]
+    fn run_once_panics_when_running() {
+        BehaviorRunner::from_node(Cheap).run_once(&mut Completions::default());
+    }
+
+    #[derive(Debug, Default)]
+    struct Patrol {
+        position: u32,
+        waypoints: Vec<u32>,
+    }
+
+    #[derive(Debug)]
+    struct WalkTo(u32);
+    impl BehaviorNode<Patrol> for WalkTo {
+        fn tick(self: Arc<Self>, context: &mut Patrol) -> NodeResult<Patrol> {
+            if context.position == self.0 {
+                context.waypoints.push(self.0);
+                NodeResult::Success
+            } else {
+                if context.position < self.0 {
+                    context.position += 1;
+                } else {
+                    context.position -= 1;
+                }
+                self.running()
+            }
+        }
+    }
+
+    #[test]
+    fn multi_runner_advances_agents_independently() {
+        let mut runner = MultiRunner::new(Sequence::new([WalkTo(2).arc(), WalkTo(4).arc()]).arc());
+        let mut agents = [0, 2, 4].map(|position| Patrol {
+            position,
+            ..Default::default()
+        });
+
+        check!(runner.proceed_many(&mut agents) == vec![None, None, None]);
+        check!(runner.proceed_many(&mut agents) == vec![None, None, None]);
+        check!(runner.proceed_many(&mut agents) == vec![None, Some(true), None]);
+        check!(agents[0].waypoints == vec![2]);
+        check!(agents[1].waypoints == vec![2, 4]);
+        check!(agents[2].waypoints == vec![2]);
+        check!(!runner.is_running(1));
+
+        check!(runner.proceed_many(&mut agents) == vec![None, None, None]);
+        check!(runner.is_running(1));
+        check!(runner.proceed_many(&mut agents) == vec![Some(true), None, Some(true)]);
+        check!(agents[0].waypoints == vec![2, 4]);
+        check!(agents[2].waypoints == vec![2, 4]);
     }
 }
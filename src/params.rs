@@ -0,0 +1,150 @@
+//! Typed access to the string parameters of data-driven leaves
+//!
+//! When trees are loaded from data, every registered constructor has the
+//! same shape: it takes the node's [`NodeParams`] and either builds the node
+//! or explains which parameter was wrong (see [`BuildNode`]).
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::BehaviorArc;
+
+/// The signature every registered node constructor follows
+pub type BuildNode<B> = fn(&NodeParams) -> Result<BehaviorArc<B>, ParamError>;
+
+/// A small map of named, unparsed parameters
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct NodeParams {
+    values: BTreeMap<String, String>,
+}
+
+impl NodeParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.values.insert(name.into(), value.into());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get_raw(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    /// Parses the parameter `name`, failing if it is missing
+    pub fn get_required<T>(&self, name: &str) -> Result<T, ParamError>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.get_optional(name)?
+            .ok_or_else(|| ParamError::Missing(name.to_string()))
+    }
+
+    /// Parses the parameter `name` if it is present
+    pub fn get_optional<T>(&self, name: &str) -> Result<Option<T>, ParamError>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.get_raw(name)
+            .map(|value| {
+                value.parse().map_err(|err: T::Err| ParamError::Invalid {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                    reason: err.to_string(),
+                })
+            })
+            .transpose()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for NodeParams {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self {
+            values: iter
+                .into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    /// A required parameter was not given
+    Missing(String),
+    /// A parameter could not be parsed into the expected type
+    Invalid {
+        name: String,
+        value: String,
+        reason: String,
+    },
+}
+
+impl std::fmt::Display for ParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamError::Missing(name) => write!(f, "missing required parameter `{name}`"),
+            ParamError::Invalid {
+                name,
+                value,
+                reason,
+            } => write!(
+                f,
+                "invalid value {value:?} for parameter `{name}`: {reason}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+
+    use super::*;
+    use crate::{composite::LimitedRepeated, testing::AlwaysSucceed, BehaviorNode, BehaviorRunner};
+
+    fn build_limited(params: &NodeParams) -> Result<BehaviorArc<()>, ParamError> {
+        let limit = params.get_required::<usize>("limit")?;
+        Ok(LimitedRepeated::new(limit, AlwaysSucceed.arc()).arc())
+    }
+
+    #[test]
+    fn builds_limited_repeated_from_params() {
+        let build: BuildNode<()> = build_limited;
+        let_assert!(Ok(tree) = build(&[("limit", "3")].into_iter().collect()));
+        let mut runner = BehaviorRunner::new(tree);
+        for _ in 0..3 {
+            check!(runner.proceed(&mut ()) == None);
+        }
+        check!(runner.proceed(&mut ()) == Some(true));
+    }
+
+    #[test]
+    fn reports_param_errors() {
+        let_assert!(Err(err) = build_limited(&NodeParams::new()));
+        check!(err == ParamError::Missing("limit".to_string()));
+        check!(err.to_string() == "missing required parameter `limit`");
+
+        let_assert!(Err(err) = build_limited(&[("limit", "three")].into_iter().collect()));
+        let_assert!(ParamError::Invalid { name, value, .. } = err);
+        check!(name == "limit");
+        check!(value == "three");
+    }
+
+    #[test]
+    fn optional_params() {
+        let params = [("speed", "1.5")].into_iter().collect::<NodeParams>();
+        check!(params.get_optional::<f32>("speed") == Ok(Some(1.5)));
+        check!(params.get_optional::<f32>("range") == Ok(None));
+    }
+}
@@ -0,0 +1,372 @@
+//! Saving and loading the shape of a tree
+//!
+//! A [`TreeShape`] records what [`crate::BehaviorNode::name`],
+//! [`crate::BehaviorNode::params`] and [`crate::BehaviorNode::children`]
+//! report for every node, which is enough for an editor to show and edit a
+//! tree. Closures can't be saved, so a leaf is only its name and whatever
+//! parameters it reports (an id, say); loading it back goes through a
+//! constructor registered under that name in a [`ShapeRegistry`].
+//!
+//! With the `serde` feature, shapes serialize as nested objects with
+//! `kind`, `params` and `children` fields.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::composite::{
+    Failer, Inverter, LimitedRepeated, Parallel, Repeated, Selector, Sequence, Succeeder,
+};
+use crate::params::{BuildNode, NodeParams, ParamError};
+use crate::{BehaviorArc, BehaviorNode};
+
+/// The structure of a tree, without any of its closures or running state
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreeShape {
+    /// The [`crate::BehaviorNode::name`] of the node
+    pub kind: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "NodeParams::is_empty")
+    )]
+    pub params: NodeParams,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub children: Vec<TreeShape>,
+}
+
+impl TreeShape {
+    /// Records the shape of the tree under `root`
+    pub fn of<B>(root: &BehaviorArc<B>) -> Self {
+        Self {
+            kind: root.name().to_string(),
+            params: root.params(),
+            children: root.children().iter().map(TreeShape::of).collect(),
+        }
+    }
+}
+
+/// Builds a node from its parameters and its already built children
+pub type BuildComposite<B> =
+    fn(&NodeParams, Vec<BehaviorArc<B>>) -> Result<BehaviorArc<B>, ShapeError>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeError {
+    /// No constructor was registered under this kind
+    UnknownKind(String),
+    /// A node had a parameter missing or malformed
+    Param { kind: String, error: ParamError },
+    /// A node had a number of children its constructor can't take
+    ChildCount { kind: String, found: usize },
+}
+
+impl std::fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShapeError::UnknownKind(kind) => write!(f, "no node is registered as `{kind}`"),
+            ShapeError::Param { kind, error } => write!(f, "in `{kind}`: {error}"),
+            ShapeError::ChildCount { kind, found } => {
+                write!(f, "`{kind}` can't take {found} children")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+type Factory<B> = Arc<dyn Fn() -> BehaviorArc<B> + Send + Sync>;
+
+enum Build<B> {
+    Leaf(BuildNode<B>),
+    Factory(Factory<B>),
+    Composite(BuildComposite<B>),
+}
+
+/// Turns [`TreeShape`]s back into trees
+///
+/// Starts out knowing the plain composites and decorators ([`Sequence`],
+/// [`Selector`], [`Parallel`], [`Inverter`], [`Succeeder`], [`Failer`],
+/// [`Repeated`] and [`LimitedRepeated`]). Everything else, leaves in
+/// particular, has to be registered under its kind first.
+pub struct ShapeRegistry<B> {
+    builders: HashMap<String, Build<B>>,
+}
+
+impl<B> std::fmt::Debug for ShapeRegistry<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShapeRegistry")
+            .field("kinds", &self.builders.keys())
+            .finish()
+    }
+}
+
+impl<B: 'static> Default for ShapeRegistry<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: 'static> ShapeRegistry<B> {
+    pub fn new() -> Self {
+        let mut registry = Self {
+            builders: HashMap::new(),
+        };
+        registry.register("Sequence", |_, children| Ok(Sequence::new(children).arc()));
+        registry.register("Selector", |_, children| Ok(Selector::new(children).arc()));
+        registry.register("Parallel", |params, children| {
+            let success = params.get_required("success_threshold")?;
+            let failure = params.get_required("failure_threshold")?;
+            Ok(Parallel::new_unchecked(success, failure, children).arc())
+        });
+        registry.register("Inverter", |_, children| {
+            Ok(Inverter::new(only_child("Inverter", children)?).arc())
+        });
+        registry.register("Succeeder", |_, children| {
+            Ok(match optional_child("Succeeder", children)? {
+                Some(child) => Succeeder::new(child).arc(),
+                None => Succeeder::default().arc(),
+            })
+        });
+        registry.register("Failer", |_, children| {
+            Ok(match optional_child("Failer", children)? {
+                Some(child) => Failer::new(child).arc(),
+                None => Failer::default().arc(),
+            })
+        });
+        registry.register("Repeated", |_, children| {
+            Ok(Repeated::new(only_child("Repeated", children)?).arc())
+        });
+        registry.register("LimitedRepeated", |params, children| {
+            let limit = params.get_required("limit")?;
+            Ok(LimitedRepeated::new(limit, only_child("LimitedRepeated", children)?).arc())
+        });
+        registry
+    }
+
+    /// Builds nodes of `kind` with `build`, replacing any earlier constructor
+    pub fn register(&mut self, kind: impl Into<String>, build: BuildComposite<B>) {
+        self.builders.insert(kind.into(), Build::Composite(build));
+    }
+
+    /// Builds childless nodes of `kind` with `build`
+    pub fn register_leaf(&mut self, kind: impl Into<String>, build: BuildNode<B>) {
+        self.builders.insert(kind.into(), Build::Leaf(build));
+    }
+
+    /// Builds childless nodes of `kind` by calling `factory`, ignoring
+    /// their parameters
+    ///
+    /// Suits leaves wrapping closures, like [`crate::composite::Action`],
+    /// which can't be saved. Their name is the node's type, so shapes
+    /// referring to them are usually written by hand or by an editor, with
+    /// `kind` set to the key registered here.
+    pub fn register_factory<F>(&mut self, kind: impl Into<String>, factory: F)
+    where
+        F: Fn() -> BehaviorArc<B> + Send + Sync + 'static,
+    {
+        self.builders
+            .insert(kind.into(), Build::Factory(Arc::new(factory)));
+    }
+
+    /// Rebuilds the tree `shape` describes
+    pub fn build(&self, shape: &TreeShape) -> Result<BehaviorArc<B>, ShapeError> {
+        let build = self
+            .builders
+            .get(&shape.kind)
+            .ok_or_else(|| ShapeError::UnknownKind(shape.kind.clone()))?;
+        let children = shape
+            .children
+            .iter()
+            .map(|child| self.build(child))
+            .collect::<Result<Vec<_>, _>>()?;
+        let res = match build {
+            Build::Leaf(_) | Build::Factory(_) if !children.is_empty() => {
+                return Err(ShapeError::ChildCount {
+                    kind: shape.kind.clone(),
+                    found: children.len(),
+                })
+            }
+            Build::Leaf(build) => build(&shape.params).map_err(ShapeError::from),
+            Build::Factory(factory) => Ok(factory()),
+            Build::Composite(build) => build(&shape.params, children),
+        };
+        // Constructors don't know the kind they were registered under
+        res.map_err(|err| match err {
+            ShapeError::Param { error, .. } => ShapeError::Param {
+                kind: shape.kind.clone(),
+                error,
+            },
+            err => err,
+        })
+    }
+}
+
+/// Lets constructors use `?` on parameters; [`ShapeRegistry::build`] fills
+/// in the kind
+impl From<ParamError> for ShapeError {
+    fn from(error: ParamError) -> Self {
+        ShapeError::Param {
+            kind: String::new(),
+            error,
+        }
+    }
+}
+
+fn only_child<B>(kind: &str, children: Vec<BehaviorArc<B>>) -> Result<BehaviorArc<B>, ShapeError> {
+    match optional_child(kind, children)? {
+        Some(child) => Ok(child),
+        None => Err(ShapeError::ChildCount {
+            kind: kind.to_string(),
+            found: 0,
+        }),
+    }
+}
+
+fn optional_child<B>(
+    kind: &str,
+    children: Vec<BehaviorArc<B>>,
+) -> Result<Option<BehaviorArc<B>>, ShapeError> {
+    let found = children.len();
+    let mut children = children.into_iter();
+    match (children.next(), children.next()) {
+        (child, None) => Ok(child),
+        _ => Err(ShapeError::ChildCount {
+            kind: kind.to_string(),
+            found,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+
+    use super::*;
+    use crate::{
+        composite::Action,
+        testing::{AlwaysFail, AlwaysSucceed},
+        BehaviorRunner, NodeResult,
+    };
+
+    /// A leaf saved by its id, standing in for one wrapping a closure
+    #[derive(Debug)]
+    struct Say(String);
+
+    impl BehaviorNode<Vec<String>> for Say {
+        fn tick(self: Arc<Self>, said: &mut Vec<String>) -> NodeResult<Vec<String>> {
+            said.push(self.0.clone());
+            NodeResult::Success
+        }
+
+        fn params(&self) -> NodeParams {
+            [("id", self.0.as_str())].into_iter().collect()
+        }
+    }
+
+    fn registry() -> ShapeRegistry<Vec<String>> {
+        let mut registry = ShapeRegistry::new();
+        registry.register_leaf("Say", |params| Ok(Say(params.get_required("id")?).arc()));
+        registry
+    }
+
+    fn tree() -> BehaviorArc<Vec<String>> {
+        Selector::new([
+            Inverter::new(Say("hello".into()).arc()).arc(),
+            Sequence::new([
+                LimitedRepeated::new(2, Say("again".into()).arc()).arc(),
+                Parallel::new_unchecked(2, 1, [Say("a".into()).arc(), Say("b".into()).arc()]).arc(),
+                Failer::default().arc(),
+            ])
+            .arc(),
+            Succeeder::new(Say("bye".into()).arc()).arc(),
+        ])
+        .arc()
+    }
+
+    fn run(tree: BehaviorArc<Vec<String>>) -> (Vec<Option<bool>>, Vec<String>) {
+        let mut runner = BehaviorRunner::new(tree);
+        let mut said = vec![];
+        let res = runner.ticks(&mut said).take(10).collect();
+        (res, said)
+    }
+
+    #[test]
+    fn shapes_rebuild_into_the_same_tree() {
+        let shape = TreeShape::of(&tree());
+        check!(shape.kind == "Selector");
+        check!(shape.children[1].children[0].params.get_raw("limit") == Some("2"));
+
+        let_assert!(Ok(rebuilt) = registry().build(&shape));
+        check!(TreeShape::of(&rebuilt) == shape);
+        check!(run(rebuilt) == run(tree()));
+    }
+
+    #[test]
+    fn unknown_and_malformed_nodes_are_errors() {
+        let shape = TreeShape::of(&tree());
+        let_assert!(Err(err) = ShapeRegistry::<Vec<String>>::new().build(&shape));
+        check!(err == ShapeError::UnknownKind("Say".to_string()));
+
+        let mut shape = shape;
+        shape.children[1].children[0].params = NodeParams::new();
+        let_assert!(Err(err) = registry().build(&shape));
+        check!(err.to_string() == "in `LimitedRepeated`: missing required parameter `limit`");
+
+        shape.children[0].children.clear();
+        let_assert!(Err(err) = registry().build(&shape));
+        check!(
+            err == ShapeError::ChildCount {
+                kind: "Inverter".to_string(),
+                found: 0
+            }
+        );
+    }
+
+    #[test]
+    fn factories_build_leaves_by_key() {
+        let shape = |kind: &str| TreeShape {
+            kind: kind.to_string(),
+            params: NodeParams::new(),
+            children: vec![],
+        };
+        let saved = TreeShape {
+            children: vec![shape("refuse"), shape("greet")],
+            ..shape("Selector")
+        };
+
+        let mut registry = ShapeRegistry::new();
+        let greeting = String::from("hi");
+        registry.register_factory("greet", move || {
+            let greeting = greeting.clone();
+            Action::new(move |said: &mut Vec<String>| {
+                said.push(greeting.clone());
+                NodeResult::Success
+            })
+            .arc()
+        });
+        registry.register_factory("refuse", || AlwaysFail.arc());
+        let_assert!(Ok(tree) = registry.build(&saved));
+        check!(run(tree) == (vec![Some(true)], vec!["hi".to_string()]));
+
+        let mut registry = ShapeRegistry::<Vec<String>>::new();
+        registry.register_factory("greet", || AlwaysSucceed.arc());
+        let_assert!(Err(err) = registry.build(&saved));
+        check!(err.to_string() == "no node is registered as `refuse`");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn shapes_round_trip_through_json() {
+        let shape = TreeShape::of(&tree());
+        let json = serde_json::to_string(&shape).unwrap();
+        check!(json.starts_with(r#"{"kind":"Selector","children":[{"kind":"Inverter","#));
+        check!(json.contains(r#"{"kind":"Say","params":{"id":"hello"}}"#));
+
+        let_assert!(Ok(loaded) = serde_json::from_str::<TreeShape>(&json));
+        check!(loaded == shape);
+        let_assert!(Ok(rebuilt) = registry().build(&loaded));
+        check!(run(rebuilt) == run(tree()));
+    }
+}
@@ -0,0 +1,148 @@
+//! Helpers for [`crate::BehaviorNode::serialize_state`]
+//!
+//! Everything is written little-endian with fixed widths, so the same state
+//! produces the same bytes on every platform.
+
+/// Why a running node's state couldn't be written or read back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateError {
+    /// This node keeps progress it has no way to write down or rebuild
+    Unsupported(&'static str),
+    /// The bytes ran out before the state did
+    Truncated,
+    /// The bytes don't describe a state this node could be in
+    Invalid(&'static str),
+}
+
+impl std::fmt::Display for StateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StateError::Unsupported(node) => write!(f, "`{node}` can't save its state"),
+            StateError::Truncated => write!(f, "the state ended early"),
+            StateError::Invalid(node) => write!(f, "not a state `{node}` could be in"),
+        }
+    }
+}
+
+impl std::error::Error for StateError {}
+
+/// Appends `value` as eight little-endian bytes
+pub fn write_u64(out: &mut Vec<u8>, value: u64) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Reads eight little-endian bytes, advancing `bytes` past them
+///
+/// Leaves `bytes` alone if fewer than eight remain.
+pub fn read_u64(bytes: &mut &[u8]) -> Result<u64, StateError> {
+    let (head, rest) = bytes
+        .split_first_chunk::<8>()
+        .ok_or(StateError::Truncated)?;
+    *bytes = rest;
+    Ok(u64::from_le_bytes(*head))
+}
+
+/// Reads a `usize` written with [`write_u64`], which must be below `len`
+///
+/// `node` names the node reading it, for the error.
+pub fn read_index(bytes: &mut &[u8], len: usize, node: &'static str) -> Result<usize, StateError> {
+    let index = read_u64(bytes)?;
+    usize::try_from(index)
+        .ok()
+        .filter(|index| *index < len)
+        .ok_or(StateError::Invalid(node))
+}
+
+/// Appends a list of indices, length first
+pub fn write_indices(out: &mut Vec<u8>, indices: &[usize]) {
+    write_u64(out, indices.len() as u64);
+    for index in indices {
+        write_u64(out, *index as u64);
+    }
+}
+
+/// Reads a list written by [`write_indices`], of at most `len` indices that
+/// must each be below `len`
+pub fn read_indices(
+    bytes: &mut &[u8],
+    len: usize,
+    node: &'static str,
+) -> Result<Vec<usize>, StateError> {
+    let count = read_u64(bytes)?;
+    if count > len as u64 {
+        return Err(StateError::Invalid(node));
+    }
+    (0..count).map(|_| read_index(bytes, len, node)).collect()
+}
+
+/// Appends a presence flag, then `f`'s output if there is something present
+pub fn write_option<T, E>(
+    out: &mut Vec<u8>,
+    value: Option<T>,
+    f: impl FnOnce(&mut Vec<u8>, T) -> Result<(), E>,
+) -> Result<(), E> {
+    match value {
+        Some(value) => {
+            write_flag(out, true);
+            f(out, value)
+        }
+        None => {
+            write_flag(out, false);
+            Ok(())
+        }
+    }
+}
+
+/// Appends `flag` as a single byte
+pub fn write_flag(out: &mut Vec<u8>, flag: bool) {
+    out.push(flag as u8);
+}
+
+/// Reads a flag written by [`write_flag`] or [`write_option`]
+pub fn read_flag(bytes: &mut &[u8]) -> Result<bool, StateError> {
+    let (&flag, rest) = bytes.split_first().ok_or(StateError::Truncated)?;
+    *bytes = rest;
+    Ok(flag != 0)
+}
+
+/// Reads back what [`write_option`] wrote, using `f` if something is present
+pub fn read_option<T>(
+    bytes: &mut &[u8],
+    f: impl FnOnce(&mut &[u8]) -> Result<T, StateError>,
+) -> Result<Option<T>, StateError> {
+    if read_flag(bytes)? {
+        f(bytes).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let write = |out: &mut Vec<u8>, value| {
+            write_u64(out, value);
+            Ok::<_, StateError>(())
+        };
+        let mut out = vec![];
+        write_u64(&mut out, 0x0102_0304_0506_0708);
+        write_option(&mut out, Some(9), write).unwrap();
+        write_option(&mut out, None, write).unwrap();
+        write_indices(&mut out, &[2, 0]);
+        write_u64(&mut out, 3);
+
+        let mut bytes = out.as_slice();
+        check!(read_u64(&mut bytes) == Ok(0x0102_0304_0506_0708));
+        check!(read_option(&mut bytes, read_u64) == Ok(Some(9)));
+        check!(read_flag(&mut bytes) == Ok(false));
+        check!(read_indices(&mut bytes, 3, "Test") == Ok(vec![2, 0]));
+        check!(read_index(&mut bytes, 3, "Test") == Err(StateError::Invalid("Test")));
+        check!(bytes.is_empty());
+        check!(read_u64(&mut bytes) == Err(StateError::Truncated));
+    }
+}
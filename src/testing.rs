@@ -0,0 +1,248 @@
+//! Canned leaf nodes for testing composite wiring
+//!
+//! None of these touch the context, so they work with any context type,
+//! including `()`.
+
+use crate::state::{self, StateError};
+use crate::{BehaviorArc, BehaviorNode, BehaviorRunner, NodeResult, TraceEntry};
+use std::hash::Hasher;
+use std::sync::Arc;
+
+/// Succeeds immediately
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AlwaysSucceed;
+
+impl<B: 'static> BehaviorNode<B> for AlwaysSucceed {
+    fn tick(self: Arc<Self>, _context: &mut B) -> NodeResult<B> {
+        NodeResult::Success
+    }
+
+    fn success_probability(&self, _blackboard: &B) -> f32 {
+        1.0
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other.downcast_ref::<Self>().is_some()
+    }
+}
+
+/// Fails immediately
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AlwaysFail;
+
+impl<B: 'static> BehaviorNode<B> for AlwaysFail {
+    fn tick(self: Arc<Self>, _context: &mut B) -> NodeResult<B> {
+        NodeResult::Failure
+    }
+
+    fn success_probability(&self, _blackboard: &B) -> f32 {
+        0.0
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other.downcast_ref::<Self>().is_some()
+    }
+}
+
+/// Runs for the given number of ticks, then succeeds
+#[derive(Debug, Clone, Copy)]
+pub struct SucceedAfter(pub u32);
+
+impl<B: 'static> BehaviorNode<B> for SucceedAfter {
+    fn tick(self: Arc<Self>, _context: &mut B) -> NodeResult<B> {
+        if self.0 > 0 {
+            NodeResult::Running(SucceedAfter(self.0 - 1).arc())
+        } else {
+            NodeResult::Success
+        }
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| self.0 == other.0)
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_u32(self.0);
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_u64(out, self.0 as u64);
+        Ok(())
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let remaining = state::read_u64(bytes)?;
+        let remaining =
+            u32::try_from(remaining).map_err(|_| StateError::Invalid("SucceedAfter"))?;
+        Ok(SucceedAfter(remaining).arc())
+    }
+}
+
+/// Runs for the given number of ticks, then fails
+#[derive(Debug, Clone, Copy)]
+pub struct FailAfter(pub u32);
+
+impl<B: 'static> BehaviorNode<B> for FailAfter {
+    fn tick(self: Arc<Self>, _context: &mut B) -> NodeResult<B> {
+        if self.0 > 0 {
+            NodeResult::Running(FailAfter(self.0 - 1).arc())
+        } else {
+            NodeResult::Failure
+        }
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn value_eq(&self, other: &dyn BehaviorNode<B>) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .is_some_and(|other| self.0 == other.0)
+    }
+
+    fn hash_params(&self, state: &mut dyn Hasher) {
+        state.write_u32(self.0);
+    }
+
+    fn serialize_state(&self, out: &mut Vec<u8>) -> Result<(), StateError> {
+        state::write_u64(out, self.0 as u64);
+        Ok(())
+    }
+
+    fn deserialize_state(&self, bytes: &mut &[u8]) -> Result<BehaviorArc<B>, StateError> {
+        let remaining = state::read_u64(bytes)?;
+        let remaining = u32::try_from(remaining).map_err(|_| StateError::Invalid("FailAfter"))?;
+        Ok(FailAfter(remaining).arc())
+    }
+}
+
+/// Re-runs `tree` against a fresh context and checks it follows `trace`
+///
+/// Proceeds once per entry with tracing on, then asserts the new trace
+/// matches. A mismatch means the tree (or something it reads) isn't
+/// deterministic. If [`BehaviorNode::is_deterministic`] already says as
+/// much, the panic message points that out.
+///
+/// # Panics
+///
+/// Panics at the first proceed that went differently.
+pub fn replay<B>(tree: BehaviorArc<B>, trace: &[TraceEntry], context: impl FnOnce() -> B) {
+    let hint = if tree.is_deterministic() {
+        ""
+    } else {
+        " (the tree has nondeterministic nodes)"
+    };
+    let mut context = context();
+    let mut runner = BehaviorRunner::new(tree).with_trace();
+    for expected in trace {
+        runner.proceed(&mut context);
+        let actual = runner.trace().and_then(|trace| trace.last());
+        assert_eq!(
+            actual,
+            Some(expected),
+            "replay diverged at proceed {}{hint}",
+            expected.proceed
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::composite::{Selector, Sequence};
+
+    fn run(tree: BehaviorArc<()>) -> (usize, bool) {
+        let mut runner = BehaviorRunner::new(tree);
+        let mut proceeds = 1;
+        loop {
+            if let Some(res) = runner.proceed(&mut ()) {
+                return (proceeds, res);
+            }
+            proceeds += 1;
+        }
+    }
+
+    #[test]
+    fn canned_leaves() {
+        check!(run(AlwaysSucceed.arc()) == (1, true));
+        check!(run(AlwaysFail.arc()) == (1, false));
+        check!(run(SucceedAfter(0).arc()) == (1, true));
+        check!(run(SucceedAfter(3).arc()) == (4, true));
+        check!(run(FailAfter(0).arc()) == (1, false));
+        check!(run(FailAfter(2).arc()) == (3, false));
+    }
+
+    #[test]
+    fn canned_leaves_in_composites() {
+        check!(run(Sequence::new([SucceedAfter(1).arc(), AlwaysSucceed.arc()]).arc()) == (2, true));
+        check!(run(Sequence::new([SucceedAfter(1).arc(), FailAfter(2).arc()]).arc()) == (4, false));
+        check!(run(Selector::new([FailAfter(1).arc(), AlwaysFail.arc()]).arc()) == (2, false));
+        check!(run(Selector::new([AlwaysFail.arc(), SucceedAfter(2).arc()]).arc()) == (3, true));
+    }
+
+    fn traced_tree() -> BehaviorArc<bool> {
+        Sequence::new([
+            SucceedAfter(1).arc(),
+            Selector::new([FailAfter(1).arc(), Coin.arc()]).arc(),
+        ])
+        .arc()
+    }
+
+    /// Succeeds or fails depending on the context
+    #[derive(Debug)]
+    struct Coin;
+
+    impl BehaviorNode<bool> for Coin {
+        fn tick(self: Arc<Self>, heads: &mut bool) -> NodeResult<bool> {
+            if *heads {
+                NodeResult::Success
+            } else {
+                NodeResult::Failure
+            }
+        }
+    }
+
+    fn record(heads: bool) -> Vec<TraceEntry> {
+        let mut runner = BehaviorRunner::new(traced_tree()).with_trace();
+        let mut context = heads;
+        while runner.proceed(&mut context).is_none() {}
+        runner.trace().unwrap().to_vec()
+    }
+
+    #[test]
+    fn traces_replay() {
+        let trace = record(true);
+        let active = trace
+            .iter()
+            .map(|entry| entry.active.len())
+            .collect::<Vec<_>>();
+        check!(active == vec![2, 3, 0]);
+        check!(trace[1].active.last() == Some(&crate::coverage::NodeId(vec![1, 0])));
+        check!(trace.last().unwrap().result == Some(true));
+
+        replay(traced_tree(), &trace, || true);
+    }
+
+    #[test]
+    #[should_panic(expected = "replay diverged at proceed 2")]
+    fn replay_catches_divergence() {
+        replay(traced_tree(), &record(true), || false);
+    }
+}
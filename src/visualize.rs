@@ -0,0 +1,206 @@
+//! Rendering trees for humans
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::coverage::NodeId;
+use crate::{tree_hash, BehaviorArc, BehaviorRunner};
+
+/// Renders a tree as a Graphviz `digraph`
+///
+/// Node identifiers come from [`tree_hash`], so the same tree always renders
+/// to the same text and two versions of a tree can be diffed line by line.
+/// A subtree that appears more than once gets a numbered suffix on its
+/// later appearances.
+pub fn to_dot<B>(root: &BehaviorArc<B>) -> String {
+    let mut out = String::from("digraph {\n");
+    let mut seen = HashMap::new();
+    write_dot(root, &mut seen, &mut out);
+    out.push_str("}\n");
+    out
+}
+
+fn write_dot<B>(node: &BehaviorArc<B>, seen: &mut HashMap<u64, usize>, out: &mut String) -> String {
+    let hash = tree_hash(node);
+    let count = seen.entry(hash).or_default();
+    *count += 1;
+    let id = match *count {
+        1 => format!("n{hash:016x}"),
+        n => format!("n{hash:016x}_{n}"),
+    };
+    let _ = writeln!(out, "    {id} [label=\"{}\"];", node.name());
+    if node.is_leaf() {
+        return id;
+    }
+    for (idx, child) in node.children().iter().enumerate() {
+        let child_id = write_dot(child, seen, out);
+        match node.child_label(idx) {
+            Some(label) => {
+                let _ = writeln!(out, "    {id} -> {child_id} [label=\"{label}\"];");
+            }
+            None => {
+                let _ = writeln!(out, "    {id} -> {child_id};");
+            }
+        }
+    }
+    id
+}
+
+/// Renders a tree as indented text, one node per line
+pub fn to_text<B>(root: &BehaviorArc<B>) -> String {
+    let mut out = String::new();
+    write_text(root, NodeId::root(), 0, None, None, &mut out);
+    out
+}
+
+/// Renders a runner's tree as indented text, marking the active spine
+///
+/// Nodes the runner is paused inside are prefixed with `*`. If a running
+/// node can't tell which of its children it is waiting on (for example a
+/// parallel that has already dropped its finished children), marking stops
+/// at that node.
+pub fn debug_runner<B>(runner: &BehaviorRunner<B>) -> String {
+    let mut out = String::new();
+    let active = runner.active_node_ids();
+    write_text(
+        &runner.tree,
+        NodeId::root(),
+        0,
+        None,
+        Some(&active),
+        &mut out,
+    );
+    out
+}
+
+fn write_text<B>(
+    node: &BehaviorArc<B>,
+    id: NodeId,
+    depth: usize,
+    label: Option<&str>,
+    active: Option<&[NodeId]>,
+    out: &mut String,
+) {
+    if let Some(active) = active {
+        out.push_str(if active.contains(&id) { "* " } else { "  " });
+    }
+    let _ = write!(out, "{:indent$}", "", indent = depth * 2);
+    if let Some(label) = label {
+        let _ = write!(out, "{label}: ");
+    }
+    let _ = writeln!(out, "{}", node.name());
+    if node.is_leaf() {
+        return;
+    }
+    for (idx, child) in node.children().iter().enumerate() {
+        let label = node.child_label(idx);
+        write_text(child, id.child(idx), depth + 1, label, active, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{
+        composite::{Inverter, LimitedRepeated, Selector, Sequence},
+        testing::{AlwaysFail, AlwaysSucceed, SucceedAfter},
+        BehaviorNode, BehaviorRunner,
+    };
+
+    fn tree(wait: u32) -> BehaviorArc<()> {
+        Selector::new([
+            Sequence::new([AlwaysFail.arc(), SucceedAfter(wait).arc()]).arc(),
+            LimitedRepeated::new(2, Inverter::new(AlwaysSucceed.arc()).arc()).arc(),
+        ])
+        .arc()
+    }
+
+    #[test]
+    fn dot_is_stable() {
+        let dot = to_dot(&tree(3));
+        check!(dot == to_dot(&tree(3)));
+        check!(dot.starts_with("digraph {\n"));
+        check!(dot.matches("->").count() == 6);
+        check!(dot.contains("[label=\"LimitedRepeated\"]"));
+    }
+
+    #[test]
+    fn dot_has_an_edge_per_child() {
+        let root = Sequence::new([AlwaysSucceed.arc(), AlwaysFail.arc()]).arc();
+        let dot = to_dot::<()>(&root);
+        let id = |node: &BehaviorArc<()>| format!("n{:016x}", tree_hash(node));
+        check!(dot.contains(&format!("{} -> {};", id(&root), id(&AlwaysSucceed.arc()))));
+        check!(dot.contains(&format!("{} -> {};", id(&root), id(&AlwaysFail.arc()))));
+    }
+
+    #[test]
+    fn dot_changes_only_along_changed_path() {
+        let before = to_dot(&tree(3));
+        let after = to_dot(&tree(4));
+        check!(before != after);
+
+        let lines = |dot: &str| dot.lines().map(String::from).collect::<Vec<_>>();
+        let (before, after) = (lines(&before), lines(&after));
+        check!(before.len() == after.len());
+        let changed = before
+            .iter()
+            .filter(|line| !after.contains(line))
+            .collect::<Vec<_>>();
+        // Only the wait leaf and its ancestors get new ids: their three node
+        // lines change, and so do the four edges leaving or entering them
+        check!(changed.len() == 7);
+        check!(changed.iter().all(|line| !line.contains("LimitedRepeated")));
+    }
+
+    #[test]
+    fn dot_disambiguates_repeated_subtrees() {
+        let leaf = AlwaysSucceed.arc();
+        let dot = to_dot::<()>(&Sequence::new([leaf.clone(), leaf]).arc());
+        let leaf_id = format!("n{:016x}", tree_hash::<()>(&AlwaysSucceed.arc()));
+        check!(dot.contains(&format!("    {leaf_id} [label")));
+        check!(dot.contains(&format!("    {leaf_id}_2 [label")));
+    }
+
+    #[test]
+    fn text_lists_nodes_by_depth() {
+        check!(
+            to_text(&tree(3))
+                == "Selector\n  Sequence\n    AlwaysFail\n    SucceedAfter\n  LimitedRepeated\n    Inverter\n      AlwaysSucceed\n"
+        );
+    }
+
+    #[test]
+    fn debug_runner_marks_active_spine() {
+        let mut runner = BehaviorRunner::new(
+            Selector::new([
+                AlwaysFail.arc(),
+                Sequence::new([
+                    AlwaysSucceed.arc(),
+                    Inverter::new(SucceedAfter(2).arc()).arc(),
+                ])
+                .arc(),
+                AlwaysSucceed.arc(),
+            ])
+            .arc(),
+        );
+        check!(!debug_runner(&runner).contains('*'));
+
+        check!(runner.proceed(&mut ()) == None);
+        check!(
+            debug_runner(&runner)
+                == [
+                    "* Selector",
+                    "    AlwaysFail",
+                    "*   Sequence",
+                    "      AlwaysSucceed",
+                    "*     Inverter",
+                    "*       SucceedAfter",
+                    "    AlwaysSucceed",
+                    "",
+                ]
+                .join("\n")
+        );
+    }
+}